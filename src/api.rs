@@ -1,16 +1,23 @@
 //! ClickUp API client for fetching tasks
 
-use crate::models::Task;
+use crate::models::{Assignee, Checklist, ChecklistItem, Comment, StatusChange, Task};
 use anyhow::{Context, Result};
+use chrono::{Local, TimeZone};
 use reqwest::Client;
 use serde::Deserialize;
+use std::time::Duration;
 
+/// ClickUp REST API root used unless overridden
 const CLICKUP_API_BASE: &str = "https://api.clickup.com/api/v2";
 
 /// ClickUp API client
 pub struct ClickUpClient {
     client: Client,
     api_token: String,
+    /// API root URL, without a trailing slash
+    base_url: String,
+    /// Per-request timeout (for error messages)
+    timeout: Duration,
 }
 
 /// Response from ClickUp task search
@@ -26,13 +33,21 @@ struct ClickUpTask {
     name: String,
     status: ClickUpStatus,
     list: ClickUpList,
+    /// Workspace (team) the task belongs to
+    team_id: Option<String>,
     due_date: Option<String>,
+    /// Creation time (Unix timestamp in ms, as a string)
+    date_created: Option<String>,
+    /// Last update time (Unix timestamp in ms, as a string)
+    date_updated: Option<String>,
     priority: Option<ClickUpPriority>,
     url: String,
     #[serde(default)]
     tags: Vec<ClickUpTag>,
-    /// Task description/content
+    /// Task description/content (plain text)
     text_content: Option<String>,
+    /// Task description as Markdown (requires `include_markdown_description`)
+    markdown_description: Option<String>,
     /// Custom task type ID (e.g., 1020 = Back-End Developer)
     custom_item_id: Option<u32>,
     /// Custom task ID (e.g., "PROJ-123")
@@ -42,6 +57,69 @@ struct ClickUpTask {
     /// Assignees
     #[serde(default)]
     assignees: Vec<ClickUpAssignee>,
+    /// Watchers (followers)
+    #[serde(default)]
+    watchers: Vec<ClickUpAssignee>,
+    /// Custom field values (a "users" field links Person tasks to a user)
+    #[serde(default)]
+    custom_fields: Vec<ClickUpCustomField>,
+    #[serde(default)]
+    checklists: Vec<ClickUpChecklist>,
+    /// Child tasks (only when requested with `subtasks=true`)
+    #[serde(default)]
+    subtasks: Vec<ClickUpTask>,
+}
+
+/// Response from ClickUp task comments
+#[derive(Debug, Deserialize)]
+struct CommentsResponse {
+    comments: Vec<ClickUpComment>,
+}
+
+/// Raw comment from ClickUp API
+#[derive(Debug, Deserialize)]
+struct ClickUpComment {
+    #[serde(default)]
+    comment_text: String,
+    user: Option<ClickUpAssignee>,
+}
+
+/// Response from ClickUp task time-in-status (needs the "Total time in Status" ClickApp)
+#[derive(Debug, Deserialize)]
+struct TimeInStatusResponse {
+    #[serde(default)]
+    status_history: Vec<ClickUpStatusTime>,
+}
+
+/// A status the task has been in, from time-in-status
+#[derive(Debug, Deserialize)]
+struct ClickUpStatusTime {
+    status: String,
+    total_time: Option<ClickUpTotalTime>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpTotalTime {
+    since: Option<String>,
+}
+
+impl TimeInStatusResponse {
+    /// Statuses the task entered, oldest first
+    fn into_changes(self) -> Vec<StatusChange> {
+        let mut changes: Vec<StatusChange> = self
+            .status_history
+            .into_iter()
+            .map(|s| StatusChange {
+                status: s.status,
+                since: s
+                    .total_time
+                    .and_then(|t| t.since)
+                    .and_then(|d| d.parse().ok()),
+            })
+            .collect();
+        changes.sort_by_key(|c| c.since);
+        changes
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -51,6 +129,7 @@ struct ClickUpStatus {
 
 #[derive(Debug, Deserialize)]
 struct ClickUpList {
+    id: Option<String>,
     name: String,
 }
 
@@ -64,9 +143,159 @@ struct ClickUpTag {
     name: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct ClickUpCustomField {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    field_type: String,
+    /// Field definition (dropdown and label options)
+    #[serde(default)]
+    type_config: ClickUpFieldConfig,
+    value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ClickUpFieldConfig {
+    #[serde(default)]
+    options: Vec<ClickUpFieldOption>,
+}
+
+/// A dropdown option (`name`) or label (`label`)
+#[derive(Debug, Deserialize)]
+struct ClickUpFieldOption {
+    id: String,
+    name: Option<String>,
+    label: Option<String>,
+    orderindex: Option<serde_json::Value>,
+}
+
+impl ClickUpFieldOption {
+    fn display_name(&self) -> Option<&str> {
+        self.name.as_deref().or(self.label.as_deref())
+    }
+}
+
+impl ClickUpCustomField {
+    /// First user ID in a "users" field's value
+    fn first_user_id(&self) -> Option<u64> {
+        if self.field_type != "users" {
+            return None;
+        }
+        self.value
+            .as_ref()?
+            .as_array()?
+            .first()?
+            .get("id")?
+            .as_u64()
+    }
+
+    /// The value as display text, for the common field types (`None` if empty
+    /// or of a type that isn't shown)
+    ///
+    /// Dropdown values are an option's order index (or ID in some payloads) and
+    /// are mapped to the option's name; dates are ms timestamps.
+    fn display_value(&self) -> Option<String> {
+        use serde_json::Value;
+
+        let value = self.value.as_ref()?;
+        let text = match self.field_type.as_str() {
+            "text" | "short_text" | "url" | "email" | "phone" => value.as_str()?.to_string(),
+            "number" | "currency" | "emoji" => match value {
+                Value::Number(n) => n.to_string(),
+                Value::String(s) => s.clone(),
+                _ => return None,
+            },
+            "drop_down" => {
+                let option = self.type_config.options.iter().find(|o| {
+                    o.orderindex.as_ref().is_some_and(|i| same_scalar(i, value))
+                        || Value::String(o.id.clone()) == *value
+                })?;
+                option.display_name()?.to_string()
+            }
+            "labels" => {
+                let ids = value.as_array()?;
+                let names: Vec<&str> = ids
+                    .iter()
+                    .filter_map(|id| {
+                        self.type_config
+                            .options
+                            .iter()
+                            .find(|o| Some(o.id.as_str()) == id.as_str())?
+                            .display_name()
+                    })
+                    .collect();
+                names.join(", ")
+            }
+            "date" => {
+                let ms = match value {
+                    Value::Number(n) => n.as_i64()?,
+                    Value::String(s) => s.parse().ok()?,
+                    _ => return None,
+                };
+                Local
+                    .timestamp_millis_opt(ms)
+                    .single()?
+                    .format("%Y-%m-%d")
+                    .to_string()
+            }
+            "checkbox" => match value {
+                Value::Bool(true) => "Yes".to_string(),
+                Value::String(s) if s == "true" => "Yes".to_string(),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let text = text.trim().to_string();
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+/// Whether two JSON scalars are equal, treating `2` and `"2"` alike
+fn same_scalar(a: &serde_json::Value, b: &serde_json::Value) -> bool {
+    let text = |v: &serde_json::Value| match v {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+    text(a).is_some_and(|a| Some(a) == text(b))
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpChecklist {
+    id: String,
+    name: String,
+    #[serde(default)]
+    items: Vec<ClickUpChecklistItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpChecklistItem {
+    id: String,
+    name: String,
+    #[serde(default)]
+    resolved: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct ClickUpAssignee {
     id: u64,
+    username: Option<String>,
+    initials: Option<String>,
+}
+
+/// Response from user endpoint
+#[derive(Debug, Deserialize)]
+struct UserResponse {
+    user: AuthorizedUser,
+}
+
+/// The user that owns the API token
+#[derive(Debug, Deserialize)]
+pub struct AuthorizedUser {
+    pub id: u64,
+    #[serde(default)]
+    pub username: Option<String>,
 }
 
 /// Response from team endpoint
@@ -75,25 +304,46 @@ struct TeamsResponse {
     teams: Vec<Team>,
 }
 
-#[derive(Debug, Deserialize)]
-struct Team {
-    id: String,
-    #[allow(dead_code)]
-    name: String,
+/// A ClickUp workspace (called a team in the API)
+#[derive(Debug, Clone, Deserialize)]
+pub struct Team {
+    pub id: String,
+    pub name: String,
 }
 
 impl ClickUpClient {
-    /// Create a new ClickUp client
-    pub fn new(api_token: String) -> Self {
-        Self {
-            client: Client::new(),
+    /// Create a new ClickUp client whose requests give up after `timeout`
+    pub fn new(api_token: String, timeout: Duration) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self {
+            client,
             api_token,
+            base_url: CLICKUP_API_BASE.to_string(),
+            timeout,
+        })
+    }
+
+    /// Send requests to another API root (e.g. a proxy or a local mock server)
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Describe a failed request, calling out timeouts explicitly
+    fn request_error(&self, error: reqwest::Error, context: &'static str) -> anyhow::Error {
+        if error.is_timeout() {
+            anyhow::anyhow!("Request timed out after {}s", self.timeout.as_secs())
+        } else {
+            anyhow::Error::new(error).context(context)
         }
     }
 
-    /// Get the team/workspace ID (needed for task queries)
-    pub async fn get_team_id(&self) -> Result<String> {
-        let url = format!("{}/team", CLICKUP_API_BASE);
+    /// Get the user that owns the API token
+    pub async fn get_authorized_user(&self) -> Result<AuthorizedUser> {
+        let url = format!("{}/user", self.base_url);
 
         let response = self
             .client
@@ -101,7 +351,7 @@ impl ClickUpClient {
             .header("Authorization", &self.api_token)
             .send()
             .await
-            .context("Failed to fetch teams")?;
+            .map_err(|e| self.request_error(e, "Failed to fetch user"))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -109,36 +359,25 @@ impl ClickUpClient {
             anyhow::bail!("ClickUp API error ({}): {}", status, body);
         }
 
-        let teams: TeamsResponse = response
+        let user: UserResponse = response
             .json()
             .await
-            .context("Failed to parse teams response")?;
+            .map_err(|e| self.request_error(e, "Failed to parse user response"))?;
 
-        teams
-            .teams
-            .first()
-            .map(|t| t.id.clone())
-            .context("No teams found in workspace")
+        Ok(user.user)
     }
 
-    /// Fetch all tasks assigned to a user, including parent tasks of subtasks
-    pub async fn fetch_tasks(&self, team_id: &str, user_id: &str) -> Result<Vec<Task>> {
-        use std::collections::HashSet;
-
-        let url = format!("{}/team/{}/task", CLICKUP_API_BASE, team_id);
+    /// Get the workspaces the token can see
+    pub async fn get_teams(&self) -> Result<Vec<Team>> {
+        let url = format!("{}/team", self.base_url);
 
         let response = self
             .client
             .get(&url)
             .header("Authorization", &self.api_token)
-            .query(&[
-                ("assignees[]", user_id),
-                ("include_closed", "true"),
-                ("subtasks", "true"),
-            ])
             .send()
             .await
-            .context("Failed to fetch tasks")?;
+            .map_err(|e| self.request_error(e, "Failed to fetch teams"))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -146,19 +385,102 @@ impl ClickUpClient {
             anyhow::bail!("ClickUp API error ({}): {}", status, body);
         }
 
-        let tasks_response: TasksResponse = response
+        let teams: TeamsResponse = response
             .json()
             .await
-            .context("Failed to parse tasks response")?;
+            .map_err(|e| self.request_error(e, "Failed to parse teams response"))?;
 
-        let mut tasks: Vec<Task> = tasks_response
-            .tasks
+        Ok(teams.teams)
+    }
+
+    /// Get the team/workspace ID (needed for task queries)
+    ///
+    /// Uses `workspace_id` when given, otherwise the first workspace the token
+    /// can see.
+    pub async fn get_team_id(&self, workspace_id: Option<&str>) -> Result<String> {
+        if let Some(id) = workspace_id {
+            return Ok(id.to_string());
+        }
+        self.get_teams()
+            .await?
             .into_iter()
-            .map(|t| self.convert_task(t))
-            .collect();
+            .next()
+            .map(|t| t.id)
+            .context("No teams found in workspace")
+    }
+
+    /// Start a running time entry on a task
+    pub async fn start_timer(&self, team_id: &str, task_id: &str) -> Result<()> {
+        let url = format!("{}/team/{}/time_entries/start", self.base_url, team_id);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", &self.api_token)
+            .json(&serde_json::json!({ "tid": task_id }))
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to start timer"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        Ok(())
+    }
+
+    /// Stop the running time entry, recording its duration
+    pub async fn stop_timer(&self, team_id: &str) -> Result<()> {
+        let url = format!("{}/team/{}/time_entries/stop", self.base_url, team_id);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", &self.api_token)
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to stop timer"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        Ok(())
+    }
+
+    /// Fetch all tasks assigned to or watched by a user, including parent tasks of subtasks
+    ///
+    /// With `updated_after` (ms), only tasks changed since then are fetched.
+    pub async fn fetch_tasks(
+        &self,
+        team_id: &str,
+        user_id: &str,
+        updated_after: Option<i64>,
+    ) -> Result<Vec<Task>> {
+        use std::collections::HashSet;
+
+        let mut tasks = self
+            .search_tasks(team_id, "assignees[]", user_id, updated_after)
+            .await?;
 
         // Collect IDs of tasks we already have
-        let existing_ids: HashSet<String> = tasks.iter().map(|t| t.id.clone()).collect();
+        let mut existing_ids: HashSet<String> = tasks.iter().map(|t| t.id.clone()).collect();
+
+        // Add watched tasks the user isn't assigned to (best effort, like parents below)
+        if let Ok(watched) = self
+            .search_tasks(team_id, "watchers[]", user_id, updated_after)
+            .await
+        {
+            for task in watched {
+                if existing_ids.insert(task.id.clone()) {
+                    tasks.push(task);
+                }
+            }
+        }
 
         // Find parent IDs that we don't have yet
         let missing_parent_ids: Vec<String> = tasks
@@ -180,17 +502,65 @@ impl ClickUpClient {
         Ok(tasks)
     }
 
+    /// Fetch the team's tasks matching one user filter (e.g. `assignees[]`)
+    async fn search_tasks(
+        &self,
+        team_id: &str,
+        filter: &str,
+        user_id: &str,
+        updated_after: Option<i64>,
+    ) -> Result<Vec<Task>> {
+        let url = format!("{}/team/{}/task", self.base_url, team_id);
+        let updated_after = updated_after.map(|ms| ms.to_string());
+        let mut query = vec![
+            (filter, user_id),
+            ("include_closed", "true"),
+            ("subtasks", "true"),
+            ("include_markdown_description", "true"),
+        ];
+        if let Some(since) = &updated_after {
+            query.push(("date_updated_gt", since));
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.api_token)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to fetch tasks"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        let tasks_response: TasksResponse = response
+            .json()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to parse tasks response"))?;
+
+        Ok(tasks_response
+            .tasks
+            .into_iter()
+            .map(|t| self.convert_task(t))
+            .collect())
+    }
+
     /// Fetch a single task by ID
     pub async fn fetch_task_by_id(&self, task_id: &str) -> Result<Task> {
-        let url = format!("{}/task/{}", CLICKUP_API_BASE, task_id);
+        let url = format!("{}/task/{}", self.base_url, task_id);
 
         let response = self
             .client
             .get(&url)
             .header("Authorization", &self.api_token)
+            .query(&[("include_markdown_description", "true")])
             .send()
             .await
-            .context("Failed to fetch task")?;
+            .map_err(|e| self.request_error(e, "Failed to fetch task"))?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -201,11 +571,208 @@ impl ClickUpClient {
         let task: ClickUpTask = response
             .json()
             .await
-            .context("Failed to parse task response")?;
+            .map_err(|e| self.request_error(e, "Failed to parse task response"))?;
 
         Ok(self.convert_task(task))
     }
 
+    /// Fetch every subtask under a task, however deeply nested
+    pub async fn fetch_subtasks(&self, task_id: &str) -> Result<Vec<Task>> {
+        let url = format!("{}/task/{}", self.base_url, task_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.api_token)
+            .query(&[
+                ("subtasks", "true"),
+                ("include_markdown_description", "true"),
+            ])
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to fetch subtasks"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        let task: ClickUpTask = response
+            .json()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to parse task response"))?;
+
+        // Flatten the nested subtasks
+        let mut pending = task.subtasks;
+        let mut subtasks = Vec::new();
+        while let Some(mut subtask) = pending.pop() {
+            pending.append(&mut subtask.subtasks);
+            subtasks.push(self.convert_task(subtask));
+        }
+        Ok(subtasks)
+    }
+
+    /// Fetch a task's comments (newest first)
+    pub async fn fetch_comments(&self, task_id: &str) -> Result<Vec<Comment>> {
+        let url = format!("{}/task/{}/comment", self.base_url, task_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.api_token)
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to fetch comments"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        let comments_response: CommentsResponse = response
+            .json()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to parse comments response"))?;
+
+        Ok(comments_response
+            .comments
+            .into_iter()
+            .map(|c| Comment {
+                author: c
+                    .user
+                    .and_then(|u| u.username)
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                text: c.comment_text.trim().to_string(),
+            })
+            .collect())
+    }
+
+    /// Fetch the statuses a task has been in, oldest first
+    ///
+    /// Fails if the workspace doesn't expose time in status.
+    pub async fn fetch_task_history(&self, task_id: &str) -> Result<Vec<StatusChange>> {
+        let url = format!("{}/task/{}/time_in_status", self.base_url, task_id);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.api_token)
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to fetch status history"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        let history: TimeInStatusResponse = response
+            .json()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to parse status history response"))?;
+
+        Ok(history.into_changes())
+    }
+
+    /// Set a task's priority (1=Urgent .. 4=Low), or clear it with `None`
+    pub async fn set_priority(&self, task_id: &str, priority: Option<u8>) -> Result<()> {
+        let url = format!("{}/task/{}", self.base_url, task_id);
+
+        let response = self
+            .client
+            .put(&url)
+            .header("Authorization", &self.api_token)
+            .json(&serde_json::json!({ "priority": priority }))
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to update priority"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        Ok(())
+    }
+
+    /// Check off (or un-check) an item on a task checklist
+    pub async fn set_checklist_item(
+        &self,
+        checklist_id: &str,
+        item_id: &str,
+        resolved: bool,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/checklist/{}/checklist_item/{}",
+            self.base_url, checklist_id, item_id
+        );
+
+        let response = self
+            .client
+            .put(&url)
+            .header("Authorization", &self.api_token)
+            .json(&serde_json::json!({ "resolved": resolved }))
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to update checklist item"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        Ok(())
+    }
+
+    /// Add and remove assignees (by user ID) on a task
+    pub async fn update_assignees(&self, task_id: &str, add: &[u64], rem: &[u64]) -> Result<()> {
+        let url = format!("{}/task/{}", self.base_url, task_id);
+
+        let response = self
+            .client
+            .put(&url)
+            .header("Authorization", &self.api_token)
+            .json(&serde_json::json!({ "assignees": { "add": add, "rem": rem } }))
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to update assignees"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        Ok(())
+    }
+
+    /// Post a comment on a task
+    pub async fn post_comment(&self, task_id: &str, text: &str) -> Result<()> {
+        let url = format!("{}/task/{}/comment", self.base_url, task_id);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", &self.api_token)
+            .json(&serde_json::json!({ "comment_text": text, "notify_all": false }))
+            .send()
+            .await
+            .map_err(|e| self.request_error(e, "Failed to post comment"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ClickUp API error ({}): {}", status, body);
+        }
+
+        Ok(())
+    }
+
     /// Convert ClickUpTask to Task
     fn convert_task(&self, t: ClickUpTask) -> Task {
         Task {
@@ -213,15 +780,186 @@ impl ClickUpClient {
             name: t.name,
             status: t.status.status,
             list_name: t.list.name,
+            list_id: t.list.id,
+            team_id: t.team_id,
             due_date: t.due_date.and_then(|d| d.parse().ok()),
+            date_created: t.date_created.and_then(|d| d.parse().ok()),
+            date_updated: t.date_updated.and_then(|d| d.parse().ok()),
             priority: t.priority.and_then(|p| p.id.parse().ok()),
             url: t.url,
             tags: t.tags.into_iter().map(|t| t.name).collect(),
-            description: t.text_content,
+            description: t
+                .markdown_description
+                .filter(|d| !d.is_empty())
+                .or(t.text_content),
             custom_item_id: t.custom_item_id,
             custom_id: t.custom_id,
             parent_id: t.parent,
-            assignee_ids: t.assignees.into_iter().map(|a| a.id).collect(),
+            person_id: t
+                .custom_fields
+                .iter()
+                .find_map(ClickUpCustomField::first_user_id),
+            custom_fields: t
+                .custom_fields
+                .iter()
+                .filter_map(|f| Some((f.name.clone(), f.display_value()?)))
+                .collect(),
+            assignee_ids: t.assignees.iter().map(|a| a.id).collect(),
+            watcher_ids: t.watchers.iter().map(|w| w.id).collect(),
+            assignees: t
+                .assignees
+                .into_iter()
+                .map(|a| Assignee {
+                    id: a.id,
+                    username: a.username,
+                    initials: a.initials,
+                })
+                .collect(),
+            checklists: t
+                .checklists
+                .into_iter()
+                .map(|c| Checklist {
+                    id: c.id,
+                    name: c.name,
+                    items: c
+                        .items
+                        .into_iter()
+                        .map(|i| ChecklistItem {
+                            id: i.id,
+                            name: i.name,
+                            resolved: i.resolved,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            fetched_as_subtask: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(json: serde_json::Value) -> ClickUpCustomField {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_dropdown_maps_to_option_name() {
+        let sprint = serde_json::json!({
+            "name": "Sprint",
+            "type": "drop_down",
+            "type_config": { "options": [
+                { "id": "a1", "name": "Sprint 1", "orderindex": 0 },
+                { "id": "b2", "name": "Sprint 2", "orderindex": 1 }
+            ] },
+            "value": 1
+        });
+        assert_eq!(field(sprint).display_value().as_deref(), Some("Sprint 2"));
+
+        let by_id = serde_json::json!({
+            "name": "Sprint",
+            "type": "drop_down",
+            "type_config": { "options": [{ "id": "a1", "name": "Sprint 1", "orderindex": "0" }] },
+            "value": "a1"
+        });
+        assert_eq!(field(by_id).display_value().as_deref(), Some("Sprint 1"));
+    }
+
+    #[test]
+    fn test_empty_and_unknown_fields_are_skipped() {
+        let points = serde_json::json!({ "name": "Story Points", "type": "number", "value": "3" });
+        assert_eq!(field(points).display_value().as_deref(), Some("3"));
+
+        let empty = serde_json::json!({ "name": "Notes", "type": "text", "value": "  " });
+        assert_eq!(field(empty).display_value(), None);
+        let unset = serde_json::json!({ "name": "Notes", "type": "text" });
+        assert_eq!(field(unset).display_value(), None);
+        let users = serde_json::json!({ "name": "Owner", "type": "users", "value": [{ "id": 1 }] });
+        assert_eq!(field(users).display_value(), None);
+    }
+
+    #[tokio::test]
+    async fn test_base_url_routes_requests() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        // One-shot server that records the request line and returns no comments
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut lines = BufReader::new(&stream).lines();
+            let request_line = lines.next().unwrap().unwrap();
+            // Drain the headers before answering
+            for line in lines.by_ref() {
+                if line.unwrap().is_empty() {
+                    break;
+                }
+            }
+            let body = r#"{"comments":[]}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request_line
+        });
+
+        // The trailing slash is trimmed so paths don't double up
+        let client = ClickUpClient::new("token".to_string(), Duration::from_secs(5))
+            .unwrap()
+            .with_base_url(&format!("http://127.0.0.1:{}/api/v2/", port));
+        assert!(client.fetch_comments("abc").await.unwrap().is_empty());
+        assert_eq!(
+            server.join().unwrap(),
+            "GET /api/v2/task/abc/comment HTTP/1.1"
+        );
+    }
+
+    #[test]
+    fn test_time_in_status_sorted_oldest_first() {
+        let history: TimeInStatusResponse = serde_json::from_value(serde_json::json!({
+            "current_status": {
+                "status": "blocked",
+                "total_time": { "by_minute": 240, "since": "1700014400000" }
+            },
+            "status_history": [
+                {
+                    "status": "in progress",
+                    "type": "custom",
+                    "total_time": { "by_minute": 2640, "since": "1700000000000" }
+                },
+                {
+                    "status": "blocked",
+                    "type": "custom",
+                    "total_time": { "by_minute": 240, "since": "1700014400000" }
+                },
+                {
+                    "status": "to do",
+                    "type": "open",
+                    "total_time": { "by_minute": 60, "since": "1699990000000" }
+                },
+                { "status": "review", "type": "custom", "total_time": { "by_minute": 0 } }
+            ]
+        }))
+        .unwrap();
+
+        let change = |status: &str, since: Option<i64>| StatusChange {
+            status: status.to_string(),
+            since,
+        };
+        assert_eq!(
+            history.into_changes(),
+            vec![
+                change("review", None),
+                change("to do", Some(1699990000000)),
+                change("in progress", Some(1700000000000)),
+                change("blocked", Some(1700014400000)),
+            ]
+        );
+    }
+}
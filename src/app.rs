@@ -1,10 +1,19 @@
 //! TUI application state and logic
 
-use crate::config::Config;
-use crate::models::{DisplayTask, LocalState, Task, TaskGroup};
+use crate::config::{BacklogConfig, Config};
+use crate::keymap::{Action, Keymap};
+use crate::models::{
+    priority_label, Comment, DisplayTask, LocalState, StatusChange, StatusGroups, Task, TaskGroup,
+    TaskOverlay,
+};
+use crate::theme::Theme;
 use anyhow::{Context, Result};
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, TimeZone, Utc, Weekday};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Position, Rect};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
+use std::time::Instant;
 
 /// Input mode for the application
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -13,6 +22,16 @@ pub enum InputMode {
     Normal,
     Search,
     Snooze,
+    TagFilter,
+    JumpToId,
+    ListPicker,
+    PriorityPicker,
+    QrCode,
+    Note,
+    WaitingReason,
+    Comment,
+    Confirm,
+    MessageLog,
     Help,
 }
 
@@ -24,6 +43,331 @@ pub enum FocusedPane {
     Preview,
 }
 
+/// How root tasks are ordered in the task list (subtasks stay under their parents)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    #[default]
+    Priority,
+    DueDate,
+    Name,
+    Status,
+    /// Most recently updated first
+    Updated,
+}
+
+impl SortMode {
+    /// Label shown in the task list title
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortMode::Priority => "priority",
+            SortMode::DueDate => "due date",
+            SortMode::Name => "name",
+            SortMode::Status => "status",
+            SortMode::Updated => "recently updated",
+        }
+    }
+
+    /// Look up a mode by config name (e.g. "priority", "due_date", "updated")
+    pub fn from_name(name: &str) -> Option<SortMode> {
+        let normalized: String = name
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "priority" => Some(SortMode::Priority),
+            "duedate" | "due" => Some(SortMode::DueDate),
+            "name" => Some(SortMode::Name),
+            "status" => Some(SortMode::Status),
+            "updated" | "recent" | "recentlyupdated" => Some(SortMode::Updated),
+            _ => None,
+        }
+    }
+
+    /// Next mode in the cycle
+    pub fn next(&self) -> SortMode {
+        match self {
+            SortMode::Priority => SortMode::DueDate,
+            SortMode::DueDate => SortMode::Name,
+            SortMode::Name => SortMode::Status,
+            SortMode::Status => SortMode::Updated,
+            SortMode::Updated => SortMode::Priority,
+        }
+    }
+
+    /// Compare two tasks under this mode (missing values sort last)
+    pub fn compare(&self, a: &Task, b: &Task) -> std::cmp::Ordering {
+        fn some_first<T: Ord>(a: Option<T>, b: Option<T>) -> std::cmp::Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        }
+
+        match self {
+            SortMode::Priority => some_first(a.priority, b.priority),
+            SortMode::DueDate => some_first(a.due_date, b.due_date),
+            SortMode::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortMode::Status => a.status.to_lowercase().cmp(&b.status.to_lowercase()),
+            SortMode::Updated => some_first(
+                a.date_updated.map(std::cmp::Reverse),
+                b.date_updated.map(std::cmp::Reverse),
+            ),
+        }
+    }
+}
+
+/// Quick snooze durations bound to single keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnoozePreset {
+    /// Until local midnight tomorrow
+    Tomorrow,
+    /// One week from now
+    Week,
+    /// One calendar month from now
+    Month,
+}
+
+impl SnoozePreset {
+    /// Compute the wake-up time for this preset
+    pub fn until(&self) -> Option<DateTime<Utc>> {
+        match self {
+            SnoozePreset::Tomorrow => parse_snooze_input("tomorrow"),
+            SnoozePreset::Week => Some(Utc::now() + Duration::days(7)),
+            SnoozePreset::Month => Utc::now().checked_add_months(Months::new(1)),
+        }
+    }
+}
+
+/// A side effect of a key press that the event loop carries out (network calls)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Fetch tasks changed since the last refresh
+    Refresh,
+    /// Refetch every task
+    FullRefresh,
+    /// Start or stop the time tracker on the selected task
+    ToggleTimer,
+    /// Re-read the config file and apply it
+    ReloadConfig,
+    /// Send a checklist item's new state to ClickUp (already applied locally)
+    SetChecklistItem {
+        task_id: String,
+        checklist_id: String,
+        item_id: String,
+        resolved: bool,
+    },
+    /// Fetch the task's whole subtask tree, including children assigned to others
+    FetchSubtasks { task_id: String },
+    /// Change a task's priority in ClickUp (`None` clears it), once confirmed
+    SetPriority {
+        task_id: String,
+        priority: Option<u8>,
+    },
+    /// Add the user to (or remove them from) a task's assignees in ClickUp, once confirmed
+    SetAssigned { task_id: String, assigned: bool },
+}
+
+/// A mutating command that waits for a yes/no confirmation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingAction {
+    /// Snooze every marked task until the given time
+    Snooze {
+        task_ids: Vec<String>,
+        until: DateTime<Utc>,
+    },
+    /// Change a task's priority in ClickUp (`None` clears it)
+    SetPriority {
+        task_id: String,
+        priority: Option<u8>,
+    },
+    /// Add the user to (or remove them from) a task's assignees in ClickUp
+    SetAssigned { task_id: String, assigned: bool },
+}
+
+/// A pending action and the question shown in the confirmation dialog
+#[derive(Debug, Clone)]
+pub struct Confirmation {
+    pub action: PendingAction,
+    pub prompt: String,
+}
+
+/// What changed between two task refreshes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TaskDiff {
+    /// Tasks that weren't there before
+    pub added: usize,
+    /// Tasks whose status changed
+    pub moved: usize,
+    /// Tasks that are gone
+    pub removed: usize,
+}
+
+impl TaskDiff {
+    /// Compare task sets by ID and status
+    pub fn between(old: &[Task], new: &[Task]) -> Self {
+        let old_status: HashMap<&str, &str> = old
+            .iter()
+            .map(|t| (t.id.as_str(), t.status.as_str()))
+            .collect();
+        let mut diff = TaskDiff::default();
+        let mut kept = 0;
+        for task in new {
+            match old_status.get(task.id.as_str()) {
+                None => diff.added += 1,
+                Some(&status) => {
+                    kept += 1;
+                    if status != task.status {
+                        diff.moved += 1;
+                    }
+                }
+            }
+        }
+        diff.removed = old_status.len() - kept;
+        diff
+    }
+
+    /// e.g. "3 new, 2 moved, 1 removed" (`None` if nothing changed)
+    pub fn summary(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            (self.added, "new"),
+            (self.moved, "moved"),
+            (self.removed, "removed"),
+        ]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, label)| format!("{} {}", n, label))
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// A ClickUp timer running on a task
+#[derive(Debug, Clone)]
+pub struct RunningTimer {
+    pub task_id: String,
+    pub task_name: String,
+    pub started: DateTime<Utc>,
+}
+
+/// Person task drilled into: its linked user's tasks across all groups
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonView {
+    pub user_id: u64,
+    pub name: String,
+}
+
+/// Maximum number of undoable actions kept in history
+const UNDO_LIMIT: usize = 100;
+
+/// How long a typed number waits for a motion (`5j`) before switching to that tab
+const COUNT_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Refreshes fetch everything at least this often, dropping tasks that were deleted
+/// or reassigned (an incremental fetch only sees tasks that are still the user's)
+const FULL_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Maximum number of past status messages kept for the message log
+const MESSAGE_LOG_LIMIT: usize = 50;
+
+/// Search score bonus per recent open (before decay)
+const FRECENCY_PER_OPEN: f64 = 8.0;
+
+/// Opens beyond this count no longer raise the search bonus
+const FRECENCY_MAX_OPENS: u32 = 10;
+
+/// Days for the frecency bonus to halve since the last open
+const FRECENCY_HALF_LIFE_DAYS: f64 = 7.0;
+
+/// Per-field multipliers (in percent) applied to fuzzy search scores
+///
+/// A task's search score is its best weighted field score, so a match in the
+/// name outranks an equally good match in the description. `combined` scores
+/// all fields joined together, letting a query span e.g. a name and a tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchWeights {
+    pub name: i32,
+    pub custom_id: i32,
+    pub tag: i32,
+    pub status: i32,
+    pub list: i32,
+    pub description: i32,
+    pub combined: i32,
+}
+
+impl Default for SearchWeights {
+    fn default() -> Self {
+        Self {
+            name: 100,
+            custom_id: 90,
+            tag: 80,
+            status: 70,
+            list: 60,
+            description: 50,
+            combined: 40,
+        }
+    }
+}
+
+impl SearchWeights {
+    /// Best weighted fuzzy score of `task` for the (lowercased) query, if any field matches
+    pub fn score(&self, task: &Task, query_chars: &[char]) -> Option<i32> {
+        let weighted = |text: &str, weight: i32| {
+            fuzzy_score(text, query_chars).map(|score| score * weight / 100)
+        };
+        let combined = std::iter::once(task.name.as_str())
+            .chain(task.custom_id.as_deref())
+            .chain(task.tags.iter().map(String::as_str))
+            .chain([task.status.as_str(), task.list_name.as_str()])
+            .chain(task.description.as_deref())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        [
+            weighted(&task.name, self.name),
+            task.custom_id
+                .as_deref()
+                .and_then(|id| weighted(id, self.custom_id)),
+            task.tags
+                .iter()
+                .filter_map(|tag| weighted(tag, self.tag))
+                .max(),
+            weighted(&task.status, self.status),
+            weighted(&task.list_name, self.list),
+            task.description
+                .as_deref()
+                .and_then(|d| weighted(d, self.description)),
+            weighted(&combined, self.combined),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+    }
+}
+
+/// Braille spinner shown in the status bar while tasks load
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// How long each spinner frame stays up
+const SPINNER_FRAME_MS: u128 = 100;
+
+/// How long the selection must stay put before its comments are fetched
+const COMMENT_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// Priorities offered by the priority picker, most urgent first (`None` clears it)
+pub const PRIORITY_CHOICES: [Option<u8>; 5] = [Some(1), Some(2), Some(3), Some(4), None];
+
+/// A reversible change to one or more tasks' local overlays
+#[derive(Debug, Clone)]
+pub struct UndoAction {
+    /// Changed task IDs with their overlay before the change (`None` if there was none)
+    pub prior: Vec<(String, Option<TaskOverlay>)>,
+    /// What was done, for the status message (e.g. "pin", "snooze")
+    pub label: &'static str,
+}
+
 /// Application state
 pub struct App {
     /// All tasks from ClickUp
@@ -32,30 +376,153 @@ pub struct App {
     pub local_state: LocalState,
     /// Current tab/group
     pub current_group: TaskGroup,
+    /// Tabs shown in the tab bar, in order
+    pub visible_groups: Vec<TaskGroup>,
     /// Selected task index within current group
     pub selected_index: usize,
+    /// Tasks marked for a bulk pin/snooze (empty when not multi-selecting)
+    pub selected_ids: HashSet<String>,
+    /// Overdue tasks already notified about (`None` until the first check)
+    overdue_notified: Option<HashSet<String>>,
+    /// Ordering of root tasks in the task list
+    pub sort_mode: SortMode,
+    /// Default sort per tab from config (others sort by priority)
+    pub group_sorts: HashMap<TaskGroup, SortMode>,
     /// Search/filter query
     pub search_query: String,
+    /// Cached global search results for `search_query`
+    search_results: Vec<DisplayTask>,
+    /// How much each task field counts toward a search match
+    pub search_weights: SearchWeights,
+    /// Time entry currently running (started from this session)
+    pub timer: Option<RunningTimer>,
+    /// Fetched comments by task ID (`None` if they couldn't be loaded)
+    pub comments: HashMap<String, Option<Vec<Comment>>>,
+    /// Fetched status histories by task ID (`None` if unavailable), loaded with comments
+    pub status_history: HashMap<String, Option<Vec<StatusChange>>>,
+    /// Previewed task ID and when it was first seen (debounces comment fetches)
+    comment_target: Option<(String, Instant)>,
+    /// Task whose comments are being fetched in the background
+    comments_fetching: Option<String>,
+    /// Undo history for pin/snooze/unsnooze (most recent last)
+    pub undo: Vec<UndoAction>,
     /// Current input mode
     pub input_mode: InputMode,
+    /// Action awaiting confirmation (in Confirm mode)
+    pub confirmation: Option<Confirmation>,
+    /// Whether bulk/mutating actions ask for confirmation first
+    pub confirm_actions: bool,
     /// Snooze input buffer
     pub snooze_input: String,
-    /// Status message to display
+    /// Note editor buffer
+    pub note_input: String,
+    /// Waiting-on reason input buffer
+    pub waiting_input: String,
+    /// Comment editor buffer
+    pub comment_input: String,
+    /// Tag filter input buffer
+    pub tag_filter_input: String,
+    /// Only show tasks with this tag (case-insensitive)
+    pub active_tag_filter: Option<String>,
+    /// Custom ID typed into the jump-to-task prompt
+    pub jump_input: String,
+    /// List picker fuzzy query
+    pub list_picker_query: String,
+    /// Selected index in the list picker
+    pub list_picker_index: usize,
+    /// Selected row in the priority picker (index into `PRIORITY_CHOICES`)
+    pub priority_picker_index: usize,
+    /// Only show tasks from this ClickUp list
+    pub active_list_filter: Option<String>,
+    /// Show a Person task's user's tasks instead of the current group
+    pub person_view: Option<PersonView>,
+    /// Status message to display (set via `set_status`)
     pub status_message: Option<String>,
+    /// When the status message was set
+    status_set_at: Option<Instant>,
+    /// Recent status messages with when they were shown, oldest first
+    pub message_log: VecDeque<(DateTime<Local>, String)>,
+    /// How long status messages stay up (`None` keeps them until replaced)
+    status_timeout: Option<std::time::Duration>,
+    /// Show parents of the user's subtasks for context (greyed out if not theirs)
+    pub show_unassigned_ancestors: bool,
+    /// Drop Done subtasks from the tree while their parent is still open (only
+    /// person views and fetched subtask trees list them under open parents)
+    pub hide_done_subtasks: bool,
+    /// Sort pinned tasks ahead of the rest (pinned subtasks first among their siblings)
+    pub pin_to_top: bool,
+    /// Done tab only shows tasks updated within this many days (`None` shows all)
+    pub done_recent_days: Option<u32>,
+    /// Days untouched before a Backlog task shows as aging, then stale
+    pub backlog_age: BacklogConfig,
+    /// Temporarily show every done task regardless of `done_recent_days`
+    pub done_show_all: bool,
     /// Whether app should quit
     pub should_quit: bool,
-    /// Whether data is loading
-    pub is_loading: bool,
+    /// Whether tasks have been fetched since startup (otherwise they're from the cache)
+    refreshed_this_session: bool,
+    /// When every task was last fetched (not just changed ones) this session
+    last_full_refresh: Option<Instant>,
+    /// Work only from the cache: no fetches, and changes to ClickUp are refused
+    pub offline: bool,
+    /// Jump to tasks newly assigned to the user when a refresh brings them in
+    pub triage: bool,
+    /// When the in-flight task fetch started (`None` when idle)
+    loading_since: Option<Instant>,
     /// Selected index in global search results
     pub search_selected_index: usize,
     /// Show help screen
     pub show_help: bool,
+    /// Help overlay scroll offset
+    pub help_scroll: u16,
+    /// Maximum help scroll offset (recorded at render time)
+    pub help_max_scroll: u16,
+    /// Whether local state and task cache changes are written to disk
+    pub persist: bool,
+    /// Named config profile (separate config, state, and cache files)
+    pub profile: Option<String>,
     /// Current user's ID (for checking task assignment)
     pub user_id: Option<u64>,
     /// Which pane is focused
     pub focused_pane: FocusedPane,
     /// Preview pane scroll offset
     pub preview_scroll: u16,
+    /// Highlighted checklist item and the task it belongs to (`None` until moved to)
+    pub checklist_cursor: Option<(String, usize)>,
+    /// Pending vim-style count prefix (e.g. "5" in `5j`)
+    pub count_buffer: String,
+    /// When the last count digit was typed (a count left alone switches tabs)
+    count_typed_at: Option<Instant>,
+    /// Whether the previous Normal-mode key was `g` (for `gg`)
+    pub pending_g: bool,
+    /// Preview pane inner height (recorded at render time)
+    pub preview_height: u16,
+    /// Maximum preview scroll offset for the current content (recorded at render time)
+    pub preview_max_scroll: u16,
+    /// Task list area from the last render (for mouse hit-testing)
+    pub task_list_area: Rect,
+    /// First visible row of the task list (scroll offset, recorded at render time)
+    pub task_list_offset: usize,
+    /// Rows each task takes up in the list when names wrap (recorded at render time)
+    pub task_list_heights: Vec<usize>,
+    /// Wrap long task names onto extra rows instead of cutting them off
+    pub wrap_task_names: bool,
+    /// Dense rows: a status color bar instead of the status, type, and custom ID tags
+    pub dense: bool,
+    /// Start each row with a bar in its list's color
+    pub color_by_list: bool,
+    /// Description lines shown in the preview before it's cut short (`None` shows all)
+    pub preview_max_lines: Option<usize>,
+    /// Template for what `y` copies (`None` copies status, type, custom ID, and name)
+    pub clipboard_template: Option<String>,
+    /// Preview pane area from the last render (for mouse hit-testing)
+    pub preview_area: Rect,
+    /// User-configured status -> group overrides
+    pub status_groups: StatusGroups,
+    /// Normal-mode keybindings
+    pub keymap: Keymap,
+    /// Colors used for rendering
+    pub theme: Theme,
 }
 
 impl App {
@@ -65,18 +532,79 @@ impl App {
             tasks: Vec::new(),
             local_state: LocalState::default(),
             current_group: TaskGroup::MyAction,
+            visible_groups: TaskGroup::all().to_vec(),
             selected_index: 0,
+            selected_ids: HashSet::new(),
+            overdue_notified: None,
+            sort_mode: SortMode::default(),
+            group_sorts: HashMap::new(),
             search_query: String::new(),
+            search_results: Vec::new(),
+            search_weights: SearchWeights::default(),
+            timer: None,
+            comments: HashMap::new(),
+            status_history: HashMap::new(),
+            comment_target: None,
+            comments_fetching: None,
+            undo: Vec::new(),
             input_mode: InputMode::Normal,
+            confirmation: None,
+            confirm_actions: true,
             snooze_input: String::new(),
+            note_input: String::new(),
+            waiting_input: String::new(),
+            comment_input: String::new(),
+            tag_filter_input: String::new(),
+            jump_input: String::new(),
+            active_tag_filter: None,
+            list_picker_query: String::new(),
+            list_picker_index: 0,
+            priority_picker_index: 0,
+            active_list_filter: None,
+            person_view: None,
             status_message: None,
+            status_set_at: None,
+            message_log: VecDeque::new(),
+            status_timeout: Some(std::time::Duration::from_secs(3)),
+            show_unassigned_ancestors: true,
+            hide_done_subtasks: false,
+            pin_to_top: true,
+            backlog_age: BacklogConfig::default(),
+            done_recent_days: Some(7),
+            done_show_all: false,
             should_quit: false,
-            is_loading: false,
+            refreshed_this_session: false,
+            last_full_refresh: None,
+            offline: false,
+            triage: false,
+            loading_since: None,
             search_selected_index: 0,
             show_help: false,
+            help_scroll: 0,
+            help_max_scroll: 0,
+            persist: true,
+            profile: None,
             user_id: None,
             focused_pane: FocusedPane::TaskList,
             preview_scroll: 0,
+            checklist_cursor: None,
+            count_buffer: String::new(),
+            count_typed_at: None,
+            pending_g: false,
+            preview_height: 0,
+            preview_max_scroll: 0,
+            task_list_area: Rect::default(),
+            task_list_offset: 0,
+            task_list_heights: Vec::new(),
+            wrap_task_names: false,
+            dense: false,
+            color_by_list: false,
+            preview_max_lines: None,
+            clipboard_template: None,
+            preview_area: Rect::default(),
+            status_groups: StatusGroups::new(),
+            keymap: Keymap::default(),
+            theme: Theme::default(),
         }
     }
 
@@ -96,29 +624,272 @@ impl App {
         };
     }
 
-    /// Scroll preview down
+    /// Scroll preview down (by the pending count, if any)
     pub fn scroll_preview_down(&mut self) {
-        self.preview_scroll = self.preview_scroll.saturating_add(1);
+        let count = self.take_count().unwrap_or(1).min(u16::MAX as usize) as u16;
+        self.preview_scroll = self
+            .preview_scroll
+            .saturating_add(count)
+            .min(self.preview_max_scroll);
     }
 
-    /// Scroll preview up
+    /// Scroll preview up (by the pending count, if any)
     pub fn scroll_preview_up(&mut self) {
-        self.preview_scroll = self.preview_scroll.saturating_sub(1);
+        let count = self.take_count().unwrap_or(1).min(u16::MAX as usize) as u16;
+        self.preview_scroll = self.preview_scroll.saturating_sub(count);
+    }
+
+    /// Scroll preview down by half a page (Ctrl+d)
+    pub fn page_down(&mut self) {
+        let step = (self.preview_height / 2).max(1);
+        self.preview_scroll = self
+            .preview_scroll
+            .saturating_add(step)
+            .min(self.preview_max_scroll);
+    }
+
+    /// Scroll preview up by half a page (Ctrl+u)
+    pub fn page_up(&mut self) {
+        let step = (self.preview_height / 2).max(1);
+        self.preview_scroll = self.preview_scroll.saturating_sub(step);
+    }
+
+    /// Scroll the help overlay by `delta` lines, within its content
+    pub fn scroll_help(&mut self, delta: i32) {
+        let scroll = (self.help_scroll as i32 + delta).clamp(0, self.help_max_scroll as i32);
+        self.help_scroll = scroll as u16;
+    }
+
+    /// Record the help overlay's scroll limit, clamping the current offset
+    pub fn set_help_bounds(&mut self, max_scroll: u16) {
+        self.help_max_scroll = max_scroll;
+        self.help_scroll = self.help_scroll.min(max_scroll);
+    }
+
+    /// Record the preview viewport height and scroll limit, clamping the current offset
+    pub fn set_preview_bounds(&mut self, height: u16, max_scroll: u16) {
+        self.preview_height = height;
+        self.preview_max_scroll = max_scroll;
+        self.preview_scroll = self.preview_scroll.min(max_scroll);
+    }
+
+    /// Handle a left click: select the clicked task or focus the clicked pane
+    pub fn click(&mut self, column: u16, row: u16) {
+        let pos = Position::new(column, row);
+        if self.task_list_area.contains(pos) {
+            self.focused_pane = FocusedPane::TaskList;
+            // First row inside the border is the first visible task
+            let Some(row) = row.checked_sub(self.task_list_area.y + 1) else {
+                return;
+            };
+            // Wrapped tasks span several rows
+            let mut row = row as usize;
+            let mut index = self.task_list_offset;
+            loop {
+                let height = self.task_list_heights.get(index).copied().unwrap_or(1);
+                if row < height {
+                    break;
+                }
+                row -= height;
+                index += 1;
+            }
+            if index < self.current_task_refs().len() {
+                self.selected_index = index;
+                self.reset_preview_scroll();
+            }
+        } else if self.preview_area.contains(pos) {
+            self.focused_pane = FocusedPane::Preview;
+        }
+    }
+
+    /// Handle the scroll wheel: scroll the preview if hovered, otherwise move selection
+    pub fn scroll_wheel(&mut self, column: u16, row: u16, down: bool) {
+        if self.preview_area.contains(Position::new(column, row)) {
+            if down {
+                self.scroll_preview_down();
+            } else {
+                self.scroll_preview_up();
+            }
+        } else {
+            if down {
+                self.select_next();
+            } else {
+                self.select_prev();
+            }
+            self.reset_preview_scroll();
+        }
     }
 
     /// Reset preview scroll when task changes
     pub fn reset_preview_scroll(&mut self) {
         self.preview_scroll = 0;
+        self.checklist_cursor = None;
+    }
+
+    /// Set the config profile used for state and cache files
+    pub fn set_profile(&mut self, profile: Option<String>) {
+        self.profile = profile;
+    }
+
+    /// Turn triage mode on or off (`--triage`)
+    pub fn set_triage(&mut self, triage: bool) {
+        self.triage = triage;
+    }
+
+    /// Turn offline mode on or off (`--offline`)
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    /// Refuse a change that has to go to ClickUp while offline; `true` if refused
+    fn refuse_offline(&mut self) -> bool {
+        if self.offline {
+            self.set_status("Offline mode: changes can't be sent to ClickUp");
+        }
+        self.offline
     }
 
     /// Set the user ID from config
-    pub fn set_user_id(&mut self, user_id: &str) {
-        self.user_id = user_id.parse().ok();
+    ///
+    /// A non-numeric ID leaves `user_id` unset, which turns off everything keyed
+    /// on assignment (greying out, Watching, Person views); the error says so.
+    pub fn set_user_id(&mut self, user_id: &str) -> Result<()> {
+        self.user_id = user_id.trim().parse().ok();
+        if self.user_id.is_none() {
+            anyhow::bail!(
+                "user_id \"{}\" is not numeric; assignee filtering disabled",
+                user_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Set the status -> group overrides from config
+    pub fn set_status_groups(&mut self, status_groups: StatusGroups) {
+        self.status_groups = status_groups;
+    }
+
+    /// Set which tabs are shown and their order (switching away from a hidden tab)
+    pub fn set_visible_groups(&mut self, groups: Vec<TaskGroup>) {
+        self.visible_groups = groups;
+        if !self.visible_groups.contains(&self.current_group) {
+            if let Some(&first) = self.visible_groups.first() {
+                self.current_group = first;
+            }
+        }
+    }
+
+    /// Open on this tab at startup (ignored if the tab is hidden)
+    pub fn set_default_group(&mut self, group: TaskGroup) {
+        if self.visible_groups.contains(&group) {
+            self.switch_group(group);
+        }
+    }
+
+    /// Set each tab's default sort from config, applying it to the current tab
+    pub fn set_group_sorts(&mut self, sorts: HashMap<TaskGroup, SortMode>) {
+        self.group_sorts = sorts;
+        self.sort_mode = self.default_sort(self.current_group);
+    }
+
+    /// Sort a tab starts with when switched to
+    fn default_sort(&self, group: TaskGroup) -> SortMode {
+        self.group_sorts.get(&group).copied().unwrap_or_default()
+    }
+
+    /// Set the Normal-mode keybindings
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+
+    /// Set the rendering colors
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Set whether bulk/mutating actions ask for confirmation
+    pub fn set_confirm_actions(&mut self, confirm: bool) {
+        self.confirm_actions = confirm;
+    }
+
+    /// Set how long status messages stay up (`None` keeps them until replaced)
+    pub fn set_status_timeout(&mut self, timeout: Option<std::time::Duration>) {
+        self.status_timeout = timeout;
+    }
+
+    /// Show (greyed out) or hide parents of subtasks that aren't the user's own
+    pub fn set_show_unassigned_ancestors(&mut self, show: bool) {
+        self.show_unassigned_ancestors = show;
+    }
+
+    /// Float pinned tasks to the top of the list (from config)
+    pub fn set_pin_to_top(&mut self, pin_to_top: bool) {
+        self.pin_to_top = pin_to_top;
+    }
+
+    /// Age thresholds for the Backlog heat dot (from config)
+    pub fn set_backlog_age(&mut self, backlog_age: BacklogConfig) {
+        self.backlog_age = backlog_age;
+    }
+
+    /// How stale a task looks on the Backlog tab (`None` elsewhere, or without timestamps)
+    pub fn backlog_heat(&self, task: &Task, now: DateTime<Utc>) -> Option<TaskAge> {
+        if self.current_group != TaskGroup::Backlog {
+            return None;
+        }
+        let touched = task.date_updated.or(task.date_created)?;
+        let days = (now.timestamp_millis() - touched).max(0) / 86_400_000;
+        Some(if days >= self.backlog_age.stale_days as i64 {
+            TaskAge::Stale
+        } else if days >= self.backlog_age.aging_days as i64 {
+            TaskAge::Aging
+        } else {
+            TaskAge::Fresh
+        })
+    }
+
+    /// Hide Done subtasks under parents that aren't done (from config)
+    pub fn set_hide_done_subtasks(&mut self, hide: bool) {
+        self.hide_done_subtasks = hide;
+    }
+
+    /// Limit the Done tab to tasks updated in the last `days` days (`None` shows all)
+    pub fn set_done_recent_days(&mut self, days: Option<u32>) {
+        self.done_recent_days = days;
+    }
+
+    /// Days the Done tab is currently limited to, if the limit is active
+    pub fn done_window(&self) -> Option<u32> {
+        self.done_recent_days.filter(|_| !self.done_show_all)
+    }
+
+    /// Switch the Done tab between recent completions and everything
+    pub fn toggle_done_show_all(&mut self) {
+        let Some(days) = self.done_recent_days else {
+            self.set_status("Done shows all tasks (done_recent_days = 0)");
+            return;
+        };
+        self.done_show_all = !self.done_show_all;
+        self.clamp_selection();
+        if self.done_show_all {
+            self.set_status("Done: showing all tasks");
+        } else {
+            self.set_status(format!("Done: showing the last {} days", days));
+        }
+    }
+
+    /// Whether a task was updated inside the Done window (tasks with no update time count)
+    fn is_recently_done(&self, task: &Task) -> bool {
+        let Some(days) = self.done_window() else {
+            return true;
+        };
+        let cutoff = (Utc::now() - Duration::days(days.into())).timestamp_millis();
+        task.date_updated.is_none_or(|updated| updated >= cutoff)
     }
 
     /// Load local state from disk
     pub fn load_local_state(&mut self) -> Result<()> {
-        let path = Config::state_path()?;
+        let path = Config::state_path(self.profile.as_deref())?;
         if path.exists() {
             let content = fs::read_to_string(&path)
                 .with_context(|| format!("Failed to read state from {}", path.display()))?;
@@ -128,9 +899,22 @@ impl App {
         Ok(())
     }
 
+    /// Clear snoozes that have already ended, saying "Woke N tasks" if there were any
+    pub fn wake_expired_snoozes(&mut self) {
+        let woken = self.local_state.wake_expired(Utc::now());
+        if woken > 0 {
+            let noun = if woken == 1 { "task" } else { "tasks" };
+            self.set_status(format!("Woke {} {}", woken, noun));
+            let _ = self.save_local_state();
+        }
+    }
+
     /// Save local state to disk
     pub fn save_local_state(&self) -> Result<()> {
-        let path = Config::state_path()?;
+        if !self.persist {
+            return Ok(());
+        }
+        let path = Config::state_path(self.profile.as_deref())?;
         let dir = path.parent().unwrap();
         fs::create_dir_all(dir)?;
         let content = serde_json::to_string_pretty(&self.local_state)?;
@@ -138,9 +922,32 @@ impl App {
         Ok(())
     }
 
+    /// Remember the current tab and selected task for the next session
+    pub fn remember_position(&mut self) {
+        self.local_state.last_group = Some(self.current_group);
+        self.local_state.last_selected_task_id = self.selected_task().map(|dt| dt.task.id);
+    }
+
+    /// Reopen the tab and task remembered from the last session
+    ///
+    /// A hidden tab is skipped; a task that's gone leaves the first one selected.
+    pub fn restore_position(&mut self) {
+        if let Some(group) = self.local_state.last_group {
+            self.set_default_group(group);
+        }
+        if let Some(idx) = self
+            .local_state
+            .last_selected_task_id
+            .as_deref()
+            .and_then(|id| self.current_task_refs().iter().position(|t| t.id == id))
+        {
+            self.selected_index = idx;
+        }
+    }
+
     /// Load cached tasks from disk
     pub fn load_cached_tasks(&mut self) -> Result<()> {
-        let path = Config::cache_path()?;
+        let path = Config::cache_path(self.profile.as_deref())?;
         if path.exists() {
             let content = fs::read_to_string(&path)?;
             self.tasks = serde_json::from_str(&content)?;
@@ -150,7 +957,10 @@ impl App {
 
     /// Save tasks to cache
     pub fn save_tasks_cache(&self) -> Result<()> {
-        let path = Config::cache_path()?;
+        if !self.persist {
+            return Ok(());
+        }
+        let path = Config::cache_path(self.profile.as_deref())?;
         let dir = path.parent().unwrap();
         fs::create_dir_all(dir)?;
         let content = serde_json::to_string_pretty(&self.tasks)?;
@@ -158,170 +968,442 @@ impl App {
         Ok(())
     }
 
-    /// Set tasks and update local state timestamp
-    pub fn set_tasks(&mut self, tasks: Vec<Task>) {
+    /// Set tasks and update local state timestamp, keeping the selected task if it survives
+    ///
+    /// Subtasks fetched on demand stay as long as their parent does, since refreshes
+    /// don't return them.
+    pub fn set_tasks(&mut self, mut tasks: Vec<Task>) -> TaskDiff {
+        let selected_id = self.selected_task().map(|dt| dt.task.id);
+        let mut kept: HashSet<String> = tasks.iter().map(|t| t.id.clone()).collect();
+        let mut lazy: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|t| t.fetched_as_subtask && !kept.contains(&t.id))
+            .collect();
+        // Parents may come after their children, so go until nothing else attaches
+        while let Some(pos) = lazy
+            .iter()
+            .position(|t| t.parent_id.as_ref().is_some_and(|pid| kept.contains(pid)))
+        {
+            let task = lazy.swap_remove(pos);
+            kept.insert(task.id.clone());
+            tasks.push(task.clone());
+        }
+        let diff = TaskDiff::between(&self.tasks, &tasks);
         self.tasks = tasks;
         self.local_state.last_refresh = Some(Utc::now());
-        self.selected_index = 0;
+        self.refreshed_this_session = true;
+        // On-demand subtasks weren't part of the incremental sync, so they don't move its cursor
+        self.local_state.last_updated = self
+            .tasks
+            .iter()
+            .filter(|t| !t.fetched_as_subtask)
+            .filter_map(|t| t.date_updated)
+            .max();
+        self.refresh_search_results();
+        self.selected_index = selected_id
+            .and_then(|id| self.current_task_refs().iter().position(|t| t.id == id))
+            .unwrap_or(0);
+        diff
+    }
+
+    /// Merge tasks changed since the last refresh into the current set by ID
+    pub fn merge_tasks(&mut self, changed: Vec<Task>) -> TaskDiff {
+        let mut tasks = self.tasks.clone();
+        for task in changed {
+            match tasks.iter_mut().find(|t| t.id == task.id) {
+                Some(existing) => *existing = task,
+                None => tasks.push(task),
+            }
+        }
+        self.set_tasks(tasks)
+    }
+
+    /// How fresh the task list is: "updated 5m ago", or "cached" until the first
+    /// refresh of this session ("offline" in offline mode)
+    pub fn freshness_label(&self, now: DateTime<Utc>) -> String {
+        if self.offline {
+            return "offline".to_string();
+        }
+        match self
+            .local_state
+            .last_refresh
+            .filter(|_| self.refreshed_this_session)
+        {
+            Some(at) => format!("updated {}", format_age(at.timestamp_millis(), now)),
+            None => "cached".to_string(),
+        }
+    }
+
+    /// `date_updated` cursor for an incremental refresh (`None` means fetch everything)
+    pub fn sync_cursor(&self) -> Option<i64> {
+        self.local_state
+            .last_updated
+            .filter(|_| !self.tasks.is_empty())
+    }
+
+    /// Cursor for the next `r` or auto refresh: `None` (fetch everything) when
+    /// there hasn't been a full fetch within `FULL_REFRESH_INTERVAL`
+    pub fn refresh_cursor(&self, now: Instant) -> Option<i64> {
+        self.last_full_refresh
+            .filter(|&at| now.duration_since(at) < FULL_REFRESH_INTERVAL)
+            .and(self.sync_cursor())
+    }
+
+    /// Record that every task was just fetched
+    pub fn mark_full_refresh(&mut self, at: Instant) {
+        self.last_full_refresh = Some(at);
     }
 
     /// Get display tasks for the current group
     pub fn current_tasks(&self) -> Vec<DisplayTask> {
-        use std::collections::{HashMap, HashSet};
+        self.current_task_refs()
+            .into_iter()
+            .map(|t| self.display_task(t))
+            .collect()
+    }
 
-        let user_id = self.user_id;
+    /// Pair a task with its local overlay for display
+    pub fn display_task(&self, task: &Task) -> DisplayTask {
+        DisplayTask::new(task.clone(), self.local_state.get_overlay(&task.id))
+    }
 
-        // Build all display tasks indexed by ID
-        let all_tasks: HashMap<String, DisplayTask> = self
-            .tasks
+    /// Effective group of a task (considering status overrides and snooze)
+    fn effective_group(&self, task: &Task) -> TaskGroup {
+        task.effective_group(self.local_state.overlays.get(&task.id), &self.status_groups)
+    }
+
+    /// Whether a task belongs in a tab
+    ///
+    /// Person tasks only ever appear under Person; Today is based on the due date
+    /// rather than status, and skips done and snoozed tasks. Tasks the user only
+    /// watches appear under Watching while open (and under Snoozed if snoozed).
+    /// Done is limited to recently updated tasks unless `done_show_all` is set.
+    fn in_group(&self, task: &Task, group: TaskGroup) -> bool {
+        let is_person = task.is_person();
+        let only_watched = self
+            .user_id
+            .map(|uid| task.is_only_watched_by(uid))
+            .unwrap_or(false);
+        match group {
+            TaskGroup::Person => is_person,
+            _ if is_person => false,
+            TaskGroup::Watching => {
+                only_watched
+                    && !matches!(
+                        self.effective_group(task),
+                        TaskGroup::Done | TaskGroup::Snoozed
+                    )
+            }
+            TaskGroup::Snoozed => self.effective_group(task) == TaskGroup::Snoozed,
+            _ if only_watched => false,
+            TaskGroup::Today => {
+                !matches!(
+                    self.effective_group(task),
+                    TaskGroup::Done | TaskGroup::Snoozed
+                ) && task.is_due_by(Local::now().date_naive())
+            }
+            TaskGroup::Done => {
+                self.effective_group(task) == TaskGroup::Done && self.is_recently_done(task)
+            }
+            _ => self.effective_group(task) == group,
+        }
+    }
+
+    /// Tasks in the current group, in display order, without cloning
+    ///
+    /// Includes the user's tasks plus their visible ancestors; roots are ordered by
+    /// the active sort mode and subtasks stay under their parents. Subtasks of
+    /// collapsed parents are left out.
+    pub fn current_task_refs(&self) -> Vec<&Task> {
+        let tree = self.task_tree();
+        let hidden = collapsed_descendants(&tree, &self.local_state.collapsed);
+        tree.into_iter()
+            .filter(|t| !hidden.contains_key(t.id.as_str()))
+            .collect()
+    }
+
+    /// Number of hidden subtasks under each collapsed parent in the current group
+    pub fn collapsed_counts(&self) -> HashMap<String, usize> {
+        let tree = self.task_tree();
+        let mut counts = HashMap::new();
+        for parent in collapsed_descendants(&tree, &self.local_state.collapsed).into_values() {
+            *counts.entry(parent.to_string()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// A task's direct subtasks in the current view, counted per status
+    /// (most common first; empty if it has none). Children collapsed or hidden
+    /// as done under the task still count; those filtered out of the tab don't.
+    pub fn subtask_status_counts(&self, task_id: &str) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for child in self
+            .build_task_tree(false)
+            .into_iter()
+            .filter(|t| t.parent_id.as_deref() == Some(task_id))
+        {
+            match counts
+                .iter_mut()
+                .find(|(status, _)| *status == child.status)
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((child.status.clone(), 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Command to fetch the selected task's full subtask tree
+    fn fetch_subtasks_command(&mut self) -> Option<Command> {
+        let task_id = self.selected_task()?.task.id;
+        if self.offline {
+            self.set_status("Offline mode");
+            return None;
+        }
+        self.set_status("Fetching subtasks...");
+        Some(Command::FetchSubtasks { task_id })
+    }
+
+    /// Merge a task's fetched subtree and expand it; subtasks not already loaded
+    /// are marked as fetched on demand
+    pub fn add_subtasks(&mut self, parent_id: &str, subtasks: Vec<Task>) {
+        if subtasks.is_empty() {
+            self.set_status("No subtasks");
+            return;
+        }
+        let count = subtasks.len();
+        let mut added = 0;
+        for mut task in subtasks {
+            match self.tasks.iter_mut().find(|t| t.id == task.id) {
+                Some(existing) => {
+                    task.fetched_as_subtask = existing.fetched_as_subtask;
+                    *existing = task;
+                }
+                None => {
+                    task.fetched_as_subtask = true;
+                    self.tasks.push(task);
+                    added += 1;
+                }
+            }
+        }
+        self.local_state.collapsed.remove(parent_id);
+        self.refresh_search_results();
+        self.clamp_selection();
+        let noun = if count == 1 { "subtask" } else { "subtasks" };
+        self.set_status(format!("Fetched {} {} ({} new)", count, noun, added));
+        let _ = self.save_tasks_cache();
+        let _ = self.save_local_state();
+    }
+
+    /// Collapse or expand the selected task's subtasks
+    pub fn toggle_collapse(&mut self) {
+        let Some(dt) = self.selected_task() else {
+            return;
+        };
+        let id = dt.task.id;
+        if self.local_state.collapsed.remove(&id) {
+            self.set_status("Subtasks expanded");
+        } else if self
+            .task_tree()
             .iter()
-            .map(|t| {
-                (
-                    t.id.clone(),
-                    DisplayTask::new(t.clone(), self.local_state.get_overlay(&t.id)),
-                )
-            })
-            .collect();
+            .any(|t| t.parent_id.as_deref() == Some(id.as_str()))
+        {
+            self.local_state.collapsed.insert(id);
+            self.set_status("Subtasks collapsed");
+        } else {
+            self.set_status("No subtasks to collapse");
+            return;
+        }
+        let _ = self.save_local_state();
+    }
+
+    /// Every task in the current group in display order, collapsed or not
+    fn task_tree(&self) -> Vec<&Task> {
+        self.build_task_tree(self.hide_done_subtasks)
+    }
+
+    /// The current group's tree, with or without Done subtasks of open parents
+    fn build_task_tree(&self, hide_done_subtasks: bool) -> Vec<&Task> {
+        // In a person view, that person's tasks stand in for the user's own
+        let (user_id, include_watched) = match &self.person_view {
+            Some(view) => (Some(view.user_id), false),
+            None => (self.user_id, true),
+        };
+        let is_assigned = |t: &Task| -> bool {
+            user_id
+                .map(|uid| {
+                    t.is_assigned_to(uid) || (include_watched && t.watcher_ids.contains(&uid))
+                })
+                .unwrap_or(true)
+        };
+        let in_view = |t: &Task| -> bool {
+            match &self.person_view {
+                Some(_) => !t.is_person(),
+                None => self.in_group(t, self.current_group),
+            }
+        };
+
+        // Index all tasks by ID
+        let all_tasks: HashMap<&str, &Task> =
+            self.tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+
+        let query = self.search_query.to_lowercase();
+        let list_filter = self.active_list_filter.as_ref().map(|l| l.to_lowercase());
+
+        // Done subtasks of open parents, if hidden (the Done tab still lists them)
+        let is_hidden_done_subtask = |t: &Task| -> bool {
+            hide_done_subtasks
+                && self.current_group != TaskGroup::Done
+                && self.effective_group(t) == TaskGroup::Done
+                && t.parent_id
+                    .as_deref()
+                    .and_then(|pid| all_tasks.get(pid))
+                    .is_some_and(|&parent| self.effective_group(parent) != TaskGroup::Done)
+        };
 
         // Get tasks assigned to user in this group (iterate self.tasks for stable order)
-        let my_tasks: Vec<DisplayTask> = self
+        let my_tasks: Vec<&Task> = self
             .tasks
             .iter()
-            .map(|t| DisplayTask::new(t.clone(), self.local_state.get_overlay(&t.id)))
-            .filter(|dt| {
-                let in_group = if self.current_group == TaskGroup::Person {
-                    dt.task.custom_item_id == Some(1020)
-                } else {
-                    dt.task.custom_item_id != Some(1020)
-                        && dt.effective_group() == self.current_group
-                };
-                let is_assigned = user_id
-                    .map(|uid| dt.task.is_assigned_to(uid))
-                    .unwrap_or(true);
-                in_group && is_assigned
+            .filter(|t| in_view(t) && is_assigned(t) && !is_hidden_done_subtask(t))
+            .filter(|t| match &list_filter {
+                Some(list) => t.list_name.to_lowercase() == *list,
+                None => true,
             })
-            .filter(|dt| {
-                if self.search_query.is_empty() {
-                    true
-                } else {
-                    let query = self.search_query.to_lowercase();
-                    dt.task.name.to_lowercase().contains(&query)
-                        || dt.task.list_name.to_lowercase().contains(&query)
-                        || dt.task.status.to_lowercase().contains(&query)
-                        || dt
-                            .task
-                            .description
-                            .as_ref()
-                            .map(|d| d.to_lowercase().contains(&query))
-                            .unwrap_or(false)
-                }
+            .filter(|t| match &self.active_tag_filter {
+                Some(tag) => t.tags.iter().any(|tt| tt.eq_ignore_ascii_case(tag)),
+                None => true,
+            })
+            .filter(|t| {
+                query.is_empty()
+                    || t.name.to_lowercase().contains(&query)
+                    || t.custom_id
+                        .as_ref()
+                        .map(|id| id.to_lowercase().contains(&query))
+                        .unwrap_or(false)
+                    || t.list_name.to_lowercase().contains(&query)
+                    || t.status.to_lowercase().contains(&query)
+                    || t.description
+                        .as_ref()
+                        .map(|d| d.to_lowercase().contains(&query))
+                        .unwrap_or(false)
             })
             .collect();
 
         // Build set of tasks to include (my tasks + their ancestors)
-        let mut included: Vec<DisplayTask> = Vec::new();
-        let mut added_ids: HashSet<String> = HashSet::new();
+        let mut included: Vec<&Task> = Vec::new();
+        let mut added_ids: HashSet<&str> = HashSet::new();
 
-        for dt in &my_tasks {
-            // Add ancestor chain (stop at first unassigned ancestor)
-            let mut ancestors: Vec<DisplayTask> = Vec::new();
-            let mut current_parent_id = dt.task.parent_id.clone();
+        for &task in &my_tasks {
+            // Add ancestor chain (stop at first unassigned ancestor), unless turned off
+            let mut ancestors: Vec<&Task> = Vec::new();
+            let mut current_parent_id = task
+                .parent_id
+                .as_deref()
+                .filter(|_| self.show_unassigned_ancestors);
 
             while let Some(pid) = current_parent_id {
-                if let Some(parent) = all_tasks.get(&pid) {
-                    ancestors.push(parent.clone());
-                    let parent_assigned = user_id
-                        .map(|uid| parent.task.is_assigned_to(uid))
-                        .unwrap_or(true);
-                    if !parent_assigned {
+                if let Some(&parent) = all_tasks.get(pid) {
+                    ancestors.push(parent);
+                    if !is_assigned(parent) {
                         break;
                     }
-                    current_parent_id = parent.task.parent_id.clone();
+                    current_parent_id = parent.parent_id.as_deref();
                 } else {
                     break;
                 }
             }
 
-            // Add ancestors
-            for ancestor in ancestors.into_iter().rev() {
-                if !added_ids.contains(&ancestor.task.id) {
-                    included.push(ancestor.clone());
-                    added_ids.insert(ancestor.task.id.clone());
+            // Add ancestors, then the task itself
+            for t in ancestors.into_iter().rev().chain(std::iter::once(task)) {
+                if added_ids.insert(t.id.as_str()) {
+                    included.push(t);
                 }
             }
+        }
 
-            // Add the task itself
-            if !added_ids.contains(&dt.task.id) {
-                included.push(dt.clone());
-                added_ids.insert(dt.task.id.clone());
+        // Subtasks fetched on demand show under their parent, whoever they're assigned to
+        let mut attached = true;
+        while attached {
+            attached = false;
+            for task in &self.tasks {
+                if task.fetched_as_subtask
+                    && !added_ids.contains(task.id.as_str())
+                    && !is_hidden_done_subtask(task)
+                    && task
+                        .parent_id
+                        .as_deref()
+                        .is_some_and(|pid| added_ids.contains(pid))
+                {
+                    added_ids.insert(task.id.as_str());
+                    included.push(task);
+                    attached = true;
+                }
             }
         }
 
-        // Build a map of id -> depth (for sorting)
-        let mut depth_map: HashMap<String, usize> = HashMap::new();
-        for dt in &included {
+        // Root ancestor (within the visible set) and depth of each included task
+        let mut root_map: HashMap<&str, &Task> = HashMap::new();
+        let mut depth_map: HashMap<&str, usize> = HashMap::new();
+        for &task in &included {
+            let mut root = task;
             let mut depth = 0;
-            let mut pid = dt.task.parent_id.clone();
-            while let Some(p) = pid {
-                if added_ids.contains(&p) {
-                    depth += 1;
-                    pid = all_tasks.get(&p).and_then(|t| t.task.parent_id.clone());
-                } else {
-                    break;
+            while let Some(pid) = root.parent_id.as_deref() {
+                match all_tasks.get(pid) {
+                    Some(&parent) if added_ids.contains(pid) => {
+                        root = parent;
+                        depth += 1;
+                    }
+                    _ => break,
                 }
             }
-            depth_map.insert(dt.task.id.clone(), depth);
+            root_map.insert(task.id.as_str(), root);
+            depth_map.insert(task.id.as_str(), depth);
         }
 
-        // Helper to get root ancestor within visible set
-        let get_root = |id: &str, parent_id: &Option<String>| -> String {
-            let mut root = id.to_string();
-            let mut current = parent_id.clone();
-            while let Some(pid) = current {
-                if added_ids.contains(&pid) {
-                    root = pid.clone();
-                    current = all_tasks.get(&pid).and_then(|t| t.task.parent_id.clone());
-                } else {
-                    break;
-                }
-            }
-            root
-        };
-
-        // Sort: root tasks by priority, then children under their parents
+        // Sort: root tasks by the active sort mode, then children under their parents
+        let is_pinned = |t: &Task| self.local_state.is_pinned(&t.id);
         included.sort_by(|a, b| {
-            let root_a = get_root(&a.task.id, &a.task.parent_id);
-            let root_b = get_root(&b.task.id, &b.task.parent_id);
+            let root_a = root_map[a.id.as_str()];
+            let root_b = root_map[b.id.as_str()];
 
-            // Compare by root's priority
-            let root_a_priority = all_tasks.get(&root_a).and_then(|t| t.task.priority);
-            let root_b_priority = all_tasks.get(&root_b).and_then(|t| t.task.priority);
+            // Pinned roots first, if configured
+            if self.pin_to_top {
+                let pin_cmp = is_pinned(root_b).cmp(&is_pinned(root_a));
+                if pin_cmp != std::cmp::Ordering::Equal {
+                    return pin_cmp;
+                }
+            }
 
-            let priority_cmp = match (root_a_priority, root_b_priority) {
-                (Some(pa), Some(pb)) => pa.cmp(&pb),
-                (Some(_), None) => std::cmp::Ordering::Less,
-                (None, Some(_)) => std::cmp::Ordering::Greater,
-                (None, None) => std::cmp::Ordering::Equal,
-            };
-            if priority_cmp != std::cmp::Ordering::Equal {
-                return priority_cmp;
+            // Compare roots by the active sort mode
+            let root_cmp = self.sort_mode.compare(root_a, root_b);
+            if root_cmp != std::cmp::Ordering::Equal {
+                return root_cmp;
             }
 
             // Group by root
-            if root_a != root_b {
-                return root_a.cmp(&root_b);
+            if root_a.id != root_b.id {
+                return root_a.id.cmp(&root_b.id);
             }
 
             // Same family: sort by depth (parents before children)
-            let depth_a = depth_map.get(&a.task.id).unwrap_or(&0);
-            let depth_b = depth_map.get(&b.task.id).unwrap_or(&0);
-            let depth_cmp = depth_a.cmp(depth_b);
+            let depth_cmp = depth_map[a.id.as_str()].cmp(&depth_map[b.id.as_str()]);
             if depth_cmp != std::cmp::Ordering::Equal {
                 return depth_cmp;
             }
 
+            // Pinned siblings first, if configured
+            if self.pin_to_top {
+                let pin_cmp = is_pinned(b).cmp(&is_pinned(a));
+                if pin_cmp != std::cmp::Ordering::Equal {
+                    return pin_cmp;
+                }
+            }
+
             // Final tiebreaker: task ID for stable sort
-            a.task.id.cmp(&b.task.id)
+            a.id.cmp(&b.id)
         });
 
         included
@@ -329,75 +1411,121 @@ impl App {
 
     /// Get count of tasks in each group
     pub fn group_counts(&self) -> Vec<(TaskGroup, usize)> {
-        TaskGroup::all()
+        self.visible_groups
             .iter()
             .map(|&group| {
                 let count = self
                     .tasks
                     .iter()
-                    .map(|t| DisplayTask::new(t.clone(), self.local_state.get_overlay(&t.id)))
-                    .filter(|dt| {
-                        if group == TaskGroup::Person {
-                            dt.task.custom_item_id == Some(1020)
-                        } else {
-                            dt.task.custom_item_id != Some(1020) && dt.effective_group() == group
-                        }
-                    })
+                    .filter(|t| self.in_group(t, group))
                     .count();
                 (group, count)
             })
             .collect()
     }
 
+    /// Number of the user's open tasks whose due date has passed (excludes done and snoozed)
+    pub fn overdue_count(&self) -> usize {
+        self.overdue_tasks(Utc::now()).count()
+    }
+
+    /// Overdue tasks that haven't been reported yet, marking them reported
+    ///
+    /// The first check (once tasks are loaded) only records what's already
+    /// overdue, so starting the app doesn't announce old news.
+    pub fn take_newly_overdue(&mut self, now: DateTime<Utc>) -> Vec<Task> {
+        if self.tasks.is_empty() {
+            return Vec::new();
+        }
+        let overdue: Vec<Task> = self.overdue_tasks(now).cloned().collect();
+        let first_check = self.overdue_notified.is_none();
+        let notified = self.overdue_notified.get_or_insert_with(HashSet::new);
+        let fresh: Vec<Task> = overdue
+            .into_iter()
+            .filter(|t| notified.insert(t.id.clone()))
+            .collect();
+        if first_check {
+            Vec::new()
+        } else {
+            fresh
+        }
+    }
+
+    /// The user's open tasks whose due date is before `now` (excludes done and snoozed)
+    fn overdue_tasks(&self, now: DateTime<Utc>) -> impl Iterator<Item = &Task> {
+        let now = now.timestamp_millis();
+        self.tasks
+            .iter()
+            .filter(|t| {
+                self.user_id
+                    .map(|uid| t.is_assigned_to(uid))
+                    .unwrap_or(true)
+            })
+            .filter(move |t| t.due_date.is_some_and(|due| due < now))
+            .filter(|t| {
+                !matches!(
+                    self.effective_group(t),
+                    TaskGroup::Done | TaskGroup::Snoozed
+                )
+            })
+    }
+
     /// Get currently selected task
     pub fn selected_task(&self) -> Option<DisplayTask> {
-        let tasks = self.current_tasks();
-        tasks.get(self.selected_index).cloned()
+        self.current_task_refs()
+            .get(self.selected_index)
+            .map(|t| self.display_task(t))
+    }
+
+    /// Global search results for the current query (cached; see `refresh_search_results`)
+    pub fn search_all_tasks(&self) -> &[DisplayTask] {
+        &self.search_results
+    }
+
+    /// Recompute the cached global search results (call when the query or tasks change)
+    pub fn refresh_search_results(&mut self) {
+        self.search_results = self.compute_search_results();
     }
 
     /// Search all tasks globally (across all groups) with fuzzy matching
-    pub fn search_all_tasks(&self) -> Vec<DisplayTask> {
+    fn compute_search_results(&self) -> Vec<DisplayTask> {
         if self.search_query.is_empty() {
             return Vec::new();
         }
 
         let query = self.search_query.to_lowercase();
         let query_chars: Vec<char> = query.chars().collect();
+        let now = Utc::now();
 
-        let mut results: Vec<(DisplayTask, i32)> = self
+        // Score by reference (weighted match quality plus a bonus for frequently
+        // opened tasks); only matching tasks are cloned into DisplayTasks
+        let mut results: Vec<(&Task, i32)> = self
             .tasks
             .iter()
-            .map(|t| DisplayTask::new(t.clone(), self.local_state.get_overlay(&t.id)))
-            .filter_map(|dt| {
-                let score = fuzzy_score(&dt.task.name, &query_chars)
-                    .or_else(|| fuzzy_score(&dt.task.list_name, &query_chars))
-                    .or_else(|| fuzzy_score(&dt.task.status, &query_chars))
-                    .or_else(|| {
-                        dt.task
-                            .description
-                            .as_ref()
-                            .and_then(|d| fuzzy_score(d, &query_chars))
-                    })
-                    .or_else(|| {
-                        dt.task
-                            .tags
-                            .iter()
-                            .find_map(|tag| fuzzy_score(tag, &query_chars))
-                    });
-                score.map(|s| (dt, s))
+            .filter_map(|t| {
+                let score = self.search_weights.score(t, &query_chars);
+                let bonus = self
+                    .local_state
+                    .overlays
+                    .get(&t.id)
+                    .map(|o| frecency_bonus(o, now))
+                    .unwrap_or(0);
+                score.map(|s| (t, s + bonus))
             })
             .collect();
 
         // Sort by score (higher is better)
-        results.sort_by(|a, b| b.1.cmp(&a.1));
+        results.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
 
-        results.into_iter().map(|(dt, _)| dt).collect()
+        results
+            .into_iter()
+            .map(|(t, _)| DisplayTask::new(t.clone(), self.local_state.get_overlay(&t.id)))
+            .collect()
     }
 
     /// Get currently selected search result
-    pub fn selected_search_result(&self) -> Option<DisplayTask> {
-        let results = self.search_all_tasks();
-        results.get(self.search_selected_index).cloned()
+    pub fn selected_search_result(&self) -> Option<&DisplayTask> {
+        self.search_results.get(self.search_selected_index)
     }
 
     /// Move search selection up
@@ -409,59 +1537,553 @@ impl App {
 
     /// Move search selection down
     pub fn search_select_next(&mut self) {
-        let results = self.search_all_tasks();
-        if self.search_selected_index < results.len().saturating_sub(1) {
+        if self.search_selected_index < self.search_results.len().saturating_sub(1) {
             self.search_selected_index += 1;
         }
     }
 
-    /// Move selection up
-    pub fn select_prev(&mut self) {
-        if self.selected_index > 0 {
-            self.selected_index -= 1;
+    /// Handle a Normal-mode key press
+    ///
+    /// Covers the built-in `g` prefix, digit counts, and the configurable keymap.
+    /// Anything that needs the network is returned as a [`Command`] for the event
+    /// loop to carry out.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Command> {
+        let mut command = None;
+        let was_pending_g = std::mem::take(&mut self.pending_g);
+        // A count is dropped by any key that isn't a motion (`3` then `p` just pins)
+        if !self.continues_count(&key, was_pending_g) {
+            self.count_buffer.clear();
+        }
+        // Built-in `g` prefix and counts, then the configurable keymap
+        match key.code {
+            KeyCode::Char('g') if key.modifiers.is_empty() => {
+                if was_pending_g {
+                    self.count_buffer.clear();
+                    self.select_first();
+                    self.reset_preview_scroll();
+                } else {
+                    self.pending_g = true;
+                }
+            }
+            KeyCode::Char(c) if was_pending_g && c.is_ascii_lowercase() => {
+                // g{letter}: first tab whose label starts with it (or the next one)
+                self.switch_to_tab_letter(c);
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.push_count_digit(c);
+            }
+            _ => match self.keymap.action_for(&key) {
+                Some(Action::Quit) => {
+                    self.should_quit = true;
+                }
+                Some(Action::LastTask) => {
+                    self.select_last();
+                    self.reset_preview_scroll();
+                }
+                Some(Action::HalfPageDown) => {
+                    self.page_down();
+                }
+                Some(Action::HalfPageUp) => {
+                    self.page_up();
+                }
+                Some(Action::NextTask) => match self.focused_pane {
+                    FocusedPane::TaskList => {
+                        self.select_next();
+                        self.reset_preview_scroll();
+                    }
+                    FocusedPane::Preview => self.scroll_preview_down(),
+                },
+                Some(Action::PrevTask) => match self.focused_pane {
+                    FocusedPane::TaskList => {
+                        self.select_prev();
+                        self.reset_preview_scroll();
+                    }
+                    FocusedPane::Preview => self.scroll_preview_up(),
+                },
+                Some(Action::NextPane) => {
+                    self.focus_next_pane();
+                }
+                Some(Action::PrevPane) => {
+                    self.focus_prev_pane();
+                }
+                Some(Action::NextTab) => {
+                    self.next_tab();
+                }
+                Some(Action::PrevTab) => {
+                    self.prev_tab();
+                }
+                Some(Action::TogglePin) => {
+                    self.toggle_pin();
+                }
+                Some(Action::ToggleSelect) => {
+                    self.toggle_select();
+                }
+                Some(Action::ToggleCollapse) => {
+                    self.toggle_collapse();
+                }
+                Some(Action::ToggleWrap) => {
+                    self.toggle_wrap_task_names();
+                }
+                Some(Action::ToggleDense) => {
+                    self.toggle_dense();
+                }
+                Some(Action::ToggleDoneSubtasks) => {
+                    self.toggle_hide_done_subtasks();
+                }
+                Some(Action::Snooze) => {
+                    self.start_snooze();
+                }
+                Some(Action::Unsnooze) => {
+                    self.unsnooze();
+                }
+                Some(Action::Undo) => {
+                    self.undo_last();
+                }
+                Some(Action::CycleSort) => {
+                    self.cycle_sort_mode();
+                }
+                Some(Action::TagFilter) => {
+                    self.start_tag_filter();
+                }
+                Some(Action::SetPriority) => {
+                    self.start_priority_picker();
+                }
+                Some(Action::AssignMe) => {
+                    return self.set_assigned_to_me(true);
+                }
+                Some(Action::UnassignMe) => {
+                    return self.set_assigned_to_me(false);
+                }
+                Some(Action::ToggleDoneAll) => {
+                    self.toggle_done_show_all();
+                }
+                Some(Action::QrCode) => {
+                    self.show_qr_code();
+                }
+                Some(Action::JumpToId) => {
+                    self.start_jump_to_id();
+                }
+                Some(Action::ListPicker) => {
+                    self.start_list_picker();
+                }
+                Some(Action::PersonView) => {
+                    self.toggle_person_view();
+                }
+                Some(Action::ClearFilters) => {
+                    self.clear_filters();
+                }
+                Some(Action::SnoozeTomorrow) => {
+                    self.snooze_preset(SnoozePreset::Tomorrow);
+                }
+                Some(Action::SnoozeWeek) => {
+                    self.snooze_preset(SnoozePreset::Week);
+                }
+                Some(Action::SnoozeMonth) => {
+                    self.snooze_preset(SnoozePreset::Month);
+                }
+                Some(Action::WaitingOn) => {
+                    self.start_waiting_reason();
+                }
+                Some(Action::ChecklistNext) => {
+                    self.move_checklist_cursor(true);
+                }
+                Some(Action::ChecklistPrev) => {
+                    self.move_checklist_cursor(false);
+                }
+                Some(Action::ToggleChecklistItem) => {
+                    command = self.toggle_checklist_item();
+                }
+                Some(Action::EditNote) => {
+                    self.start_note();
+                }
+                Some(Action::FetchSubtasks) => {
+                    command = self.fetch_subtasks_command();
+                }
+                Some(Action::Comment) => {
+                    self.start_comment();
+                }
+                Some(Action::Export) => {
+                    self.export_markdown();
+                }
+                Some(Action::ToggleTimer) => {
+                    command = (!self.refuse_offline()).then_some(Command::ToggleTimer);
+                }
+                Some(Action::Open) => {
+                    self.open_in_browser();
+                }
+                Some(Action::OpenList) => {
+                    self.open_list_in_browser();
+                }
+                Some(Action::Copy) => {
+                    self.copy_to_clipboard();
+                }
+                Some(Action::CopyUrl) => {
+                    self.copy_url();
+                }
+                Some(Action::CopyLink) => {
+                    self.copy_markdown_link();
+                }
+                Some(Action::Search) => {
+                    self.start_search();
+                }
+                Some(Action::Refresh) => {
+                    command = Some(Command::Refresh);
+                }
+                Some(Action::FullRefresh) => {
+                    command = Some(Command::FullRefresh);
+                }
+                Some(Action::ReloadConfig) => {
+                    command = Some(Command::ReloadConfig);
+                }
+                Some(Action::MessageLog) => {
+                    self.input_mode = InputMode::MessageLog;
+                }
+                Some(Action::Help) => {
+                    self.show_help = true;
+                    self.help_scroll = 0;
+                    self.input_mode = InputMode::Help;
+                }
+                None => {}
+            },
         }
+
+        // A count prefix only survives further digits or a pending `g`
+        let is_digit = matches!(key.code, KeyCode::Char(c) if c.is_ascii_digit());
+        if !is_digit && !self.pending_g {
+            self.count_buffer.clear();
+        }
+
+        command
+    }
+
+    /// Append a digit to the pending count prefix (a leading `0` is ignored)
+    pub fn push_count_digit(&mut self, c: char) {
+        if c.is_ascii_digit() && !(c == '0' && self.count_buffer.is_empty()) {
+            self.count_buffer.push(c);
+            self.count_typed_at = Some(Instant::now());
+        }
+    }
+
+    /// Whether a key uses the pending count: more digits, the `g` prefix, or a motion
+    fn continues_count(&self, key: &KeyEvent, was_pending_g: bool) -> bool {
+        if was_pending_g {
+            return true;
+        }
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() => true,
+            KeyCode::Char('g') if key.modifiers.is_empty() => true,
+            _ => matches!(
+                self.keymap.action_for(key),
+                Some(Action::NextTask | Action::PrevTask | Action::LastTask)
+            ),
+        }
+    }
+
+    /// Switch to the tab numbered by the pending count, if any
+    fn count_to_tab(&mut self) {
+        if let Some(n) = self.take_count() {
+            self.switch_to_tab(n);
+        }
+    }
+
+    /// Treat a count nothing followed for `COUNT_TIMEOUT` as a tab number
+    pub fn expire_count(&mut self, now: Instant) {
+        if self.pending_g || self.count_buffer.is_empty() {
+            return;
+        }
+        if self
+            .count_typed_at
+            .is_some_and(|at| now.duration_since(at) >= COUNT_TIMEOUT)
+        {
+            self.count_to_tab();
+        }
+    }
+
+    /// Consume the pending count prefix, if any
+    pub fn take_count(&mut self) -> Option<usize> {
+        let count = self.count_buffer.parse().ok();
+        self.count_buffer.clear();
+        count
+    }
+
+    /// Move selection up (by the pending count, if any)
+    pub fn select_prev(&mut self) {
+        let count = self.take_count().unwrap_or(1);
+        self.selected_index = self.selected_index.saturating_sub(count);
     }
 
-    /// Move selection down
+    /// Move selection down (by the pending count, if any)
     pub fn select_next(&mut self) {
-        let tasks = self.current_tasks();
-        if self.selected_index < tasks.len().saturating_sub(1) {
-            self.selected_index += 1;
+        let count = self.take_count().unwrap_or(1);
+        let last = self.current_task_refs().len().saturating_sub(1);
+        self.selected_index = self.selected_index.saturating_add(count).min(last);
+    }
+
+    /// Jump to first task (gg)
+    pub fn select_first(&mut self) {
+        self.selected_index = 0;
+    }
+
+    /// Jump to last task (G), or to row N with a count prefix (NG)
+    pub fn select_last(&mut self) {
+        let last = self.current_task_refs().len().saturating_sub(1);
+        self.selected_index = match self.take_count() {
+            Some(row) => row.saturating_sub(1).min(last),
+            None => last,
+        };
+    }
+
+    /// Cycle the task list sort mode, keeping the selected task selected
+    pub fn cycle_sort_mode(&mut self) {
+        let selected_id = self.selected_task().map(|dt| dt.task.id);
+        self.sort_mode = self.sort_mode.next();
+        if let Some(id) = selected_id {
+            if let Some(idx) = self.current_task_refs().iter().position(|t| t.id == id) {
+                self.selected_index = idx;
+            }
         }
+        self.set_status(format!("Sorted by {}", self.sort_mode.label()));
+    }
+
+    /// Start in dense mode (from config)
+    pub fn set_dense(&mut self, dense: bool) {
+        self.dense = dense;
+    }
+
+    /// Mark rows with their list's color (from config)
+    pub fn set_color_by_list(&mut self, color_by_list: bool) {
+        self.color_by_list = color_by_list;
+    }
+
+    /// Cut long descriptions in the preview short after `max` lines (from config)
+    pub fn set_preview_max_lines(&mut self, max: Option<usize>) {
+        self.preview_max_lines = max;
+    }
+
+    /// Set the template `y` copies with (from config)
+    pub fn set_clipboard_template(&mut self, template: Option<String>) {
+        self.clipboard_template = template;
+    }
+
+    /// Toggle dense rows on and off
+    pub fn toggle_dense(&mut self) {
+        self.dense = !self.dense;
+        let label = if self.dense {
+            "Dense rows"
+        } else {
+            "Detailed rows"
+        };
+        match self.save_dense() {
+            Ok(()) => self.set_status(label),
+            Err(e) => self.set_status(format!("{} (not saved: {:#})", label, e)),
+        }
+    }
+
+    /// Write the dense setting back to the config file so it sticks across restarts
+    fn save_dense(&self) -> Result<()> {
+        if !self.persist {
+            return Ok(());
+        }
+        let mut config = Config::load_raw(self.profile.as_deref())?;
+        if config.dense != self.dense {
+            config.dense = self.dense;
+            config.save(self.profile.as_deref())?;
+        }
+        Ok(())
+    }
+
+    /// Toggle hiding Done subtasks under parents that aren't done
+    pub fn toggle_hide_done_subtasks(&mut self) {
+        self.hide_done_subtasks = !self.hide_done_subtasks;
+        self.clamp_selection();
+        self.set_status(if self.hide_done_subtasks {
+            "Hiding done subtasks"
+        } else {
+            "Showing done subtasks"
+        });
+    }
+
+    /// Toggle between cutting off long task names and wrapping them
+    pub fn toggle_wrap_task_names(&mut self) {
+        self.wrap_task_names = !self.wrap_task_names;
+        self.set_status(if self.wrap_task_names {
+            "Wrapping long task names"
+        } else {
+            "Truncating long task names"
+        });
     }
 
     /// Switch to a tab/group
+    ///
+    /// Marks are dropped, so bulk actions never reach tasks out of view.
     pub fn switch_group(&mut self, group: TaskGroup) {
         self.current_group = group;
+        self.sort_mode = self.default_sort(group);
         self.selected_index = 0;
+        self.selected_ids.clear();
+    }
+
+    /// Jump to a tab by 1-based position (a number key)
+    pub fn switch_to_tab(&mut self, position: usize) {
+        if let Some(&group) = position
+            .checked_sub(1)
+            .and_then(|idx| self.visible_groups.get(idx))
+        {
+            self.switch_group(group);
+        }
+    }
+
+    /// Jump to the first visible tab (in tab order) whose label starts with `letter` (`g{letter}`)
+    ///
+    /// From a tab with that letter, moves on to the next one (wrapping), so
+    /// repeating `gw` alternates between Waiting and Watching.
+    pub fn switch_to_tab_letter(&mut self, letter: char) {
+        let matches: Vec<TaskGroup> = self
+            .visible_groups
+            .iter()
+            .copied()
+            .filter(|g| {
+                g.label()
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.eq_ignore_ascii_case(&letter))
+            })
+            .collect();
+        let next = match matches.iter().position(|&g| g == self.current_group) {
+            Some(idx) => matches.get((idx + 1) % matches.len()),
+            None => matches.first(),
+        };
+        match next {
+            Some(&group) => self.switch_group(group),
+            None => self.set_status(format!("No tab starting with '{}'", letter)),
+        }
+    }
+
+    /// Position of the current group among the visible tabs
+    pub fn current_tab_index(&self) -> usize {
+        self.visible_groups
+            .iter()
+            .position(|&g| g == self.current_group)
+            .unwrap_or(0)
     }
 
     /// Switch to next tab
     pub fn next_tab(&mut self) {
-        let idx = (self.current_group.index() + 1) % TaskGroup::all().len();
-        if let Some(group) = TaskGroup::from_index(idx) {
-            self.switch_group(group);
+        let len = self.visible_groups.len();
+        if len == 0 {
+            return;
         }
+        let idx = (self.current_tab_index() + 1) % len;
+        self.switch_group(self.visible_groups[idx]);
     }
 
     /// Switch to previous tab
     pub fn prev_tab(&mut self) {
-        let len = TaskGroup::all().len();
-        let idx = (self.current_group.index() + len - 1) % len;
-        if let Some(group) = TaskGroup::from_index(idx) {
-            self.switch_group(group);
+        let len = self.visible_groups.len();
+        if len == 0 {
+            return;
+        }
+        let idx = (self.current_tab_index() + len - 1) % len;
+        self.switch_group(self.visible_groups[idx]);
+    }
+
+    /// Remember a task's overlay before changing it, so the change can be undone
+    fn record_undo(&mut self, task_id: &str, label: &'static str) {
+        self.record_undo_many(&[task_id.to_string()], label);
+    }
+
+    /// Remember several tasks' overlays before a bulk change, undone as one step
+    fn record_undo_many(&mut self, task_ids: &[String], label: &'static str) {
+        if self.undo.len() >= UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+        let prior = task_ids
+            .iter()
+            .map(|id| (id.clone(), self.local_state.overlays.get(id).cloned()))
+            .collect();
+        self.undo.push(UndoAction { prior, label });
+    }
+
+    /// Undo the most recent pin/snooze/unsnooze
+    pub fn undo_last(&mut self) {
+        let Some(action) = self.undo.pop() else {
+            self.set_status("Nothing to undo");
+            return;
+        };
+
+        for (task_id, prior) in &action.prior {
+            match prior {
+                Some(overlay) => {
+                    self.local_state
+                        .overlays
+                        .insert(task_id.clone(), overlay.clone());
+                }
+                None => {
+                    self.local_state.overlays.remove(task_id);
+                }
+            }
+        }
+
+        let target = match action.prior.as_slice() {
+            [(task_id, _)] => self
+                .tasks
+                .iter()
+                .find(|t| t.id == *task_id)
+                .map(|t| t.name.clone())
+                .unwrap_or_else(|| task_id.clone()),
+            many => format!("{} tasks", many.len()),
+        };
+        self.set_status(format!("Undid {}: {}", action.label, target));
+        let _ = self.save_local_state();
+    }
+
+    /// Mark or unmark the selected task for a bulk pin/snooze
+    pub fn toggle_select(&mut self) {
+        let Some(dt) = self.selected_task() else {
+            return;
+        };
+        if !self.selected_ids.remove(&dt.task.id) {
+            self.selected_ids.insert(dt.task.id);
         }
+        self.set_status(format!("{} selected", self.selected_ids.len()));
+    }
+
+    /// Take the marked task IDs (sorted, for stable undo order), ending multi-select
+    fn take_selection(&mut self) -> Vec<String> {
+        let mut ids: Vec<String> = self.selected_ids.drain().collect();
+        ids.sort();
+        ids
     }
 
-    /// Toggle pin on selected task
+    /// Toggle pin on selected task, or on every marked task
+    ///
+    /// Marked tasks are all pinned unless every one already is, in which case
+    /// they're all unpinned.
     pub fn toggle_pin(&mut self) {
+        if !self.selected_ids.is_empty() {
+            let ids = self.take_selection();
+            let pin = !ids.iter().all(|id| self.local_state.is_pinned(id));
+            self.record_undo_many(&ids, if pin { "pin" } else { "unpin" });
+            for id in &ids {
+                if self.local_state.is_pinned(id) != pin {
+                    self.local_state.toggle_pin(id);
+                }
+            }
+            let verb = if pin { "Pinned" } else { "Unpinned" };
+            let noun = if ids.len() == 1 { "task" } else { "tasks" };
+            self.set_status(format!("{} {} {}", verb, ids.len(), noun));
+            let _ = self.save_local_state();
+            return;
+        }
         if let Some(task) = self.selected_task() {
+            let label = if task.overlay.pinned { "unpin" } else { "pin" };
+            self.record_undo(&task.task.id, label);
             self.local_state.toggle_pin(&task.task.id);
             let pinned = self.local_state.is_pinned(&task.task.id);
-            self.status_message = Some(if pinned {
-                "Task pinned".to_string()
+            self.set_status(if pinned {
+                "Task pinned"
             } else {
-                "Task unpinned".to_string()
+                "Task unpinned"
             });
             let _ = self.save_local_state();
         }
@@ -469,180 +2091,3014 @@ impl App {
 
     /// Start snooze input mode
     pub fn start_snooze(&mut self) {
-        if self.selected_task().is_some() {
+        if !self.selected_ids.is_empty() || self.selected_task().is_some() {
             self.input_mode = InputMode::Snooze;
             self.snooze_input.clear();
-            self.status_message = Some("Snooze for how many days? (Enter number)".to_string());
+            let target = match self.selected_ids.len() {
+                0 => String::new(),
+                1 => " 1 task".to_string(),
+                n => format!(" {} tasks", n),
+            };
+            self.set_status(format!(
+                "Snooze{} until? (days, YYYY-MM-DD, tomorrow, next week, weekday)",
+                target
+            ));
         }
     }
 
-    /// Confirm snooze with entered days
+    /// Confirm snooze with entered days, date, or keyword
     pub fn confirm_snooze(&mut self) {
-        if let Ok(days) = self.snooze_input.parse::<i64>() {
-            if let Some(task) = self.selected_task() {
-                let until = Utc::now() + Duration::days(days);
-                self.local_state.snooze(&task.task.id, until);
-                self.status_message = Some(format!("Task snoozed for {} days", days));
-                let _ = self.save_local_state();
-            }
+        self.input_mode = InputMode::Normal;
+        if let Some(until) = parse_snooze_input(&self.snooze_input) {
+            self.snooze_selected_until(until);
         } else {
-            self.status_message = Some("Invalid number".to_string());
+            self.set_status("Invalid snooze date");
         }
-        self.input_mode = InputMode::Normal;
         self.snooze_input.clear();
     }
 
-    /// Unsnooze selected task
-    pub fn unsnooze(&mut self) {
+    /// Snooze selected task using a quick preset
+    pub fn snooze_preset(&mut self, preset: SnoozePreset) {
+        if let Some(until) = preset.until() {
+            self.snooze_selected_until(until);
+        }
+    }
+
+    /// Snooze selected task (or every marked task, after asking) until a date
+    fn snooze_selected_until(&mut self, until: DateTime<Utc>) {
+        if !self.selected_ids.is_empty() {
+            // Marks stay until the snooze runs, so cancelling keeps them
+            let mut task_ids: Vec<String> = self.selected_ids.iter().cloned().collect();
+            task_ids.sort();
+            let noun = if task_ids.len() == 1 { "task" } else { "tasks" };
+            let prompt = format!(
+                "Snooze {} {} until {}?",
+                task_ids.len(),
+                noun,
+                until.with_timezone(&Local).format("%a %b %-d")
+            );
+            let _ = self.request_confirmation(PendingAction::Snooze { task_ids, until }, prompt);
+            return;
+        }
         if let Some(task) = self.selected_task() {
-            self.local_state.unsnooze(&task.task.id);
-            self.status_message = Some("Task unsnoozed".to_string());
+            self.record_undo(&task.task.id, "snooze");
+            self.local_state.snooze(&task.task.id, until);
+            self.set_status(format!(
+                "Snoozed until {}",
+                until.with_timezone(&Local).format("%a %b %-d")
+            ));
             let _ = self.save_local_state();
         }
     }
 
-    /// Open selected task in browser
-    pub fn open_in_browser(&mut self) {
-        if let Some(task) = self.selected_task() {
-            if let Err(e) = open::that(&task.task.url) {
-                self.status_message = Some(format!("Failed to open: {}", e));
-            } else {
-                self.status_message = Some("Opened in browser".to_string());
-            }
+    /// Ask before running a mutating action (runs immediately if confirmations are off)
+    pub fn request_confirmation(
+        &mut self,
+        action: PendingAction,
+        prompt: String,
+    ) -> Option<Command> {
+        if self.confirm_actions {
+            self.confirmation = Some(Confirmation { action, prompt });
+            self.input_mode = InputMode::Confirm;
+            None
+        } else {
+            self.run_action(action)
         }
     }
 
-    /// Copy selected task details to clipboard
-    pub fn copy_to_clipboard(&mut self) {
-        if let Some(task) = self.selected_task() {
-            let mut parts = Vec::new();
+    /// Run the pending action (y/Enter in the confirmation dialog)
+    pub fn confirm_action(&mut self) -> Option<Command> {
+        self.input_mode = InputMode::Normal;
+        let confirmation = self.confirmation.take()?;
+        self.run_action(confirmation.action)
+    }
 
-            // Status
-            parts.push(format!("[{}]", task.task.status));
+    /// Discard the pending action (n/Esc in the confirmation dialog)
+    pub fn cancel_action(&mut self) {
+        self.input_mode = InputMode::Normal;
+        if self.confirmation.take().is_some() {
+            self.set_status("Cancelled");
+        }
+    }
 
-            // Type (if present)
-            if let Some(type_label) = task.task.task_type_label() {
-                parts.push(format!("[{}]", type_label));
+    /// Execute a confirmed action, returning the command that sends it to ClickUp
+    fn run_action(&mut self, action: PendingAction) -> Option<Command> {
+        match action {
+            PendingAction::Snooze { task_ids, until } => {
+                self.selected_ids.clear();
+                self.record_undo_many(&task_ids, "snooze");
+                for id in &task_ids {
+                    self.local_state.snooze(id, until);
+                }
+                let noun = if task_ids.len() == 1 { "task" } else { "tasks" };
+                self.set_status(format!(
+                    "Snoozed {} {} until {}",
+                    task_ids.len(),
+                    noun,
+                    until.with_timezone(&Local).format("%a %b %-d")
+                ));
+                let _ = self.save_local_state();
+                None
             }
-
-            // Custom ID (if present)
-            if let Some(custom_id) = &task.task.custom_id {
-                parts.push(custom_id.clone());
+            PendingAction::SetPriority { task_id, priority } => {
+                self.set_status("Updating priority...");
+                Some(Command::SetPriority { task_id, priority })
             }
-
-            // Task name
-            parts.push(task.task.name.clone());
-
-            let text = parts.join(" ");
-
-            match arboard::Clipboard::new() {
-                Ok(mut clipboard) => {
-                    if let Err(e) = clipboard.set_text(&text) {
-                        self.status_message = Some(format!("Failed to copy: {}", e));
-                    } else {
-                        self.status_message = Some("Copied task details".to_string());
-                    }
-                }
-                Err(e) => {
-                    self.status_message = Some(format!("Clipboard error: {}", e));
-                }
+            PendingAction::SetAssigned { task_id, assigned } => {
+                self.set_status("Updating assignees...");
+                Some(Command::SetAssigned { task_id, assigned })
             }
         }
     }
 
-    /// Start search mode
-    pub fn start_search(&mut self) {
-        self.input_mode = InputMode::Search;
-        self.search_query.clear();
-        self.search_selected_index = 0;
+    /// Unsnooze selected task
+    pub fn unsnooze(&mut self) {
+        if let Some(task) = self.selected_task() {
+            self.record_undo(&task.task.id, "unsnooze");
+            self.local_state.unsnooze(&task.task.id);
+            self.set_status("Task unsnoozed");
+            let _ = self.save_local_state();
+        }
     }
 
-    /// Exit search/snooze mode
-    pub fn cancel_input(&mut self) {
+    /// Start editing the selected task's note (prefilled with the current note)
+    pub fn start_note(&mut self) {
+        if let Some(task) = self.selected_task() {
+            self.note_input = task.overlay.note.unwrap_or_default();
+            self.input_mode = InputMode::Note;
+        }
+    }
+
+    /// Save the edited note on the selected task (empty input removes it)
+    pub fn confirm_note(&mut self) {
+        if let Some(task) = self.selected_task() {
+            let note = self.note_input.trim().to_string();
+            let note = (!note.is_empty()).then_some(note);
+            if note != task.overlay.note {
+                self.record_undo(&task.task.id, "note");
+                self.set_status(if note.is_some() {
+                    "Note saved"
+                } else {
+                    "Note removed"
+                });
+                self.local_state.set_note(&task.task.id, note);
+                let _ = self.save_local_state();
+            }
+        }
         self.input_mode = InputMode::Normal;
-        self.search_query.clear();
-        self.snooze_input.clear();
+        self.note_input.clear();
     }
 
-    /// Handle character input based on mode
-    pub fn handle_char(&mut self, c: char) {
-        match self.input_mode {
-            InputMode::Search => {
-                self.search_query.push(c);
-                self.search_selected_index = 0;
+    /// Start editing what the selected task is waiting on
+    pub fn start_waiting_reason(&mut self) {
+        if let Some(task) = self.selected_task() {
+            self.waiting_input = task.overlay.waiting_reason.unwrap_or_default();
+            self.input_mode = InputMode::WaitingReason;
+        }
+    }
+
+    /// Save the entered waiting-on reason (empty input removes it)
+    pub fn confirm_waiting_reason(&mut self) {
+        if let Some(task) = self.selected_task() {
+            let reason = self.waiting_input.trim().to_string();
+            let reason = (!reason.is_empty()).then_some(reason);
+            if reason != task.overlay.waiting_reason {
+                self.record_undo(&task.task.id, "waiting reason");
+                self.set_status(if reason.is_some() {
+                    "Waiting reason saved"
+                } else {
+                    "Waiting reason removed"
+                });
+                self.local_state.set_waiting_reason(&task.task.id, reason);
+                let _ = self.save_local_state();
             }
-            InputMode::Snooze => {
-                if c.is_ascii_digit() {
-                    self.snooze_input.push(c);
-                }
+        }
+        self.input_mode = InputMode::Normal;
+        self.waiting_input.clear();
+    }
+
+    /// Start writing a comment on the selected task
+    pub fn start_comment(&mut self) {
+        if self.refuse_offline() {
+            return;
+        }
+        if self.selected_task().is_some() {
+            self.comment_input.clear();
+            self.input_mode = InputMode::Comment;
+        }
+    }
+
+    /// Submit the comment being written, returning the task ID and text to post
+    ///
+    /// The comment is added to the task's cached comments right away; call
+    /// `comment_failed` to take it back out if posting fails.
+    pub fn submit_comment(&mut self) -> Option<(String, String)> {
+        self.input_mode = InputMode::Normal;
+        let text = std::mem::take(&mut self.comment_input).trim().to_string();
+        let task = self.selected_task()?;
+        if text.is_empty() {
+            return None;
+        }
+
+        let author = self.current_username();
+        if let Some(Some(comments)) = self.comments.get_mut(&task.task.id) {
+            // ClickUp lists comments newest first
+            comments.insert(
+                0,
+                Comment {
+                    author,
+                    text: text.clone(),
+                },
+            );
+        }
+        self.set_status("Posting comment...");
+        Some((task.task.id, text))
+    }
+
+    /// Remove an optimistically added comment after posting it failed
+    pub fn comment_failed(&mut self, task_id: &str, text: &str) {
+        if let Some(Some(comments)) = self.comments.get_mut(task_id) {
+            if let Some(pos) = comments.iter().position(|c| c.text == text) {
+                comments.remove(pos);
             }
-            InputMode::Normal | InputMode::Help => {}
         }
     }
 
-    /// Handle backspace in input modes
-    pub fn handle_backspace(&mut self) {
-        match self.input_mode {
-            InputMode::Search => {
-                self.search_query.pop();
-                self.search_selected_index = 0;
+    /// Current user's display name, as seen on their task assignments
+    fn current_username(&self) -> String {
+        self.user_id
+            .and_then(|id| {
+                self.tasks
+                    .iter()
+                    .flat_map(|t| &t.assignees)
+                    .find(|a| a.id == id)
+                    .and_then(|a| a.username.clone())
+            })
+            .unwrap_or_else(|| "You".to_string())
+    }
+
+    /// Record that a timer started on a task
+    pub fn begin_timer(&mut self, task_id: &str, task_name: &str) {
+        self.timer = Some(RunningTimer {
+            task_id: task_id.to_string(),
+            task_name: task_name.to_string(),
+            started: Utc::now(),
+        });
+        self.set_status(format!("Timer started: {}", task_name));
+    }
+
+    /// Record that the running timer stopped (its duration was logged)
+    pub fn finish_timer(&mut self) {
+        if let Some(timer) = self.timer.take() {
+            let elapsed = (Utc::now() - timer.started).num_seconds();
+            self.set_status(format!(
+                "Logged {} on {}",
+                format_elapsed(elapsed),
+                timer.task_name
+            ));
+        }
+    }
+
+    /// Elapsed time of the running timer, formatted for the status bar
+    pub fn timer_elapsed(&self) -> Option<String> {
+        self.timer
+            .as_ref()
+            .map(|t| format_elapsed((Utc::now() - t.started).num_seconds()))
+    }
+
+    /// ID of the task shown in the preview pane
+    fn previewed_task_id(&self) -> Option<String> {
+        if self.input_mode == InputMode::Search {
+            self.selected_search_result().map(|dt| dt.task.id.clone())
+        } else {
+            self.selected_task().map(|dt| dt.task.id)
+        }
+    }
+
+    /// Task whose comments should be fetched now, once the preview has settled on it
+    ///
+    /// Call on every loop tick; a task is returned only after it has stayed
+    /// previewed for `COMMENT_DEBOUNCE`, its comments aren't cached yet, and no
+    /// other fetch is in flight. The returned task counts as fetching until
+    /// `set_comments` is called for it.
+    pub fn comments_due(&mut self, now: Instant) -> Option<String> {
+        if self.offline {
+            return None;
+        }
+        let id = self.previewed_task_id()?;
+        if self.comments.contains_key(&id) {
+            return None;
+        }
+        match &self.comment_target {
+            Some((target, since)) if *target == id => {
+                let settled = now.duration_since(*since) >= COMMENT_DEBOUNCE
+                    && self.comments_fetching.is_none();
+                if settled {
+                    self.comments_fetching = Some(id.clone());
+                }
+                settled.then_some(id)
             }
-            InputMode::Snooze => {
-                self.snooze_input.pop();
+            _ => {
+                self.comment_target = Some((id, now));
+                None
             }
-            InputMode::Normal | InputMode::Help => {}
         }
     }
 
-    /// Clear status message
-    pub fn clear_status(&mut self) {
-        self.status_message = None;
+    /// Cache a task's comments (`None` if fetching them failed)
+    pub fn set_comments(&mut self, task_id: &str, comments: Option<Vec<Comment>>) {
+        if self.comments_fetching.as_deref() == Some(task_id) {
+            self.comments_fetching = None;
+        }
+        self.comments.insert(task_id.to_string(), comments);
     }
-}
 
-impl Default for App {
-    fn default() -> Self {
-        Self::new()
+    /// Cache a task's status history (`None` if it isn't available)
+    pub fn set_status_history(&mut self, task_id: &str, history: Option<Vec<StatusChange>>) {
+        self.status_history.insert(task_id.to_string(), history);
     }
-}
 
-/// Simple fuzzy matching score - returns Some(score) if all query chars found in order
-fn fuzzy_score(text: &str, query_chars: &[char]) -> Option<i32> {
-    if query_chars.is_empty() {
-        return Some(0);
+    /// Open selected task in browser
+    pub fn open_in_browser(&mut self) {
+        if let Some(task) = self.selected_task() {
+            self.open_task(&task.task);
+        }
     }
 
-    let text_lower = text.to_lowercase();
-    let text_chars: Vec<char> = text_lower.chars().collect();
+    /// Open the selected task's list in browser
+    pub fn open_list_in_browser(&mut self) {
+        let Some(dt) = self.selected_task() else {
+            return;
+        };
+        let Some(url) = dt.task.list_url() else {
+            self.set_status("List link unavailable (do a full refresh to fetch it)");
+            return;
+        };
+        match open::that(&url) {
+            Ok(()) => self.set_status(format!("Opened list {} in browser", dt.task.list_name)),
+            Err(e) => self.set_status(format!("Failed to open: {}", e)),
+        }
+    }
 
-    let mut query_idx = 0;
-    let mut score = 0i32;
-    let mut last_match_idx: Option<usize> = None;
-    let mut consecutive_bonus = 0;
+    /// Open the selected search result in browser and leave search
+    pub fn open_search_result(&mut self) {
+        if let Some(task) = self.selected_search_result().map(|dt| dt.task.clone()) {
+            self.open_task(&task);
+        }
+        self.input_mode = InputMode::Normal;
+    }
 
-    for (text_idx, &tc) in text_chars.iter().enumerate() {
-        if query_idx < query_chars.len() && tc == query_chars[query_idx] {
-            // Bonus for consecutive matches
-            if let Some(last) = last_match_idx {
-                if text_idx == last + 1 {
-                    consecutive_bonus += 5;
+    /// Open a task's URL, counting the open towards its search frecency
+    fn open_task(&mut self, task: &Task) {
+        if let Err(e) = open::that(&task.url) {
+            self.set_status(format!("Failed to open: {}", e));
+        } else {
+            self.set_status("Opened in browser");
+            self.local_state.record_open(&task.id, Utc::now());
+            let _ = self.save_local_state();
+        }
+    }
+
+    /// Copy selected task details to clipboard
+    pub fn copy_to_clipboard(&mut self) {
+        if let Some(task) = self.selected_task() {
+            if let Some(template) = &self.clipboard_template {
+                let text = task.render_template(template);
+                self.copy_text(text, "task details");
+                return;
+            }
+
+            let mut parts = Vec::new();
+
+            // Status
+            parts.push(format!("[{}]", task.task.status));
+
+            // Type (if present)
+            if let Some(type_label) = task.task.task_type_label() {
+                parts.push(format!("[{}]", type_label));
+            }
+
+            // Custom ID (if present)
+            if let Some(custom_id) = &task.task.custom_id {
+                parts.push(custom_id.clone());
+            }
+
+            // Task name
+            parts.push(task.task.name.clone());
+
+            self.copy_text(parts.join(" "), "task details");
+        }
+    }
+
+    /// Copy the selected task's URL to clipboard
+    pub fn copy_url(&mut self) {
+        if let Some(task) = self.selected_task() {
+            self.copy_text(task.render_template("{url}"), "task URL");
+        }
+    }
+
+    /// Copy the selected task as a Markdown link (`[PROJ-123 name](url)`) to clipboard
+    pub fn copy_markdown_link(&mut self) {
+        if let Some(task) = self.selected_task() {
+            self.copy_text(task.markdown_link(), "Markdown link");
+        }
+    }
+
+    /// Put text on the clipboard, reporting "Copied {what}" or the error
+    fn copy_text(&mut self, text: String, what: &str) {
+        match arboard::Clipboard::new() {
+            Ok(mut clipboard) => {
+                if let Err(e) = clipboard.set_text(text) {
+                    self.set_status(format!("Failed to copy: {}", e));
+                } else {
+                    self.set_status(format!("Copied {}", what));
                 }
             }
+            Err(e) => {
+                self.set_status(format!("Clipboard error: {}", e));
+            }
+        }
+    }
 
-            // Bonus for matching at word boundaries
-            if text_idx == 0 || !text_chars[text_idx - 1].is_alphanumeric() {
-                score += 10;
+    /// Copy the current view (group + filters) to the clipboard as a Markdown checklist
+    pub fn export_markdown(&mut self) {
+        let tasks = self.current_tasks();
+        if tasks.is_empty() {
+            self.set_status("No tasks to export");
+            return;
+        }
+
+        let mut title = self.current_group.label().to_string();
+        let filters: Vec<String> = [
+            self.active_list_filter
+                .as_ref()
+                .map(|l| format!("list: {}", l)),
+            self.active_tag_filter
+                .as_ref()
+                .map(|t| format!("tag: {}", t)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !filters.is_empty() {
+            title = format!("{} ({})", title, filters.join(", "));
+        }
+
+        let markdown = to_markdown(&title, &tasks, &self.status_groups);
+        self.copy_text(markdown, &format!("{} tasks as Markdown", tasks.len()));
+    }
+
+    /// Keep the selection within the current (possibly just filtered) task list
+    fn clamp_selection(&mut self) {
+        let last = self.current_task_refs().len().saturating_sub(1);
+        self.selected_index = self.selected_index.min(last);
+    }
+
+    /// Start search mode
+    pub fn start_search(&mut self) {
+        self.input_mode = InputMode::Search;
+        self.search_query.clear();
+        self.search_selected_index = 0;
+        self.refresh_search_results();
+    }
+
+    /// Start tag filter input mode (prefilled with the active filter)
+    pub fn start_tag_filter(&mut self) {
+        self.input_mode = InputMode::TagFilter;
+        self.tag_filter_input = self.active_tag_filter.clone().unwrap_or_default();
+    }
+
+    /// Apply the entered tag filter (empty input clears it)
+    pub fn confirm_tag_filter(&mut self) {
+        let tag = self.tag_filter_input.trim().to_string();
+        self.active_tag_filter = (!tag.is_empty()).then_some(tag);
+        self.selected_index = 0;
+        self.input_mode = InputMode::Normal;
+        self.tag_filter_input.clear();
+    }
+
+    /// Start typing a custom ID to jump to
+    pub fn start_jump_to_id(&mut self) {
+        self.input_mode = InputMode::JumpToId;
+        self.jump_input.clear();
+    }
+
+    /// Jump to the task with the entered custom ID (case-insensitive), switching to its tab
+    pub fn confirm_jump_to_id(&mut self) {
+        self.input_mode = InputMode::Normal;
+        let custom_id = std::mem::take(&mut self.jump_input);
+        let custom_id = custom_id.trim();
+        if custom_id.is_empty() {
+            return;
+        }
+        let Some(task) = self
+            .tasks
+            .iter()
+            .find(|t| {
+                t.custom_id
+                    .as_deref()
+                    .is_some_and(|id| id.eq_ignore_ascii_case(custom_id))
+            })
+            .cloned()
+        else {
+            self.set_status(format!("No task {}", custom_id));
+            return;
+        };
+
+        match self.reveal_task(&task) {
+            Ok(()) => self.set_status(format!("Jumped to {}", task.name)),
+            Err(why) => self.set_status(format!("{} {}", custom_id, why)),
+        }
+    }
+
+    /// Switch to a tab showing `task` and select it
+    ///
+    /// Prefers the current tab, then the first visible tab holding the task.
+    /// On failure, says why it can't be shown (e.g. "is hidden by filters").
+    fn reveal_task(&mut self, task: &Task) -> std::result::Result<(), &'static str> {
+        self.person_view = None;
+        let group = std::iter::once(self.current_group)
+            .chain(self.visible_groups.iter().copied())
+            .find(|&g| self.visible_groups.contains(&g) && self.in_group(task, g))
+            .ok_or("isn't in any visible tab")?;
+        if group != self.current_group {
+            self.switch_group(group);
+        }
+        self.selected_index = self
+            .current_task_refs()
+            .iter()
+            .position(|t| t.id == task.id)
+            .ok_or("is hidden by filters")?;
+        Ok(())
+    }
+
+    /// IDs of the tasks currently assigned to the user
+    pub fn assigned_task_ids(&self) -> HashSet<String> {
+        let Some(user_id) = self.user_id else {
+            return HashSet::new();
+        };
+        self.tasks
+            .iter()
+            .filter(|t| t.is_assigned_to(user_id))
+            .map(|t| t.id.clone())
+            .collect()
+    }
+
+    /// In triage mode, jump to the first task assigned to the user since `before`
+    /// was taken (from [`App::assigned_task_ids`]) and announce it
+    pub fn triage_new_assignments(&mut self, before: &HashSet<String>) {
+        if !self.triage {
+            return;
+        }
+        let new: HashSet<String> = self
+            .assigned_task_ids()
+            .difference(before)
+            .cloned()
+            .collect();
+        // The first in task list order, so the pick is stable
+        let Some(task) = self.tasks.iter().find(|t| new.contains(&t.id)).cloned() else {
+            return;
+        };
+        let _ = self.reveal_task(&task);
+        self.reset_preview_scroll();
+        self.set_status(match new.len() {
+            1 => format!("New task assigned: {}", task.name),
+            n => format!("New task assigned: {} (+{} more)", task.name, n - 1),
+        });
+    }
+
+    /// Clear the multi-selection if any, otherwise the active tag and list filters
+    pub fn clear_filters(&mut self) {
+        // Dropping a multi-selection comes first
+        if !self.selected_ids.is_empty() {
+            self.selected_ids.clear();
+            self.set_status("Selection cleared");
+            return;
+        }
+        let had_tag = self.active_tag_filter.take().is_some();
+        let had_list = self.active_list_filter.take().is_some();
+        let had_person = self.person_view.take().is_some();
+        if had_tag || had_list || had_person {
+            self.selected_index = 0;
+            self.set_status("Filters cleared");
+        }
+    }
+
+    /// Drill into the selected Person task's user, or back out of a person view
+    pub fn toggle_person_view(&mut self) {
+        if self.person_view.take().is_some() {
+            self.selected_index = 0;
+            self.set_status(format!("Back to {}", self.current_group.label()));
+            return;
+        }
+        let Some(dt) = self.selected_task() else {
+            return;
+        };
+        if !dt.task.is_person() {
+            self.set_status("Not a Person task");
+            return;
+        }
+        // Prefer the explicitly linked user, else an assignee other than ourselves
+        let linked = dt.task.person_id.or_else(|| {
+            dt.task
+                .assignee_ids
+                .iter()
+                .copied()
+                .find(|&id| Some(id) != self.user_id)
+        });
+        match linked {
+            Some(user_id) => {
+                self.person_view = Some(PersonView {
+                    user_id,
+                    name: dt.task.name.clone(),
+                });
+                self.selected_index = 0;
+                self.set_status(format!("Tasks for {}", dt.task.name));
+            }
+            None => {
+                self.set_status("No user linked to this Person task");
             }
+        }
+    }
 
-            score += 1;
-            last_match_idx = Some(text_idx);
-            query_idx += 1;
+    /// Distinct list names across all loaded tasks (sorted, ignoring case)
+    pub fn list_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.tasks.iter().map(|t| t.list_name.clone()).collect();
+        names.sort_by_key(|n| n.to_lowercase());
+        names.dedup_by(|a, b| a.to_lowercase() == b.to_lowercase());
+        names
+    }
+
+    /// List names matching the picker query, best fuzzy match first
+    pub fn list_picker_matches(&self) -> Vec<String> {
+        let query_chars: Vec<char> = self.list_picker_query.to_lowercase().chars().collect();
+        let mut matches: Vec<(String, i32)> = self
+            .list_names()
+            .into_iter()
+            .filter_map(|name| fuzzy_score(&name, &query_chars).map(|score| (name, score)))
+            .collect();
+        matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(name, _)| name).collect()
+    }
+
+    /// Open the list picker popup
+    pub fn start_list_picker(&mut self) {
+        self.input_mode = InputMode::ListPicker;
+        self.list_picker_query.clear();
+        self.list_picker_index = 0;
+    }
+
+    /// Move list picker selection up
+    pub fn list_picker_prev(&mut self) {
+        self.list_picker_index = self.list_picker_index.saturating_sub(1);
+    }
+
+    /// Move list picker selection down
+    pub fn list_picker_next(&mut self) {
+        let last = self.list_picker_matches().len().saturating_sub(1);
+        self.list_picker_index = (self.list_picker_index + 1).min(last);
+    }
+
+    /// Open the priority picker on the selected task's current priority
+    pub fn start_priority_picker(&mut self) {
+        if self.refuse_offline() {
+            return;
         }
+        let Some(dt) = self.selected_task() else {
+            return;
+        };
+        self.priority_picker_index = PRIORITY_CHOICES
+            .iter()
+            .position(|&p| p == dt.task.priority)
+            .unwrap_or(PRIORITY_CHOICES.len() - 1);
+        self.input_mode = InputMode::PriorityPicker;
     }
 
-    if query_idx == query_chars.len() {
-        Some(score + consecutive_bonus)
-    } else {
-        None
+    /// Move priority picker selection up
+    pub fn priority_picker_prev(&mut self) {
+        self.priority_picker_index = self.priority_picker_index.saturating_sub(1);
+    }
+
+    /// Move priority picker selection down
+    pub fn priority_picker_next(&mut self) {
+        self.priority_picker_index =
+            (self.priority_picker_index + 1).min(PRIORITY_CHOICES.len() - 1);
+    }
+
+    /// Pick by number: `1`-`4` for Urgent..Low, `0` to clear
+    pub fn priority_picker_digit(&mut self, digit: char) -> Option<Command> {
+        let index = match digit {
+            '1'..='4' => digit as usize - '1' as usize,
+            '0' => PRIORITY_CHOICES.len() - 1,
+            _ => return None,
+        };
+        self.priority_picker_index = index;
+        self.confirm_priority_picker()
+    }
+
+    /// Ask to change the selected task's priority to the picked one
+    pub fn confirm_priority_picker(&mut self) -> Option<Command> {
+        self.input_mode = InputMode::Normal;
+        let dt = self.selected_task()?;
+        let priority = PRIORITY_CHOICES[self.priority_picker_index];
+        if priority == dt.task.priority {
+            self.set_status("Priority unchanged");
+            return None;
+        }
+        let prompt = format!(
+            "Set priority of \"{}\" to {}?",
+            dt.task.name,
+            priority_label(priority).unwrap_or("none")
+        );
+        self.request_confirmation(
+            PendingAction::SetPriority {
+                task_id: dt.task.id,
+                priority,
+            },
+            prompt,
+        )
+    }
+
+    /// Record a priority change ClickUp accepted, keeping the task selected as it re-sorts
+    pub fn apply_priority(&mut self, task_id: &str, priority: Option<u8>) {
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
+            task.priority = priority;
+        }
+        if let Some(idx) = self
+            .current_task_refs()
+            .iter()
+            .position(|t| t.id == task_id)
+        {
+            self.selected_index = idx;
+        }
+        self.set_status(format!(
+            "Priority set to {}",
+            priority_label(priority).unwrap_or("none")
+        ));
+        let _ = self.save_tasks_cache();
+    }
+
+    /// Highlighted checklist item of a task, if the highlight is on that task
+    pub fn checklist_cursor_for(&self, task_id: &str) -> Option<usize> {
+        self.checklist_cursor
+            .as_ref()
+            .filter(|(id, _)| id == task_id)
+            .map(|&(_, idx)| idx)
+    }
+
+    /// Move the checklist highlight in the preview down (`true`) or up
+    pub fn move_checklist_cursor(&mut self, down: bool) {
+        let Some(dt) = self.selected_task() else {
+            return;
+        };
+        let count: usize = dt.task.checklists.iter().map(|c| c.items.len()).sum();
+        if count == 0 {
+            self.set_status("No checklist on this task");
+            return;
+        }
+        let idx = match (self.checklist_cursor_for(&dt.task.id), down) {
+            (None, _) => 0,
+            (Some(idx), true) => (idx + 1).min(count - 1),
+            (Some(idx), false) => idx.saturating_sub(1),
+        };
+        self.checklist_cursor = Some((dt.task.id, idx));
+    }
+
+    /// Check off (or un-check) the highlighted checklist item
+    ///
+    /// The change shows (and is cached) right away; the returned command sends it
+    /// to ClickUp, and [`App::set_checklist_item_resolved`] undoes it on failure.
+    pub fn toggle_checklist_item(&mut self) -> Option<Command> {
+        if self.refuse_offline() {
+            return None;
+        }
+        let dt = self.selected_task()?;
+        let Some(cursor) = self.checklist_cursor_for(&dt.task.id) else {
+            self.set_status("Highlight a checklist item first");
+            return None;
+        };
+        let (checklist, item) = dt
+            .task
+            .checklists
+            .iter()
+            .flat_map(|c| c.items.iter().map(move |i| (c, i)))
+            .nth(cursor)?;
+        if checklist.id.is_empty() || item.id.is_empty() {
+            self.set_status("Refresh to edit this checklist");
+            return None;
+        }
+        let resolved = !item.resolved;
+        self.set_status(if resolved {
+            format!("Checked off: {}", item.name)
+        } else {
+            format!("Unchecked: {}", item.name)
+        });
+        self.set_checklist_item_resolved(&dt.task.id, &item.id, resolved);
+        Some(Command::SetChecklistItem {
+            task_id: dt.task.id.clone(),
+            checklist_id: checklist.id.clone(),
+            item_id: item.id.clone(),
+            resolved,
+        })
+    }
+
+    /// Record a checklist item's state locally and in the cache
+    pub fn set_checklist_item_resolved(&mut self, task_id: &str, item_id: &str, resolved: bool) {
+        let item = self
+            .tasks
+            .iter_mut()
+            .find(|t| t.id == task_id)
+            .and_then(|t| {
+                t.checklists
+                    .iter_mut()
+                    .flat_map(|c| c.items.iter_mut())
+                    .find(|i| i.id == item_id)
+            });
+        if let Some(item) = item {
+            item.resolved = resolved;
+        }
+        let _ = self.save_tasks_cache();
+    }
+
+    /// Show the selected task's URL as a QR code
+    pub fn show_qr_code(&mut self) {
+        if self.selected_task().is_some() {
+            self.input_mode = InputMode::QrCode;
+        }
+    }
+
+    /// Ask to add the user to (`true`) or remove them from the selected task's assignees
+    pub fn set_assigned_to_me(&mut self, assigned: bool) -> Option<Command> {
+        if self.refuse_offline() {
+            return None;
+        }
+        let Some(user_id) = self.user_id else {
+            self.set_status("Set a numeric user_id to change assignees");
+            return None;
+        };
+        let dt = self.selected_task()?;
+        if dt.task.is_assigned_to(user_id) == assigned {
+            self.set_status(if assigned {
+                "Already assigned to you"
+            } else {
+                "Not assigned to you"
+            });
+            return None;
+        }
+        let prompt = if assigned {
+            format!("Assign \"{}\" to yourself?", dt.task.name)
+        } else {
+            format!("Unassign yourself from \"{}\"?", dt.task.name)
+        };
+        self.request_confirmation(
+            PendingAction::SetAssigned {
+                task_id: dt.task.id,
+                assigned,
+            },
+            prompt,
+        )
+    }
+
+    /// Record an assignment change ClickUp accepted, keeping the task selected
+    pub fn apply_assignment(&mut self, task_id: &str, assigned: bool) {
+        let Some(user_id) = self.user_id else {
+            return;
+        };
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
+            task.assignee_ids.retain(|&id| id != user_id);
+            if assigned {
+                task.assignee_ids.push(user_id);
+            }
+        }
+        if let Some(idx) = self
+            .current_task_refs()
+            .iter()
+            .position(|t| t.id == task_id)
+        {
+            self.selected_index = idx;
+        }
+        self.clamp_selection();
+        self.set_status(if assigned {
+            "Assigned to you"
+        } else {
+            "Unassigned from you"
+        });
+        let _ = self.save_tasks_cache();
+    }
+
+    /// Filter the task list to the selected list
+    pub fn confirm_list_picker(&mut self) {
+        if let Some(name) = self.list_picker_matches().get(self.list_picker_index) {
+            self.set_status(format!("Showing list: {}", name));
+            self.active_list_filter = Some(name.clone());
+            self.selected_index = 0;
+        }
+        self.input_mode = InputMode::Normal;
+        self.list_picker_query.clear();
+    }
+
+    /// Exit search/snooze/filter mode
+    pub fn cancel_input(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.search_query.clear();
+        self.refresh_search_results();
+        self.snooze_input.clear();
+        self.note_input.clear();
+        self.waiting_input.clear();
+        self.comment_input.clear();
+        self.tag_filter_input.clear();
+        self.jump_input.clear();
+        self.clear_status();
+    }
+
+    /// Handle character input based on mode
+    pub fn handle_char(&mut self, c: char) {
+        match self.input_mode {
+            InputMode::Search => {
+                self.search_query.push(c);
+                self.search_selected_index = 0;
+                self.refresh_search_results();
+                self.clamp_selection();
+            }
+            InputMode::Snooze => {
+                if c.is_ascii_alphanumeric() || c == '-' || c == ' ' {
+                    self.snooze_input.push(c);
+                }
+            }
+            InputMode::Note => {
+                self.note_input.push(c);
+            }
+            InputMode::WaitingReason => {
+                if c != '\n' {
+                    self.waiting_input.push(c);
+                }
+            }
+            InputMode::Comment => {
+                self.comment_input.push(c);
+            }
+            InputMode::TagFilter => {
+                self.tag_filter_input.push(c);
+            }
+            InputMode::JumpToId => {
+                if !c.is_whitespace() {
+                    self.jump_input.push(c);
+                }
+            }
+            InputMode::ListPicker => {
+                self.list_picker_query.push(c);
+                self.list_picker_index = 0;
+            }
+            InputMode::Normal
+            | InputMode::Confirm
+            | InputMode::Help
+            | InputMode::PriorityPicker
+            | InputMode::QrCode
+            | InputMode::MessageLog => {}
+        }
+    }
+
+    /// Handle backspace in input modes
+    pub fn handle_backspace(&mut self) {
+        match self.input_mode {
+            InputMode::Search => {
+                self.search_query.pop();
+                self.search_selected_index = 0;
+                self.refresh_search_results();
+                self.clamp_selection();
+            }
+            InputMode::Snooze => {
+                self.snooze_input.pop();
+            }
+            InputMode::Note => {
+                self.note_input.pop();
+            }
+            InputMode::WaitingReason => {
+                self.waiting_input.pop();
+            }
+            InputMode::Comment => {
+                self.comment_input.pop();
+            }
+            InputMode::TagFilter => {
+                self.tag_filter_input.pop();
+            }
+            InputMode::JumpToId => {
+                self.jump_input.pop();
+            }
+            InputMode::ListPicker => {
+                self.list_picker_query.pop();
+                self.list_picker_index = 0;
+            }
+            InputMode::Normal
+            | InputMode::Confirm
+            | InputMode::Help
+            | InputMode::PriorityPicker
+            | InputMode::QrCode
+            | InputMode::MessageLog => {}
+        }
+    }
+
+    /// Mark a task fetch as started; `false` if one is already in flight
+    pub fn start_loading(&mut self) -> bool {
+        if self.loading_since.is_some() {
+            return false;
+        }
+        self.loading_since = Some(Instant::now());
+        true
+    }
+
+    /// Mark the in-flight task fetch as done
+    pub fn finish_loading(&mut self) {
+        self.loading_since = None;
+    }
+
+    /// Current loading spinner frame, if a fetch is in flight
+    pub fn spinner_frame(&self, now: Instant) -> Option<char> {
+        self.loading_since.map(|since| {
+            let ticks = now.duration_since(since).as_millis() / SPINNER_FRAME_MS;
+            SPINNER_FRAMES[(ticks % SPINNER_FRAMES.len() as u128) as usize]
+        })
+    }
+
+    /// Show a status message (cleared after `status_timeout`)
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        if self.message_log.len() >= MESSAGE_LOG_LIMIT {
+            self.message_log.pop_front();
+        }
+        self.message_log.push_back((Local::now(), message.clone()));
+        self.status_message = Some(message);
+        self.status_set_at = Some(Instant::now());
+    }
+
+    /// Clear status message
+    pub fn clear_status(&mut self) {
+        self.status_message = None;
+        self.status_set_at = None;
+    }
+
+    /// Clear the status message once it has been up for `status_timeout`
+    pub fn expire_status(&mut self, now: Instant) {
+        if let (Some(timeout), Some(set_at)) = (self.status_timeout, self.status_set_at) {
+            if now.duration_since(set_at) >= timeout {
+                self.clear_status();
+            }
+        }
+    }
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse snooze input into a wake-up time.
+///
+/// Accepts a number of days (`3`), an absolute date (`2024-12-25`), or one of the
+/// keywords `tomorrow`, `next week` (next Monday), or a weekday name (`monday`, `fri`).
+/// Dates and keywords resolve to local midnight of that day. Returns `None` for
+/// unrecognized input or dates that are not in the future.
+pub fn parse_snooze_input(input: &str) -> Option<DateTime<Utc>> {
+    let input = input.trim().to_lowercase();
+    let now = Local::now();
+    let today = now.date_naive();
+
+    if let Ok(days) = input.parse::<i64>() {
+        return (days > 0).then(|| Utc::now() + Duration::days(days));
+    }
+
+    let date = match input.as_str() {
+        "tomorrow" => today.succ_opt()?,
+        "next week" => next_weekday(today, Weekday::Mon),
+        _ => match parse_weekday(&input) {
+            Some(weekday) => next_weekday(today, weekday),
+            None => NaiveDate::parse_from_str(&input, "%Y-%m-%d").ok()?,
+        },
+    };
+
+    let until = Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()?
+        .with_timezone(&Utc);
+    (until > now.with_timezone(&Utc)).then_some(until)
+}
+
+/// Parse a full or abbreviated weekday name
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    match input {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Next occurrence of a weekday strictly after `from`
+fn next_weekday(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days_ahead = (weekday.num_days_from_monday() as i64
+        - from.weekday().num_days_from_monday() as i64)
+        .rem_euclid(7);
+    let days_ahead = if days_ahead == 0 { 7 } else { days_ahead };
+    from + Duration::days(days_ahead)
+}
+
+/// Map each task hidden by a collapsed ancestor to that (outermost) ancestor's ID
+///
+/// `tree` must list parents before their subtasks, as `task_tree` does.
+fn collapsed_descendants<'a>(
+    tree: &[&'a Task],
+    collapsed: &HashSet<String>,
+) -> HashMap<&'a str, &'a str> {
+    let ids: HashSet<&str> = tree.iter().map(|t| t.id.as_str()).collect();
+    let mut hidden: HashMap<&'a str, &'a str> = HashMap::new();
+    for task in tree {
+        let Some(parent) = task.parent_id.as_deref().filter(|p| ids.contains(p)) else {
+            continue;
+        };
+        // Hidden along with its parent, or directly under a collapsed (visible) parent
+        let root = match hidden.get(parent) {
+            Some(&root) => Some(root),
+            None => collapsed.contains(parent).then_some(parent),
+        };
+        if let Some(root) = root {
+            hidden.insert(task.id.as_str(), root);
+        }
+    }
+    hidden
+}
+
+/// Format seconds as `m:ss`, or `h:mm:ss` from an hour up
+pub fn format_elapsed(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Format a Unix timestamp (ms) relative to `now`, e.g. "3d ago"
+pub fn format_age(timestamp_ms: i64, now: DateTime<Utc>) -> String {
+    let Some(then) = Utc.timestamp_millis_opt(timestamp_ms).single() else {
+        return "unknown".to_string();
+    };
+    let secs = (now - then).num_seconds().max(0);
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86_400 => format!("{}h ago", secs / 3600),
+        86_400..2_592_000 => format!("{}d ago", secs / 86_400),
+        2_592_000..31_536_000 => format!("{}mo ago", secs / 2_592_000),
+        _ => format!("{}y ago", secs / 31_536_000),
+    }
+}
+
+/// How long a Backlog task has gone without an update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskAge {
+    Fresh,
+    Aging,
+    Stale,
+}
+
+/// Time left on a snooze, e.g. "wakes in 3d" (`None` once it has elapsed)
+pub fn format_wake(until: DateTime<Utc>, now: DateTime<Utc>) -> Option<String> {
+    let secs = (until - now).num_seconds();
+    let left = match secs {
+        ..=0 => return None,
+        1..60 => "<1m".to_string(),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86_400 => format!("{}h", secs / 3600),
+        86_400..2_592_000 => format!("{}d", secs / 86_400),
+        _ => format!("{}mo", secs / 2_592_000),
+    };
+    Some(format!("wakes in {}", left))
+}
+
+/// Render tasks (in display order) as a Markdown checklist under a `## title` heading
+///
+/// Subtasks are indented under their visible parents; done tasks are checked.
+pub fn to_markdown(title: &str, tasks: &[DisplayTask], status_groups: &StatusGroups) -> String {
+    let depths: HashMap<&str, usize> = {
+        let mut depths = HashMap::new();
+        for dt in tasks {
+            let depth = dt
+                .task
+                .parent_id
+                .as_deref()
+                .and_then(|pid| depths.get(pid).map(|d| d + 1))
+                .unwrap_or(0);
+            depths.insert(dt.task.id.as_str(), depth);
+        }
+        depths
+    };
+
+    let mut out = format!("## {}\n\n", title);
+    for dt in tasks {
+        let task = &dt.task;
+        let indent = "  ".repeat(depths[task.id.as_str()]);
+        let checked = if task.group(status_groups) == TaskGroup::Done {
+            "x"
+        } else {
+            " "
+        };
+        let custom_id = task
+            .custom_id
+            .as_ref()
+            .map(|id| format!("{} ", id))
+            .unwrap_or_default();
+        let name = task.name.replace('[', "\\[").replace(']', "\\]");
+        out.push_str(&format!(
+            "{}- [{}] **{}** {}[{}]({})\n",
+            indent, checked, task.status, custom_id, name, task.url
+        ));
+    }
+    out
+}
+
+/// Search score bonus for how often and how recently a task was opened
+///
+/// Each open (up to `FRECENCY_MAX_OPENS`) is worth `FRECENCY_PER_OPEN`, halving
+/// every `FRECENCY_HALF_LIFE_DAYS` since the last open.
+pub fn frecency_bonus(overlay: &TaskOverlay, now: DateTime<Utc>) -> i32 {
+    let Some(last_opened) = overlay.last_opened else {
+        return 0;
+    };
+    let age_days = (now - last_opened).num_seconds().max(0) as f64 / 86_400.0;
+    let opens = overlay.open_count.min(FRECENCY_MAX_OPENS) as f64;
+    let decay = 0.5f64.powf(age_days / FRECENCY_HALF_LIFE_DAYS);
+    (opens * FRECENCY_PER_OPEN * decay).round() as i32
+}
+
+/// Simple fuzzy matching score - returns Some(score) if all query chars found in order
+fn fuzzy_score(text: &str, query_chars: &[char]) -> Option<i32> {
+    fuzzy_match(text, query_chars).map(|(score, _)| score)
+}
+
+/// Lowercase each char of `text`, keeping one char per original char (so indices line up)
+fn lower_chars(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// Fuzzy match returning the score and the char indices in `text` that matched
+pub fn fuzzy_match(text: &str, query_chars: &[char]) -> Option<(i32, Vec<usize>)> {
+    if query_chars.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let text_chars = lower_chars(text);
+    let mut positions = Vec::with_capacity(query_chars.len());
+
+    let mut query_idx = 0;
+    let mut score = 0i32;
+    let mut last_match_idx: Option<usize> = None;
+    let mut consecutive_bonus = 0;
+
+    for (text_idx, &tc) in text_chars.iter().enumerate() {
+        if query_idx < query_chars.len() && tc == query_chars[query_idx] {
+            // Bonus for consecutive matches
+            if let Some(last) = last_match_idx {
+                if text_idx == last + 1 {
+                    consecutive_bonus += 5;
+                }
+            }
+
+            // Bonus for matching at word boundaries
+            if text_idx == 0 || !text_chars[text_idx - 1].is_alphanumeric() {
+                score += 10;
+            }
+
+            score += 1;
+            last_match_idx = Some(text_idx);
+            positions.push(text_idx);
+            query_idx += 1;
+        }
+    }
+
+    if query_idx == query_chars.len() {
+        Some((score + consecutive_bonus, positions))
+    } else {
+        None
+    }
+}
+
+/// Char indices in `text` to highlight for `query`: a case-insensitive substring
+/// match if there is one, otherwise the fuzzy match positions
+pub fn match_positions(text: &str, query: &str) -> Vec<usize> {
+    let positions = substring_positions(text, query);
+    if !positions.is_empty() {
+        return positions;
+    }
+    fuzzy_match(text, &lower_chars(query))
+        .map(|(_, positions)| positions)
+        .unwrap_or_default()
+}
+
+/// Char indices of the first case-insensitive occurrence of `query` in `text`
+/// (empty if there isn't one), as the task list filter matches
+pub fn substring_positions(text: &str, query: &str) -> Vec<usize> {
+    let query_chars = lower_chars(query);
+    if query_chars.is_empty() {
+        return Vec::new();
+    }
+    lower_chars(text)
+        .windows(query_chars.len())
+        .position(|window| window == query_chars.as_slice())
+        .map(|start| (start..start + query_chars.len()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// App that never touches the on-disk state or cache
+    fn test_app() -> App {
+        App {
+            persist: false,
+            ..App::new()
+        }
+    }
+
+    fn task(id: &str) -> Task {
+        Task::test(id)
+    }
+
+    /// IDs of the current tab's tasks, in display order
+    fn ids(app: &App) -> Vec<String> {
+        app.current_task_refs()
+            .iter()
+            .map(|t| t.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_count_prefix_navigation() {
+        let mut app = test_app();
+        app.tasks = (0..20).map(|i| task(&format!("{:02}", i))).collect();
+
+        app.push_count_digit('5');
+        app.select_next();
+        assert_eq!(app.selected_index, 5);
+
+        app.push_count_digit('1');
+        app.push_count_digit('0');
+        app.select_prev();
+        assert_eq!(app.selected_index, 0);
+
+        app.push_count_digit('9');
+        app.push_count_digit('9');
+        app.select_next();
+        assert_eq!(app.selected_index, 19);
+        assert!(app.count_buffer.is_empty());
+
+        app.push_count_digit('0');
+        assert!(app.count_buffer.is_empty());
+        app.select_prev();
+        assert_eq!(app.selected_index, 18);
+    }
+
+    fn press(app: &mut App, c: char) -> Option<Command> {
+        app.handle_key(KeyEvent::from(KeyCode::Char(c)))
+    }
+
+    #[test]
+    fn test_handle_key_navigation() {
+        let mut app = test_app();
+        app.tasks = (0..20).map(|i| task(&format!("{:02}", i))).collect();
+
+        assert_eq!(press(&mut app, 'j'), None);
+        assert_eq!(app.selected_index, 1);
+        press(&mut app, '3');
+        press(&mut app, 'j');
+        assert_eq!(app.selected_index, 4);
+        press(&mut app, 'G');
+        assert_eq!(app.selected_index, 19);
+
+        press(&mut app, 'g');
+        assert!(app.pending_g);
+        press(&mut app, 'g');
+        assert!(!app.pending_g);
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_toggle_dense_saves_to_config() {
+        let _env = crate::config::EnvGuard::new(&["XDG_CONFIG_HOME"]);
+        let root = std::env::temp_dir().join(format!("clickup-tui-dense-{}", std::process::id()));
+        std::env::set_var("XDG_CONFIG_HOME", &root);
+
+        let mut app = App::new();
+        app.set_profile(Some("dense".to_string()));
+        Config {
+            user_id: "42".to_string(),
+            ..Config::default()
+        }
+        .save(Some("dense"))
+        .unwrap();
+
+        press(&mut app, 'z');
+        assert!(app.dense);
+        let config = Config::load_raw(Some("dense")).unwrap();
+        assert!(config.dense);
+        assert_eq!(config.user_id, "42");
+        press(&mut app, 'z');
+        assert!(!Config::load_raw(Some("dense")).unwrap().dense);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_number_keys_switch_tabs() {
+        let mut app = test_app();
+        app.tasks = (0..20).map(|i| task(&format!("{:02}", i))).collect();
+        let tab = |n: usize| TaskGroup::all()[n - 1];
+
+        // Left alone, a number switches tabs
+        press(&mut app, '3');
+        assert_eq!(app.current_group, TaskGroup::MyAction);
+        app.expire_count(Instant::now() + COUNT_TIMEOUT);
+        assert_eq!(app.current_group, tab(3));
+        press(&mut app, '1');
+        app.expire_count(Instant::now() + COUNT_TIMEOUT);
+        assert_eq!(app.current_group, tab(1));
+
+        // Followed by a non-motion key, it's dropped and the key runs
+        press(&mut app, '3');
+        press(&mut app, 'p');
+        assert!(app.count_buffer.is_empty());
+        app.expire_count(Instant::now() + COUNT_TIMEOUT);
+        assert_eq!(app.current_group, tab(1));
+        assert!(app.local_state.is_pinned("00"));
+
+        // Followed by a motion, it's a count
+        press(&mut app, '2');
+        press(&mut app, 'j');
+        assert_eq!(app.current_group, tab(1));
+        assert_eq!(app.selected_index, 2);
+        app.expire_count(Instant::now() + COUNT_TIMEOUT);
+        assert_eq!(app.current_group, tab(1));
+
+        // A pending `g` holds the count until the next key, which drops it
+        press(&mut app, '2');
+        press(&mut app, 'g');
+        app.expire_count(Instant::now() + COUNT_TIMEOUT);
+        assert_eq!(app.current_group, tab(1));
+        press(&mut app, 'g');
+        assert!(app.count_buffer.is_empty());
+        assert_eq!(app.selected_index, 0);
+
+        press(&mut app, '7');
+        app.expire_count(Instant::now() + COUNT_TIMEOUT);
+        assert_eq!(app.current_group, TaskGroup::Today);
+    }
+
+    #[test]
+    fn test_handle_key_actions() {
+        let mut app = test_app();
+        app.tasks = vec![task("a")];
+
+        press(&mut app, 'p');
+        assert!(app.local_state.is_pinned("a"));
+        press(&mut app, 'p');
+        assert!(!app.local_state.is_pinned("a"));
+
+        assert_eq!(press(&mut app, 'r'), Some(Command::Refresh));
+        assert_eq!(press(&mut app, 'R'), Some(Command::FullRefresh));
+        assert_eq!(press(&mut app, 'T'), Some(Command::ToggleTimer));
+        assert_eq!(press(&mut app, 'C'), Some(Command::ReloadConfig));
+
+        press(&mut app, '?');
+        assert_eq!(app.input_mode, InputMode::Help);
+        app.input_mode = InputMode::Normal;
+        press(&mut app, 'M');
+        assert_eq!(app.input_mode, InputMode::MessageLog);
+        app.input_mode = InputMode::Normal;
+        press(&mut app, 'q');
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_message_log_keeps_recent_statuses() {
+        let mut app = test_app();
+        app.set_status("Refresh failed: timed out");
+        app.clear_status();
+        assert_eq!(app.message_log.len(), 1);
+        assert_eq!(app.message_log[0].1, "Refresh failed: timed out");
+
+        for i in 0..MESSAGE_LOG_LIMIT {
+            app.set_status(format!("message {}", i));
+        }
+        assert_eq!(app.message_log.len(), MESSAGE_LOG_LIMIT);
+        assert_eq!(app.message_log.front().unwrap().1, "message 0");
+        assert_eq!(
+            app.message_log.back().unwrap().1,
+            format!("message {}", MESSAGE_LOG_LIMIT - 1)
+        );
+    }
+
+    #[test]
+    fn test_triage_jumps_to_new_assignment() {
+        let mut app = test_app();
+        app.user_id = Some(7);
+        let mine = |id: &str| Task {
+            assignee_ids: vec![7],
+            ..task(id)
+        };
+        app.set_tasks(vec![mine("a"), mine("b"), task("c")]);
+        let before = app.assigned_task_ids();
+
+        // Off by default
+        app.set_tasks(vec![mine("a"), mine("b"), mine("c")]);
+        app.triage_new_assignments(&before);
+        assert_eq!(app.selected_index, 0);
+
+        app.set_triage(true);
+        app.triage_new_assignments(&before);
+        assert_eq!(app.selected_task().unwrap().task.id, "c");
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("New task assigned: Task c")
+        );
+    }
+
+    #[test]
+    fn test_toggle_checklist_item() {
+        use crate::models::{Checklist, ChecklistItem};
+
+        let item = |id: &str, resolved| ChecklistItem {
+            id: id.to_string(),
+            name: format!("Item {}", id),
+            resolved,
+        };
+        let mut app = test_app();
+        app.tasks = vec![Task {
+            checklists: vec![Checklist {
+                id: "c1".to_string(),
+                name: "Checklist".to_string(),
+                items: vec![item("i1", true), item("i2", false)],
+            }],
+            ..task("a")
+        }];
+
+        assert_eq!(app.toggle_checklist_item(), None);
+        app.move_checklist_cursor(true);
+        app.move_checklist_cursor(true);
+        app.move_checklist_cursor(true);
+        assert_eq!(app.checklist_cursor_for("a"), Some(1));
+
+        assert_eq!(
+            app.toggle_checklist_item(),
+            Some(Command::SetChecklistItem {
+                task_id: "a".to_string(),
+                checklist_id: "c1".to_string(),
+                item_id: "i2".to_string(),
+                resolved: true,
+            })
+        );
+        assert!(app.tasks[0].checklists[0].items[1].resolved);
+
+        // A failed update puts it back
+        app.set_checklist_item_resolved("a", "i2", false);
+        assert!(!app.tasks[0].checklists[0].items[1].resolved);
+
+        // The highlight stays with its task if another one ends up selected
+        app.tasks.push(task("b"));
+        app.selected_index = app
+            .current_task_refs()
+            .iter()
+            .position(|t| t.id == "b")
+            .unwrap();
+        assert_eq!(app.toggle_checklist_item(), None);
+        assert_eq!(app.checklist_cursor_for("a"), Some(1));
+
+        app.set_offline(true);
+        assert_eq!(app.toggle_checklist_item(), None);
+    }
+
+    fn local_date(until: DateTime<Utc>) -> NaiveDate {
+        until.with_timezone(&Local).date_naive()
+    }
+
+    #[test]
+    fn test_set_tasks_keeps_selection() {
+        let mut app = test_app();
+        app.set_tasks(vec![task("a"), task("b"), task("c")]);
+        app.selected_index = 1;
+
+        app.set_tasks(vec![task("0"), task("a"), task("b"), task("c")]);
+        assert_eq!(app.selected_task().unwrap().task.id, "b");
+
+        app.set_tasks(vec![task("a"), task("c")]);
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_set_tasks_reports_changes() {
+        let mut app = test_app();
+        let diff = app.set_tasks(vec![task("a"), task("b"), task("c")]);
+        assert_eq!(diff.added, 3);
+
+        let mut b = task("b");
+        b.status = "in review".to_string();
+        let diff = app.set_tasks(vec![task("a"), b, task("d"), task("e")]);
+        assert_eq!(
+            diff,
+            TaskDiff {
+                added: 2,
+                moved: 1,
+                removed: 1
+            }
+        );
+        assert_eq!(diff.summary().as_deref(), Some("2 new, 1 moved, 1 removed"));
+
+        let diff = app.set_tasks(app.tasks.clone());
+        assert_eq!(diff.summary(), None);
+    }
+
+    #[test]
+    fn test_freshness_label() {
+        let mut app = test_app();
+        let now = Utc::now();
+        app.local_state.last_refresh = Some(now - Duration::hours(3));
+        assert_eq!(app.freshness_label(now), "cached");
+
+        app.set_tasks(vec![task("a")]);
+        let refreshed = app.local_state.last_refresh.unwrap();
+        assert_eq!(
+            app.freshness_label(refreshed + Duration::minutes(5)),
+            "updated 5m ago"
+        );
+    }
+
+    #[test]
+    fn test_offline_refuses_changes() {
+        let mut app = test_app();
+        app.user_id = Some(1);
+        app.tasks = vec![task("a")];
+        app.set_offline(true);
+
+        app.start_comment();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        app.start_priority_picker();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.set_assigned_to_me(true), None);
+        assert!(app.confirmation.is_none());
+        assert_eq!(press(&mut app, 'T'), None);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Offline mode: changes can't be sent to ClickUp")
+        );
+
+        let now = Instant::now();
+        assert_eq!(app.comments_due(now), None);
+        assert_eq!(app.comments_due(now + COMMENT_DEBOUNCE), None);
+    }
+
+    #[test]
+    fn test_merge_tasks_by_id() {
+        let mut app = test_app();
+        assert_eq!(app.sync_cursor(), None);
+
+        let updated = |id: &str, ms| Task {
+            date_updated: Some(ms),
+            ..task(id)
+        };
+        app.set_tasks(vec![updated("a", 100), updated("b", 200)]);
+        assert_eq!(app.sync_cursor(), Some(200));
+
+        let mut b = updated("b", 300);
+        b.status = "in review".to_string();
+        let diff = app.merge_tasks(vec![b, updated("c", 250)]);
+        assert_eq!(diff.summary().as_deref(), Some("1 new, 1 moved"));
+        let ids: Vec<&str> = app.tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert_eq!(app.tasks[1].status, "in review");
+        assert_eq!(app.sync_cursor(), Some(300));
+    }
+
+    #[test]
+    fn test_refresh_is_full_until_recently_reconciled() {
+        let mut app = test_app();
+        let now = Instant::now();
+        app.set_tasks(vec![Task {
+            date_updated: Some(100),
+            ..task("a")
+        }]);
+        assert_eq!(app.refresh_cursor(now), None);
+
+        app.mark_full_refresh(now);
+        assert_eq!(app.refresh_cursor(now), Some(100));
+        assert_eq!(app.refresh_cursor(now + FULL_REFRESH_INTERVAL), None);
+    }
+
+    #[test]
+    fn test_overdue_count() {
+        let mut app = test_app();
+        app.user_id = Some(1);
+        let now = Utc::now().timestamp_millis();
+        let hour_ms = 3_600_000;
+        let due = |id: &str, due: i64, assignee: u64| {
+            let mut t = task(id);
+            t.due_date = Some(due);
+            t.assignee_ids = vec![assignee];
+            t
+        };
+        let mut done = due("done", now - hour_ms, 1);
+        done.status = "done".to_string();
+        app.tasks = vec![
+            due("late", now - hour_ms, 1),
+            due("later", now + hour_ms, 1),
+            due("snoozed", now - hour_ms, 1),
+            due("theirs", now - hour_ms, 2),
+            done,
+            task("undated"),
+        ];
+        app.local_state
+            .snooze("snoozed", Utc::now() + Duration::days(1));
+
+        assert_eq!(app.overdue_count(), 1);
+    }
+
+    #[test]
+    fn test_take_newly_overdue() {
+        let mut app = test_app();
+        let now = Utc::now();
+        let due_in = |id: &str, hours: i64| Task {
+            due_date: Some((now + Duration::hours(hours)).timestamp_millis()),
+            ..task(id)
+        };
+        assert!(app.take_newly_overdue(now).is_empty());
+
+        // Already overdue at the first check: not news
+        app.tasks = vec![due_in("old", -1), due_in("soon", 1), due_in("later", 5)];
+        assert!(app.take_newly_overdue(now).is_empty());
+
+        let in_two_hours = now + Duration::hours(2);
+        let ids: Vec<String> = app
+            .take_newly_overdue(in_two_hours)
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ids, vec!["soon"]);
+        assert!(app.take_newly_overdue(in_two_hours).is_empty(), "only once");
+    }
+
+    #[test]
+    fn test_collapse_hides_subtree() {
+        let mut app = test_app();
+        let mut child = task("child");
+        child.parent_id = Some("parent".to_string());
+        let mut grandchild = task("grandchild");
+        grandchild.parent_id = Some("child".to_string());
+        app.tasks = vec![task("parent"), child, grandchild, task("zz")];
+
+        // Leaf tasks can't collapse
+        app.selected_index = 3;
+        app.toggle_collapse();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No subtasks to collapse")
+        );
+
+        // Collapsing an inner parent hides only its subtree
+        app.selected_index = 1;
+        app.toggle_collapse();
+        assert_eq!(ids(&app), vec!["parent", "child", "zz"]);
+        assert_eq!(app.collapsed_counts().get("child"), Some(&1));
+
+        // The outermost collapsed parent owns the whole hidden subtree
+        app.selected_index = 0;
+        app.toggle_collapse();
+        assert_eq!(ids(&app), vec!["parent", "zz"]);
+        assert_eq!(app.collapsed_counts().get("parent"), Some(&2));
+
+        app.toggle_collapse();
+        assert_eq!(ids(&app), vec!["parent", "child", "zz"]);
+    }
+
+    #[test]
+    fn test_sort_modes_keep_subtasks_under_parents() {
+        let mut app = test_app();
+        let mut zeta = task("1");
+        zeta.name = "Zeta".to_string();
+        zeta.due_date = Some(100);
+        let mut alpha = task("2");
+        alpha.name = "alpha".to_string();
+        alpha.priority = Some(1);
+        let mut child = task("3");
+        child.name = "Child".to_string();
+        child.parent_id = Some("1".to_string());
+        app.tasks = vec![zeta, alpha, child];
+
+        let order = |app: &App| -> Vec<String> {
+            app.current_tasks()
+                .into_iter()
+                .map(|dt| dt.task.id)
+                .collect()
+        };
+
+        assert_eq!(order(&app), vec!["2", "1", "3"]);
+        app.cycle_sort_mode();
+        assert_eq!(app.sort_mode, SortMode::DueDate);
+        assert_eq!(order(&app), vec!["1", "3", "2"]);
+        app.cycle_sort_mode();
+        assert_eq!(order(&app), vec!["2", "1", "3"]);
+    }
+
+    #[test]
+    fn test_hide_unassigned_ancestors() {
+        let mut app = test_app();
+        app.user_id = Some(1);
+        let mut parent = task("parent");
+        parent.assignee_ids = vec![2];
+        let mut child = task("child");
+        child.assignee_ids = vec![1];
+        child.parent_id = Some("parent".to_string());
+        app.tasks = vec![parent, child];
+
+        assert_eq!(ids(&app), vec!["parent", "child"]);
+
+        app.set_show_unassigned_ancestors(false);
+        assert_eq!(ids(&app), vec!["child"]);
+    }
+
+    #[test]
+    fn test_pin_to_top_keeps_subtasks_nested() {
+        let mut app = test_app();
+        let with_priority = |id: &str, priority: u8| Task {
+            priority: Some(priority),
+            ..task(id)
+        };
+        let child = |id: &str| Task {
+            parent_id: Some("parent".to_string()),
+            ..with_priority(id, 1)
+        };
+        app.tasks = vec![
+            with_priority("urgent", 1),
+            with_priority("low", 4),
+            with_priority("parent", 2),
+            child("c1"),
+            child("c2"),
+        ];
+        app.local_state.toggle_pin("low");
+        app.local_state.toggle_pin("c2");
+
+        assert_eq!(ids(&app), vec!["low", "urgent", "parent", "c2", "c1"]);
+
+        app.set_pin_to_top(false);
+        assert_eq!(ids(&app), vec!["urgent", "parent", "c1", "c2", "low"]);
+    }
+
+    #[test]
+    fn test_fetched_subtasks_show_and_survive_full_refresh() {
+        let mut app = test_app();
+        app.user_id = Some(7);
+        let parent = Task {
+            assignee_ids: vec![7],
+            date_updated: Some(1_000),
+            ..task("parent")
+        };
+        let child = |id: &str, parent: &str| Task {
+            parent_id: Some(parent.to_string()),
+            date_updated: Some(9_000),
+            ..task(id)
+        };
+        app.set_tasks(vec![parent.clone()]);
+        assert_eq!(ids(&app), vec!["parent"]);
+
+        assert_eq!(
+            press(&mut app, 'E'),
+            Some(Command::FetchSubtasks {
+                task_id: "parent".to_string()
+            })
+        );
+        app.add_subtasks(
+            "parent",
+            vec![child("grandchild", "c1"), child("c1", "parent")],
+        );
+        assert_eq!(ids(&app), vec!["parent", "c1", "grandchild"]);
+        assert!(app.tasks.iter().skip(1).all(|t| t.fetched_as_subtask));
+
+        // A full refresh doesn't return them, but they stay while the parent does,
+        // without moving the incremental sync cursor
+        app.set_tasks(vec![parent.clone()]);
+        assert_eq!(ids(&app), vec!["parent", "c1", "grandchild"]);
+        assert_eq!(app.sync_cursor(), Some(1_000));
+
+        app.set_tasks(vec![task("other")]);
+        assert_eq!(app.tasks.len(), 1);
+    }
+
+    #[test]
+    fn test_hide_done_subtasks() {
+        let mut app = test_app();
+        let parent = Task {
+            status: "in progress".to_string(),
+            assignee_ids: vec![9],
+            ..task("parent")
+        };
+        let subtask = |id: &str, status: &str| Task {
+            status: status.to_string(),
+            parent_id: Some("parent".to_string()),
+            assignee_ids: vec![9],
+            ..task(id)
+        };
+        app.tasks = vec![
+            parent,
+            subtask("open", "to do"),
+            subtask("done", "complete"),
+        ];
+        // A person view lists tasks from every group
+        app.person_view = Some(PersonView {
+            user_id: 9,
+            name: "Sam".to_string(),
+        });
+
+        assert_eq!(ids(&app), vec!["parent", "done", "open"]);
+
+        app.toggle_hide_done_subtasks();
+        assert_eq!(ids(&app), vec!["parent", "open"]);
+        assert_eq!(app.subtask_status_counts("parent").len(), 2);
+
+        // Other tabs only list a Done subtask under its parent once fetched
+        app.person_view = None;
+        app.toggle_hide_done_subtasks();
+        assert_eq!(ids(&app), vec!["parent", "open"]);
+        app.tasks[2].fetched_as_subtask = true;
+        assert_eq!(ids(&app), vec!["parent", "done", "open"]);
+        app.toggle_hide_done_subtasks();
+        assert_eq!(ids(&app), vec!["parent", "open"]);
+    }
+
+    #[test]
+    fn test_jump_to_custom_id() {
+        let mut app = test_app();
+        let mut done = task("done");
+        done.custom_id = Some("PROJ-7".to_string());
+        done.status = "complete".to_string();
+        app.tasks = vec![task("a"), task("b"), done];
+
+        app.start_jump_to_id();
+        for c in "proj-7".chars() {
+            app.handle_char(c);
+        }
+        app.confirm_jump_to_id();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(app.current_group, TaskGroup::Done);
+        assert_eq!(app.selected_task().unwrap().task.id, "done");
+
+        app.start_jump_to_id();
+        app.jump_input = "PROJ-999".to_string();
+        app.confirm_jump_to_id();
+        assert_eq!(app.status_message.as_deref(), Some("No task PROJ-999"));
+        assert_eq!(app.current_group, TaskGroup::Done);
+    }
+
+    #[test]
+    fn test_default_group_must_be_visible() {
+        let mut app = test_app();
+        app.set_visible_groups(vec![TaskGroup::MyAction, TaskGroup::Today]);
+        app.set_default_group(TaskGroup::Done);
+        assert_eq!(app.current_group, TaskGroup::MyAction);
+        app.set_default_group(TaskGroup::Today);
+        assert_eq!(app.current_group, TaskGroup::Today);
+    }
+
+    #[test]
+    fn test_group_default_sort() {
+        let mut app = test_app();
+        app.set_group_sorts(HashMap::from([(TaskGroup::Done, SortMode::Updated)]));
+        assert_eq!(app.sort_mode, SortMode::Priority);
+
+        app.switch_group(TaskGroup::Done);
+        assert_eq!(app.sort_mode, SortMode::Updated);
+        app.cycle_sort_mode();
+        app.switch_group(TaskGroup::Backlog);
+        assert_eq!(app.sort_mode, SortMode::Priority);
+        app.switch_group(TaskGroup::Done);
+        assert_eq!(app.sort_mode, SortMode::Updated);
+
+        let updated = |id: &str, ms| Task {
+            date_updated: ms,
+            ..task(id)
+        };
+        let (old, new, never) = (
+            updated("a", Some(1)),
+            updated("b", Some(2)),
+            updated("c", None),
+        );
+        assert!(SortMode::Updated.compare(&new, &old).is_lt());
+        assert!(SortMode::Updated.compare(&old, &never).is_lt());
+        assert_eq!(SortMode::from_name("Due Date"), Some(SortMode::DueDate));
+        assert_eq!(SortMode::from_name("recent"), Some(SortMode::Updated));
+        assert_eq!(SortMode::from_name("size"), None);
+    }
+
+    #[test]
+    fn test_tag_filter() {
+        let mut app = test_app();
+        let mut tagged = task("a");
+        tagged.tags = vec!["Backend".to_string()];
+        app.tasks = vec![tagged, task("b")];
+
+        app.start_tag_filter();
+        for c in "backend".chars() {
+            app.handle_char(c);
+        }
+        app.confirm_tag_filter();
+        assert_eq!(app.active_tag_filter.as_deref(), Some("backend"));
+        let ids: Vec<String> = app
+            .current_tasks()
+            .into_iter()
+            .map(|dt| dt.task.id)
+            .collect();
+        assert_eq!(ids, vec!["a"]);
+
+        app.clear_filters();
+        assert_eq!(app.current_tasks().len(), 2);
+    }
+
+    #[test]
+    fn test_list_picker_filter() {
+        let mut app = test_app();
+        let mut web = task("a");
+        web.list_name = "Website Redesign".to_string();
+        let mut api = task("b");
+        api.list_name = "API".to_string();
+        app.tasks = vec![web, api, task("c")];
+
+        assert_eq!(app.list_names(), vec!["API", "List", "Website Redesign"]);
+
+        // Lists differing only in case are one entry, and filter together
+        let mut api_lower = task("d");
+        api_lower.list_name = "api".to_string();
+        app.tasks.insert(0, api_lower);
+        assert_eq!(app.list_names(), vec!["api", "List", "Website Redesign"]);
+        app.active_list_filter = Some("api".to_string());
+        assert_eq!(app.current_tasks().len(), 2);
+        app.tasks.remove(0);
+        app.clear_filters();
+
+        app.start_list_picker();
+        for c in "wsr".chars() {
+            app.handle_char(c);
+        }
+        assert_eq!(app.list_picker_matches(), vec!["Website Redesign"]);
+        app.confirm_list_picker();
+
+        let ids: Vec<String> = app
+            .current_tasks()
+            .into_iter()
+            .map(|dt| dt.task.id)
+            .collect();
+        assert_eq!(ids, vec!["a"]);
+        app.clear_filters();
+        assert_eq!(app.current_tasks().len(), 3);
+    }
+
+    #[test]
+    fn test_search_results_are_cached_per_query() {
+        let mut app = test_app();
+        let mut deploy = task("a");
+        deploy.name = "Deploy pipeline".to_string();
+        app.tasks = vec![deploy, task("b")];
+
+        app.start_search();
+        assert!(app.search_all_tasks().is_empty());
+        for c in "dpl".chars() {
+            app.handle_char(c);
+        }
+        assert_eq!(app.search_all_tasks().len(), 1);
+        assert_eq!(app.selected_search_result().unwrap().task.id, "a");
+
+        app.handle_backspace();
+        app.handle_backspace();
+        app.handle_backspace();
+        assert!(app.search_all_tasks().is_empty());
+    }
+
+    #[test]
+    fn test_current_tasks_order_with_hierarchy() {
+        let mut app = test_app();
+        let mut parent = task("p");
+        parent.priority = Some(2);
+        let mut child = task("c1");
+        child.parent_id = Some("p".to_string());
+        let mut grandchild = task("c0");
+        grandchild.parent_id = Some("c1".to_string());
+        let mut urgent = task("z");
+        urgent.priority = Some(1);
+        let mut done = task("d");
+        done.status = "done".to_string();
+        app.tasks = vec![grandchild, done, parent, task("a"), child, urgent];
+
+        let ids: Vec<String> = app
+            .current_tasks()
+            .into_iter()
+            .map(|dt| dt.task.id)
+            .collect();
+        assert_eq!(ids, vec!["z", "p", "c1", "c0", "a"]);
+
+        let ref_ids: Vec<&str> = app
+            .current_task_refs()
+            .into_iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        assert_eq!(ref_ids, ids);
+    }
+
+    #[test]
+    fn test_today_group() {
+        let mut app = test_app();
+        let now = Utc::now().timestamp_millis();
+        let day_ms = 24 * 60 * 60 * 1000;
+
+        let mut overdue = task("overdue");
+        overdue.due_date = Some(now - 3 * day_ms);
+        overdue.status = "blocked".to_string();
+        let mut due_now = task("now");
+        due_now.due_date = Some(now);
+        let mut later = task("later");
+        later.due_date = Some(now + 3 * day_ms);
+        let mut done = task("done");
+        done.due_date = Some(now);
+        done.status = "done".to_string();
+        let mut person = task("person");
+        person.due_date = Some(now);
+        person.custom_item_id = Some(1020);
+        let mut snoozed = task("snoozed");
+        snoozed.due_date = Some(now);
+        app.tasks = vec![overdue, due_now, later, done, person, snoozed, task("none")];
+        app.local_state
+            .snooze("snoozed", Utc::now() + Duration::days(1));
+
+        app.switch_group(TaskGroup::Today);
+        let mut ids: Vec<String> = app
+            .current_tasks()
+            .into_iter()
+            .map(|dt| dt.task.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["now", "overdue"]);
+        assert_eq!(app.group_counts()[6], (TaskGroup::Today, 2));
+
+        // Status-based tabs are unaffected
+        app.switch_group(TaskGroup::Person);
+        assert_eq!(app.current_tasks().len(), 1);
+    }
+
+    #[test]
+    fn test_subtask_status_counts() {
+        let mut app = test_app();
+        let child = |id: &str, status: &str| Task {
+            status: status.to_string(),
+            parent_id: Some("p".to_string()),
+            ..task(id)
+        };
+        app.tasks = vec![
+            task("p"),
+            child("a", "done"),
+            child("b", "to do"),
+            child("c", "to do"),
+            child("d", "in progress"),
+        ];
+        assert_eq!(
+            app.subtask_status_counts("p"),
+            vec![("to do".to_string(), 2), ("in progress".to_string(), 1)]
+        );
+        assert!(app.subtask_status_counts("a").is_empty());
+
+        // Only the done child is in view under Done
+        app.done_show_all = true;
+        app.current_group = TaskGroup::Done;
+        assert_eq!(
+            app.subtask_status_counts("p"),
+            vec![("done".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_set_user_id_rejects_non_numeric() {
+        let mut app = test_app();
+        assert!(app.set_user_id("12345").is_ok());
+        assert_eq!(app.user_id, Some(12345));
+
+        let err = app.set_user_id("jdoe").unwrap_err().to_string();
+        assert!(err.contains("not numeric"), "{}", err);
+        assert_eq!(app.user_id, None);
+    }
+
+    #[test]
+    fn test_click_accounts_for_list_offset() {
+        let mut app = test_app();
+        app.tasks = (0..10).map(|i| task(&i.to_string())).collect();
+        app.task_list_area = Rect::new(0, 0, 40, 6);
+        app.task_list_offset = 5;
+
+        // Row 1 is the first row inside the border
+        app.click(3, 2);
+        assert_eq!(app.selected_index, 6);
+
+        // Task 5 wraps onto two rows, so row 2 is still task 5
+        app.task_list_heights = vec![1, 1, 1, 1, 1, 2, 1];
+        app.click(3, 2);
+        assert_eq!(app.selected_index, 5);
+        app.click(3, 3);
+        assert_eq!(app.selected_index, 6);
+    }
+
+    #[test]
+    fn test_edit_note() {
+        let mut app = test_app();
+        app.tasks = vec![task("a")];
+
+        app.start_note();
+        assert_eq!(app.input_mode, InputMode::Note);
+        for c in "call back\nafter lunch ".chars() {
+            app.handle_char(c);
+        }
+        app.confirm_note();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.local_state.get_overlay("a").note.as_deref(),
+            Some("call back\nafter lunch")
+        );
+
+        // Reopening prefills the note; clearing it removes the note
+        app.start_note();
+        assert_eq!(app.note_input, "call back\nafter lunch");
+        app.note_input.clear();
+        app.confirm_note();
+        assert!(app.local_state.get_overlay("a").note.is_none());
+
+        app.undo_last();
+        assert!(app.local_state.get_overlay("a").note.is_some());
+    }
+
+    #[test]
+    fn test_waiting_reason() {
+        let mut app = test_app();
+        app.tasks = vec![task("a")];
+
+        app.start_waiting_reason();
+        assert_eq!(app.input_mode, InputMode::WaitingReason);
+        for c in " Sam's review ".chars() {
+            app.handle_char(c);
+        }
+        app.confirm_waiting_reason();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.local_state.get_overlay("a").waiting_reason.as_deref(),
+            Some("Sam's review")
+        );
+
+        app.start_waiting_reason();
+        assert_eq!(app.waiting_input, "Sam's review");
+        app.waiting_input.clear();
+        app.confirm_waiting_reason();
+        assert!(app.local_state.get_overlay("a").waiting_reason.is_none());
+
+        app.undo_last();
+        assert!(app.local_state.get_overlay("a").waiting_reason.is_some());
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let mut parent = task("p");
+        parent.custom_id = Some("PROJ-1".to_string());
+        parent.url = "https://app.clickup.com/t/p".to_string();
+        let mut child = task("c");
+        child.name = "Fix [auth] bug".to_string();
+        child.status = "done".to_string();
+        child.parent_id = Some("p".to_string());
+        child.url = "https://app.clickup.com/t/c".to_string();
+        let tasks = vec![
+            DisplayTask::new(parent, TaskOverlay::default()),
+            DisplayTask::new(child, TaskOverlay::default()),
+        ];
+
+        assert_eq!(
+            to_markdown("My Action (tag: api)", &tasks, &StatusGroups::new()),
+            "## My Action (tag: api)\n\n\
+             - [ ] **to do** PROJ-1 [Task p](https://app.clickup.com/t/p)\n  \
+             - [x] **done** [Fix \\[auth\\] bug](https://app.clickup.com/t/c)\n"
+        );
+
+        // Statuses mapped to Done in config are checked too
+        let status_groups = StatusGroups::from([("to do".to_string(), TaskGroup::Done)]);
+        assert!(to_markdown("Done", &tasks, &status_groups).contains("- [x] **to do**"));
+    }
+
+    #[test]
+    fn test_to_markdown_empty() {
+        assert_eq!(
+            to_markdown("Done", &[], &StatusGroups::new()),
+            "## Done\n\n"
+        );
+    }
+
+    #[test]
+    fn test_match_positions() {
+        let query: Vec<char> = "fb".chars().collect();
+        assert_eq!(fuzzy_match("Fix Bug", &query).unwrap().1, vec![0, 4]);
+        assert!(fuzzy_match("Fix", &query).is_none());
+
+        // Substring matches win over scattered fuzzy positions
+        assert_eq!(match_positions("a xab", "AB"), vec![3, 4]);
+        assert_eq!(match_positions("Fix Bug", "fb"), vec![0, 4]);
+        assert!(match_positions("Fix Bug", "").is_empty());
+        assert!(match_positions("Fix Bug", "zz").is_empty());
+
+        // The list filter only highlights a substring
+        assert_eq!(substring_positions("a xab", "AB"), vec![3, 4]);
+        assert!(substring_positions("Fix Bug", "fb").is_empty());
+    }
+
+    #[test]
+    fn test_visible_groups_order_tabs() {
+        let mut app = test_app();
+        app.set_visible_groups(vec![TaskGroup::Today, TaskGroup::Waiting, TaskGroup::Done]);
+        // MyAction is hidden, so the first visible tab is selected
+        assert_eq!(app.current_group, TaskGroup::Today);
+
+        app.next_tab();
+        assert_eq!(app.current_group, TaskGroup::Waiting);
+        app.prev_tab();
+        app.prev_tab();
+        assert_eq!(app.current_group, TaskGroup::Done);
+
+        app.switch_to_tab(2);
+        assert_eq!(app.current_group, TaskGroup::Waiting);
+        app.switch_to_tab(4);
+        assert_eq!(app.current_group, TaskGroup::Waiting);
+
+        app.switch_to_tab_letter('d');
+        assert_eq!(app.current_group, TaskGroup::Done);
+        app.switch_to_tab_letter('b');
+        assert_eq!(app.current_group, TaskGroup::Done);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No tab starting with 'b'")
+        );
+        app.switch_to_tab_letter('t');
+        assert_eq!(app.current_group, TaskGroup::Today);
+
+        let groups: Vec<TaskGroup> = app.group_counts().into_iter().map(|(g, _)| g).collect();
+        assert_eq!(groups, app.visible_groups);
+
+        // Repeating a shared letter cycles through its tabs
+        app.set_visible_groups(vec![
+            TaskGroup::Waiting,
+            TaskGroup::Done,
+            TaskGroup::Watching,
+        ]);
+        app.switch_to_tab_letter('d');
+        app.switch_to_tab_letter('w');
+        assert_eq!(app.current_group, TaskGroup::Waiting);
+        app.switch_to_tab_letter('w');
+        assert_eq!(app.current_group, TaskGroup::Watching);
+        app.switch_to_tab_letter('w');
+        assert_eq!(app.current_group, TaskGroup::Waiting);
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(0), "0:00");
+        assert_eq!(format_elapsed(83), "1:23");
+        assert_eq!(format_elapsed(3600 + 62), "1:01:02");
+        assert_eq!(format_elapsed(-5), "0:00");
+    }
+
+    #[test]
+    fn test_person_view_shows_linked_users_tasks() {
+        let mut app = test_app();
+        app.user_id = Some(1);
+        let mut person = task("person");
+        person.custom_item_id = Some(1020);
+        person.person_id = Some(7);
+        person.assignee_ids = vec![1];
+        let mut theirs = task("theirs");
+        theirs.assignee_ids = vec![1, 7];
+        let mut theirs_done = task("theirs_done");
+        theirs_done.assignee_ids = vec![7];
+        theirs_done.status = "done".to_string();
+        let mut mine = task("mine");
+        mine.assignee_ids = vec![1];
+        app.tasks = vec![person, theirs, theirs_done, mine];
+
+        // Only Person tasks drill in
+        app.toggle_person_view();
+        assert_eq!(app.status_message.as_deref(), Some("Not a Person task"));
+        assert!(app.person_view.is_none());
+
+        app.switch_group(TaskGroup::Person);
+        app.toggle_person_view();
+        let mut ids: Vec<String> = app
+            .current_tasks()
+            .into_iter()
+            .map(|dt| dt.task.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["theirs", "theirs_done"]);
+
+        // Esc backs out to the group
+        app.clear_filters();
+        assert!(app.person_view.is_none());
+        assert_eq!(app.current_tasks()[0].task.id, "person");
+    }
+
+    #[test]
+    fn test_frecency_bonus_decays() {
+        let now = Utc::now();
+        let opened = |count, days_ago| TaskOverlay {
+            open_count: count,
+            last_opened: Some(now - Duration::days(days_ago)),
+            ..TaskOverlay::default()
+        };
+        assert_eq!(frecency_bonus(&TaskOverlay::default(), now), 0);
+        assert_eq!(frecency_bonus(&opened(3, 0), now), 24);
+        // Halves each week; capped opens; fades to nothing
+        assert_eq!(frecency_bonus(&opened(3, 7), now), 12);
+        assert_eq!(frecency_bonus(&opened(50, 0), now), 80);
+        assert_eq!(frecency_bonus(&opened(10, 120), now), 0);
+    }
+
+    #[test]
+    fn test_search_matches_custom_id() {
+        let mut app = test_app();
+        let mut ticket = task("abc");
+        ticket.custom_id = Some("PROJ-123".to_string());
+        app.tasks = vec![task("other"), ticket];
+
+        app.search_query = "proj123".to_string();
+        app.refresh_search_results();
+        let ids: Vec<&str> = app
+            .search_all_tasks()
+            .iter()
+            .map(|dt| dt.task.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["abc"]);
+
+        // The in-group filter matches it too
+        app.search_query = "proj-12".to_string();
+        assert_eq!(app.current_tasks()[0].task.id, "abc");
+        assert_eq!(app.current_tasks().len(), 1);
+    }
+
+    #[test]
+    fn test_filtering_clamps_selection() {
+        let mut app = test_app();
+        let mut only = task("c");
+        only.name = "Unique".to_string();
+        app.tasks = vec![task("a"), task("b"), only];
+        app.selected_index = 2;
+
+        app.start_search();
+        app.handle_char('k');
+        assert_eq!(app.current_tasks().len(), 2);
+        assert_eq!(app.selected_index, 1);
+        assert!(app.selected_task().is_some());
+    }
+
+    #[test]
+    fn test_search_prefers_name_over_description() {
+        let mut app = test_app();
+        let mut described = task("described");
+        described.description = Some("deploy".to_string());
+        let mut named = task("named");
+        named.name = "deploy".to_string();
+        app.tasks = vec![described, named];
+
+        app.search_query = "deploy".to_string();
+        app.refresh_search_results();
+        let ids: Vec<&str> = app
+            .search_all_tasks()
+            .iter()
+            .map(|dt| dt.task.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["named", "described"]);
+    }
+
+    #[test]
+    fn test_search_spans_name_and_tag() {
+        let mut app = test_app();
+        let mut tagged = task("tagged");
+        tagged.name = "Fix login".to_string();
+        tagged.tags = vec!["backend".to_string()];
+        app.tasks = vec![task("other"), tagged];
+
+        app.search_query = "login backend".to_string();
+        app.refresh_search_results();
+        assert_eq!(app.search_all_tasks().len(), 1);
+        assert_eq!(app.search_all_tasks()[0].task.id, "tagged");
+    }
+
+    #[test]
+    fn test_search_ranks_frequently_opened_tasks_higher() {
+        let mut app = test_app();
+        let mut exact = task("exact");
+        exact.name = "deploy".to_string();
+        let mut favorite = task("favorite");
+        favorite.name = "Redeploy script".to_string();
+        app.tasks = vec![exact, favorite];
+
+        app.search_query = "deploy".to_string();
+        app.refresh_search_results();
+        assert_eq!(app.search_all_tasks()[0].task.id, "exact");
+
+        // Recent opens outweigh a slightly better string match...
+        for _ in 0..5 {
+            app.local_state.record_open("favorite", Utc::now());
+        }
+        app.refresh_search_results();
+        assert_eq!(app.search_all_tasks()[0].task.id, "favorite");
+
+        // ...but not once they're long past
+        app.local_state
+            .record_open("favorite", Utc::now() - Duration::days(90));
+        app.refresh_search_results();
+        assert_eq!(app.search_all_tasks()[0].task.id, "exact");
+    }
+
+    #[test]
+    fn test_status_expires_after_timeout() {
+        let mut app = test_app();
+        app.set_status("Task pinned");
+        let set_at = app.status_set_at.unwrap();
+
+        app.expire_status(set_at + std::time::Duration::from_millis(2900));
+        assert_eq!(app.status_message.as_deref(), Some("Task pinned"));
+        app.expire_status(set_at + std::time::Duration::from_secs(3));
+        assert!(app.status_message.is_none());
+
+        // Without a timeout, messages stay until replaced
+        app.set_status_timeout(None);
+        app.set_status("Loaded 3 tasks");
+        app.expire_status(set_at + std::time::Duration::from_secs(60));
+        assert_eq!(app.status_message.as_deref(), Some("Loaded 3 tasks"));
+    }
+
+    #[test]
+    fn test_loading_spinner() {
+        let mut app = test_app();
+        assert_eq!(app.spinner_frame(Instant::now()), None);
+
+        assert!(app.start_loading());
+        assert!(!app.start_loading(), "only one fetch at a time");
+        let since = app.loading_since.unwrap();
+        let at = |ms| since + std::time::Duration::from_millis(ms);
+        assert_eq!(app.spinner_frame(at(0)), Some('⠋'));
+        assert_eq!(app.spinner_frame(at(250)), Some('⠹'));
+        assert_eq!(app.spinner_frame(at(1000)), Some('⠋'));
+
+        app.finish_loading();
+        assert_eq!(app.spinner_frame(at(1000)), None);
+    }
+
+    #[test]
+    fn test_watching_group() {
+        let mut app = test_app();
+        app.user_id = Some(1);
+        let mut mine = task("mine");
+        mine.assignee_ids = vec![1];
+        mine.watcher_ids = vec![1];
+        let mut watched = task("watched");
+        watched.assignee_ids = vec![2];
+        watched.watcher_ids = vec![1];
+        let mut watched_done = task("watched_done");
+        watched_done.watcher_ids = vec![1];
+        watched_done.status = "done".to_string();
+        app.tasks = vec![mine, watched, watched_done];
+
+        // Assigned tasks stay in their status group even when watched
+        assert_eq!(ids(&app), vec!["mine"]);
+
+        app.switch_group(TaskGroup::Watching);
+        assert_eq!(ids(&app), vec!["watched"]);
+        let counts = app.group_counts();
+        assert_eq!(counts[7], (TaskGroup::Watching, 1));
+        // Watched-only tasks don't inflate other tabs
+        assert_eq!(counts[3], (TaskGroup::Done, 0));
+
+        // Snoozing moves a watched task to Snoozed
+        app.local_state
+            .snooze("watched", Utc::now() + Duration::days(1));
+        assert!(ids(&app).is_empty());
+        app.switch_group(TaskGroup::Snoozed);
+        assert_eq!(ids(&app), vec!["watched"]);
+    }
+
+    #[test]
+    fn test_format_age() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap();
+        let ago = |secs: i64| format_age((now - Duration::seconds(secs)).timestamp_millis(), now);
+        assert_eq!(ago(30), "just now");
+        assert_eq!(ago(5 * 60), "5m ago");
+        assert_eq!(ago(3 * 3600), "3h ago");
+        assert_eq!(ago(2 * 86_400), "2d ago");
+        assert_eq!(ago(65 * 86_400), "2mo ago");
+        assert_eq!(ago(800 * 86_400), "2y ago");
+        // Clock skew never reads as the future
+        assert_eq!(ago(-90), "just now");
+    }
+
+    #[test]
+    fn test_format_wake() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap();
+        let wake = |secs: i64| format_wake(now + Duration::seconds(secs), now);
+        assert_eq!(wake(30).as_deref(), Some("wakes in <1m"));
+        assert_eq!(wake(5 * 60).as_deref(), Some("wakes in 5m"));
+        assert_eq!(wake(3 * 86_400 + 60).as_deref(), Some("wakes in 3d"));
+        assert_eq!(wake(0), None);
+        assert_eq!(wake(-60), None);
+    }
+
+    #[test]
+    fn test_timer_state() {
+        let mut app = test_app();
+        assert!(app.timer_elapsed().is_none());
+
+        app.begin_timer("a", "Task a");
+        assert_eq!(app.timer.as_ref().unwrap().task_id, "a");
+        assert_eq!(app.timer_elapsed().as_deref(), Some("0:00"));
+
+        app.finish_timer();
+        assert!(app.timer.is_none());
+        assert_eq!(app.status_message.as_deref(), Some("Logged 0:00 on Task a"));
+    }
+
+    #[test]
+    fn test_comments_due_debounces_selection() {
+        let mut app = test_app();
+        app.tasks = vec![task("a"), task("b")];
+        let start = Instant::now();
+        let later = |ms| start + std::time::Duration::from_millis(ms);
+
+        // First sighting starts the debounce; it fires once the selection settles
+        assert_eq!(app.comments_due(start), None);
+        assert_eq!(app.comments_due(later(100)), None);
+        assert_eq!(app.comments_due(later(300)).as_deref(), Some("a"));
+
+        // Moving on restarts the debounce for the new task, which waits for the
+        // in-flight fetch to land
+        app.select_next();
+        assert_eq!(app.comments_due(later(400)), None);
+        assert_eq!(app.comments_due(later(800)), None);
+        app.set_comments("a", Some(Vec::new()));
+        assert_eq!(app.comments_due(later(800)).as_deref(), Some("b"));
+
+        // Cached tasks (including failures) are never refetched
+        app.set_comments("b", None);
+        assert_eq!(app.comments_due(later(2000)), None);
+    }
+
+    #[test]
+    fn test_submit_comment_is_optimistic() {
+        let mut app = test_app();
+        app.tasks = vec![task("a")];
+        let existing = Comment {
+            author: "Sam".to_string(),
+            text: "first".to_string(),
+        };
+        app.set_comments("a", Some(vec![existing.clone()]));
+
+        app.start_comment();
+        assert_eq!(app.input_mode, InputMode::Comment);
+        for c in "  on it ".chars() {
+            app.handle_char(c);
+        }
+        let (task_id, text) = app.submit_comment().unwrap();
+        assert_eq!((task_id.as_str(), text.as_str()), ("a", "on it"));
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.comment_input.is_empty());
+
+        // Newest first, attributed to the (unknown) current user
+        let cached = |app: &App| app.comments["a"].clone().unwrap();
+        assert_eq!(cached(&app)[0].text, "on it");
+        assert_eq!(cached(&app)[0].author, "You");
+
+        app.comment_failed("a", "on it");
+        assert_eq!(cached(&app), vec![existing]);
+
+        // Blank comments aren't submitted
+        app.start_comment();
+        app.handle_char(' ');
+        assert!(app.submit_comment().is_none());
+    }
+
+    #[test]
+    fn test_undo_restores_prior_overlay() {
+        let mut app = test_app();
+        app.tasks = vec![task("a")];
+
+        app.toggle_pin();
+        app.snooze_preset(SnoozePreset::Week);
+        assert!(app.local_state.is_pinned("a"));
+        assert!(app.local_state.get_overlay("a").snoozed_until.is_some());
+
+        app.undo_last();
+        assert!(app.local_state.is_pinned("a"));
+        assert!(app.local_state.get_overlay("a").snoozed_until.is_none());
+
+        app.undo_last();
+        assert!(!app.local_state.overlays.contains_key("a"));
+        assert!(app.undo.is_empty());
+    }
+
+    #[test]
+    fn test_action_waits_for_confirmation() {
+        let mut app = test_app();
+        app.tasks = vec![task("a")];
+
+        app.start_priority_picker();
+        assert_eq!(app.priority_picker_digit('2'), None);
+        assert_eq!(app.input_mode, InputMode::Confirm);
+
+        app.cancel_action();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.confirmation.is_none());
+        assert_eq!(app.confirm_action(), None);
+
+        app.start_priority_picker();
+        app.priority_picker_digit('2');
+        assert_eq!(
+            app.confirm_action(),
+            Some(Command::SetPriority {
+                task_id: "a".to_string(),
+                priority: Some(2),
+            })
+        );
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn test_bulk_pin_and_snooze_marked_tasks() {
+        let mut app = test_app();
+        app.tasks = vec![task("a"), task("b"), task("c")];
+
+        app.toggle_select();
+        app.select_next();
+        app.toggle_select();
+        assert_eq!(app.selected_ids.len(), 2);
+        app.toggle_pin();
+        assert!(
+            app.selected_ids.is_empty(),
+            "bulk actions end the selection"
+        );
+        assert!(app.local_state.is_pinned("a") && app.local_state.is_pinned("b"));
+        assert!(!app.local_state.is_pinned("c"));
+        assert_eq!(app.status_message.as_deref(), Some("Pinned 2 tasks"));
+
+        // Marks don't follow the user to another tab
+        app.toggle_select();
+        app.switch_group(TaskGroup::Backlog);
+        assert!(app.selected_ids.is_empty());
+        app.switch_group(TaskGroup::MyAction);
+
+        app.selected_ids = HashSet::from(["a".to_string(), "c".to_string()]);
+        app.snooze_preset(SnoozePreset::Week);
+        app.confirm_action();
+        assert!(app.selected_ids.is_empty());
+        assert_eq!(app.group_counts()[4], (TaskGroup::Snoozed, 2));
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap()
+            .starts_with("Snoozed 2 tasks until"));
+
+        // Each bulk action undoes as one step
+        app.undo_last();
+        assert_eq!(app.group_counts()[4], (TaskGroup::Snoozed, 0));
+        app.undo_last();
+        assert!(!app.local_state.is_pinned("a") && !app.local_state.is_pinned("b"));
+
+        app.selected_ids = HashSet::from(["c".to_string()]);
+        app.toggle_pin();
+        assert_eq!(app.status_message.as_deref(), Some("Pinned 1 task"));
+    }
+
+    #[test]
+    fn test_bulk_snooze_waits_for_confirmation() {
+        let mut app = test_app();
+        app.tasks = vec![task("a"), task("b"), task("c")];
+        app.selected_ids = HashSet::from(["a".to_string(), "b".to_string()]);
+
+        app.snooze_preset(SnoozePreset::Week);
+        assert_eq!(app.input_mode, InputMode::Confirm);
+        assert!(app.local_state.overlays.is_empty());
+
+        app.cancel_action();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.local_state.overlays.is_empty());
+        assert_eq!(app.selected_ids.len(), 2, "cancelling keeps the marks");
+
+        app.selected_ids.remove("b");
+        app.snooze_preset(SnoozePreset::Week);
+        assert!(app
+            .confirmation
+            .as_ref()
+            .unwrap()
+            .prompt
+            .starts_with("Snooze 1 task until"));
+        app.cancel_action();
+        app.selected_ids.insert("b".to_string());
+
+        app.snooze_preset(SnoozePreset::Week);
+        app.confirm_action();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.selected_ids.is_empty());
+        assert_eq!(app.group_counts()[4], (TaskGroup::Snoozed, 2));
+
+        // One undo restores the whole batch
+        app.undo_last();
+        assert_eq!(app.current_tasks().len(), 3);
+        assert!(app.undo.is_empty());
+    }
+
+    #[test]
+    fn test_priority_picker_sets_priority() {
+        let mut app = test_app();
+        app.set_confirm_actions(false);
+        app.tasks = vec![task("a"), task("b")];
+        app.selected_index = app
+            .current_task_refs()
+            .iter()
+            .position(|t| t.id == "b")
+            .unwrap();
+
+        app.start_priority_picker();
+        assert_eq!(app.input_mode, InputMode::PriorityPicker);
+        assert_eq!(
+            app.priority_picker_digit('1'),
+            Some(Command::SetPriority {
+                task_id: "b".to_string(),
+                priority: Some(1),
+            })
+        );
+        assert_eq!(app.input_mode, InputMode::Normal);
+
+        app.apply_priority("b", Some(1));
+        assert_eq!(app.tasks[1].priority, Some(1));
+        assert_eq!(app.selected_task().unwrap().task.id, "b");
+    }
+
+    #[test]
+    fn test_assign_and_unassign_self() {
+        let mut app = test_app();
+        app.set_confirm_actions(false);
+        app.user_id = Some(1);
+        let mut t = task("a");
+        t.assignee_ids = vec![1, 2];
+        app.tasks = vec![t];
+
+        assert_eq!(app.set_assigned_to_me(true), None);
+
+        assert_eq!(
+            app.set_assigned_to_me(false),
+            Some(Command::SetAssigned {
+                task_id: "a".to_string(),
+                assigned: false,
+            })
+        );
+        app.apply_assignment("a", false);
+        assert_eq!(app.tasks[0].assignee_ids, vec![2]);
+
+        app.apply_assignment("a", true);
+        assert_eq!(app.tasks[0].assignee_ids, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_backlog_heat_by_days_since_update() {
+        let mut app = test_app();
+        let day = 24 * 60 * 60 * 1000;
+        let now = Utc::now();
+        let ms = now.timestamp_millis();
+        let mut t = task("a");
+        let heat = |app: &App, t: &Task| app.backlog_heat(t, now);
+
+        t.date_updated = Some(ms - 2 * day);
+        assert_eq!(heat(&app, &t), None, "only on the Backlog tab");
+        app.switch_group(TaskGroup::Backlog);
+        assert_eq!(heat(&app, &t), Some(TaskAge::Fresh));
+        t.date_updated = Some(ms - 14 * day);
+        assert_eq!(heat(&app, &t), Some(TaskAge::Aging));
+        t.date_updated = Some(ms - 45 * day);
+        assert_eq!(heat(&app, &t), Some(TaskAge::Stale));
+
+        // Falls back to the creation date, and needs one of them
+        t.date_updated = None;
+        t.date_created = Some(ms - day);
+        assert_eq!(heat(&app, &t), Some(TaskAge::Fresh));
+        t.date_created = None;
+        assert_eq!(heat(&app, &t), None);
+
+        app.set_backlog_age(BacklogConfig {
+            aging_days: 1,
+            stale_days: 3,
+        });
+        t.date_updated = Some(ms - 2 * day);
+        assert_eq!(heat(&app, &t), Some(TaskAge::Aging));
+    }
+
+    #[test]
+    fn test_done_limited_to_recent_tasks() {
+        let mut app = test_app();
+        let day = 24 * 60 * 60 * 1000;
+        let now = Utc::now().timestamp_millis();
+        let mut recent = task("recent");
+        recent.status = "complete".to_string();
+        recent.date_updated = Some(now - day);
+        let mut old = task("old");
+        old.status = "complete".to_string();
+        old.date_updated = Some(now - 30 * day);
+        app.tasks = vec![recent, old];
+        app.switch_group(TaskGroup::Done);
+
+        assert_eq!(ids(&app), vec!["recent"]);
+
+        app.toggle_done_show_all();
+        assert_eq!(ids(&app).len(), 2);
+
+        app.toggle_done_show_all();
+        app.set_done_recent_days(None);
+        assert_eq!(ids(&app).len(), 2);
+    }
+
+    #[test]
+    fn test_restore_position() {
+        let mut app = test_app();
+        app.tasks = vec![task("a"), task("b")];
+        app.selected_index = 1;
+        app.remember_position();
+        assert_eq!(app.local_state.last_group, Some(TaskGroup::MyAction));
+        let selected = app.local_state.last_selected_task_id.clone().unwrap();
+
+        let mut restored = test_app();
+        restored.local_state = app.local_state.clone();
+        restored.tasks = app.tasks.clone();
+        restored.restore_position();
+        assert_eq!(restored.selected_task().unwrap().task.id, selected);
+
+        // A task that's gone falls back to the top of the remembered tab
+        restored.local_state.last_group = Some(TaskGroup::Done);
+        restored.local_state.last_selected_task_id = Some("gone".to_string());
+        restored.restore_position();
+        assert_eq!(restored.current_group, TaskGroup::Done);
+        assert_eq!(restored.selected_index, 0);
+    }
+
+    #[test]
+    fn test_parse_snooze_days() {
+        let until = parse_snooze_input("3").unwrap();
+        let expected = Utc::now() + Duration::days(3);
+        assert!((expected - until).num_seconds().abs() < 5);
+        assert!(parse_snooze_input("0").is_none());
+    }
+
+    #[test]
+    fn test_parse_snooze_absolute_date() {
+        let future = Local::now().date_naive() + Duration::days(30);
+        let input = future.format("%Y-%m-%d").to_string();
+        assert_eq!(local_date(parse_snooze_input(&input).unwrap()), future);
+        assert!(parse_snooze_input("2000-01-01").is_none());
+    }
+
+    #[test]
+    fn test_parse_snooze_keywords() {
+        let today = Local::now().date_naive();
+
+        let tomorrow = parse_snooze_input("Tomorrow").unwrap();
+        assert_eq!(local_date(tomorrow), today + Duration::days(1));
+
+        let next_week = local_date(parse_snooze_input("next week").unwrap());
+        assert_eq!(next_week.weekday(), Weekday::Mon);
+        assert!(next_week > today && next_week <= today + Duration::days(7));
+    }
+
+    #[test]
+    fn test_parse_snooze_weekday() {
+        let today = Local::now().date_naive();
+        let monday = local_date(parse_snooze_input("monday").unwrap());
+        assert_eq!(monday.weekday(), Weekday::Mon);
+        assert!(monday > today && monday <= today + Duration::days(7));
+        assert_eq!(
+            local_date(parse_snooze_input("fri").unwrap()).weekday(),
+            Weekday::Fri
+        );
+    }
+
+    #[test]
+    fn test_parse_snooze_invalid() {
+        assert!(parse_snooze_input("").is_none());
+        assert!(parse_snooze_input("someday").is_none());
+        assert!(parse_snooze_input("2024-13-45").is_none());
     }
 }
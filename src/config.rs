@@ -1,97 +1,622 @@
 //! Configuration handling for clickup-tui
 //!
-//! Stores API token and user settings in XDG-compliant locations.
+//! Stores API token and user settings in XDG-compliant locations
+//! (`$XDG_CONFIG_HOME`, `%APPDATA%` on Windows, or `~/.config`).
 
+use crate::app::SortMode;
+use crate::keymap::{KeySpec, Keymap};
+use crate::models::{StatusGroups, TaskGroup};
+use crate::theme::{Theme, ThemeConfig};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+/// Environment variable that overrides `api_token`
+pub const API_TOKEN_ENV: &str = "CLICKUP_API_TOKEN";
+
+/// Environment variable that overrides `user_id`
+pub const USER_ID_ENV: &str = "CLICKUP_USER_ID";
+
+/// Why the config couldn't be loaded; each kind exits with its own code
+#[derive(Debug)]
+pub enum ConfigError {
+    /// There was no config file (a default one was written to fill in)
+    NotFound(PathBuf),
+    /// The file couldn't be read or parsed, or a setting in it is invalid
+    Invalid(anyhow::Error),
+    /// The API token or user ID isn't set in the file or the environment
+    MissingField(String),
+}
+
+impl ConfigError {
+    /// Process exit code: 2 no config file, 3 invalid config, 4 missing field
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ConfigError::NotFound(_) => 2,
+            ConfigError::Invalid(_) => 3,
+            ConfigError::MissingField(_) => 4,
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::NotFound(path) => write!(
+                f,
+                "Config file created at {}. Please edit it to add your ClickUp API token and user ID.",
+                path.display()
+            ),
+            // `{:#}` keeps the context chain (e.g. the TOML error under "Failed to parse")
+            ConfigError::Invalid(e) => write!(f, "{:#}", e),
+            ConfigError::MissingField(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<anyhow::Error> for ConfigError {
+    fn from(e: anyhow::Error) -> Self {
+        ConfigError::Invalid(e)
+    }
+}
+
 /// Application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    /// ClickUp API token
+    /// ClickUp API token (may instead come from `CLICKUP_API_TOKEN`)
+    #[serde(default)]
     pub api_token: String,
-    /// ClickUp user ID (numeric)
+    /// Shell command that prints the API token (e.g. `pass show clickup`); wins over `api_token`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_token_command: Option<String>,
+    /// ClickUp user ID, numeric (may instead come from `CLICKUP_USER_ID`)
+    #[serde(default)]
     pub user_id: String,
     /// Auto-refresh on startup
     #[serde(default = "default_auto_refresh")]
     pub auto_refresh: bool,
+    /// Ask for confirmation before mutating actions (e.g. setting a priority)
+    #[serde(default = "default_confirm_actions")]
+    pub confirm_actions: bool,
+    /// ClickUp API root override (e.g. a proxy); unset uses the public API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_base: Option<String>,
+    /// ClickUp workspace (team) ID to read tasks from; unset asks when there are several
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+    /// Seconds before an API request is abandoned
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Seconds a status message stays up (0 keeps it until the next one)
+    #[serde(default = "default_status_timeout_secs")]
+    pub status_timeout_secs: u64,
+    /// Start with dense task rows (status color bar instead of status/type/ID tags)
+    #[serde(default)]
+    pub dense: bool,
+    /// Description lines shown in the preview before it's cut short (unset shows all)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_max_lines: Option<usize>,
+    /// What `y` copies, e.g. "{custom_id} {name}" (unset copies status, type, ID, and name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clipboard_template: Option<String>,
+    /// Start each task row with a bar colored by its list
+    #[serde(default)]
+    pub color_by_list: bool,
+    /// Show unassigned parents of the user's subtasks (greyed out) for tree context
+    #[serde(default = "default_show_unassigned_ancestors")]
+    pub show_unassigned_ancestors: bool,
+    /// Sort pinned tasks ahead of the rest (subtasks stay under their parents)
+    #[serde(default = "default_pin_to_top")]
+    pub pin_to_top: bool,
+    /// Hide Done subtasks under parents that are still open (`H` toggles); other
+    /// tabs never list them there, so this affects person views and fetched subtask trees
+    #[serde(default)]
+    pub hide_done_subtasks: bool,
+    /// Days back the Done tab looks for recently updated tasks (0 shows all of them)
+    #[serde(default = "default_done_recent_days")]
+    pub done_recent_days: u32,
+    /// Show a desktop notification when a task becomes overdue while the app is open
+    #[serde(default)]
+    pub notifications: bool,
+    /// Background refresh interval in seconds (unset or 0 disables polling)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval_secs: Option<u64>,
+    /// Custom ClickUp status -> group name mapping (e.g. "ready for qa" = "waiting")
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub status_groups: HashMap<String, String>,
+    /// Tabs to show, in order (e.g. ["today", "my_action", "waiting"]); unset shows all
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub visible_groups: Vec<String>,
+    /// Tab to open on at startup (e.g. "today"); unset opens My Action
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_group: Option<String>,
+    /// Default sort per tab: group name -> sort mode (e.g. done = "updated")
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sort: HashMap<String, String>,
+    /// Normal-mode key overrides: action name -> key or list of keys (e.g. refresh = "R")
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub keybindings: HashMap<String, KeySpec>,
+    /// Color palette: `base = "light"` and/or hex overrides per color
+    #[serde(default, skip_serializing_if = "ThemeConfig::is_empty")]
+    pub theme: ThemeConfig,
+    /// Backlog heat dot thresholds: days untouched before aging, then stale
+    #[serde(default, skip_serializing_if = "BacklogConfig::is_default")]
+    pub backlog: BacklogConfig,
+}
+
+/// The `[backlog]` config table: days without an update before a Backlog task's dot
+/// turns from green to yellow (aging) and then orange (stale)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BacklogConfig {
+    #[serde(default = "default_aging_days")]
+    pub aging_days: u32,
+    #[serde(default = "default_stale_days")]
+    pub stale_days: u32,
+}
+
+impl Default for BacklogConfig {
+    fn default() -> Self {
+        Self {
+            aging_days: default_aging_days(),
+            stale_days: default_stale_days(),
+        }
+    }
+}
+
+impl BacklogConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 fn default_auto_refresh() -> bool {
     true
 }
 
+fn default_confirm_actions() -> bool {
+    true
+}
+
+fn default_request_timeout_secs() -> u64 {
+    30
+}
+
+fn default_status_timeout_secs() -> u64 {
+    3
+}
+
+fn default_show_unassigned_ancestors() -> bool {
+    true
+}
+
+fn default_pin_to_top() -> bool {
+    true
+}
+
+fn default_done_recent_days() -> u32 {
+    7
+}
+
+fn default_aging_days() -> u32 {
+    14
+}
+
+fn default_stale_days() -> u32 {
+    30
+}
+
+/// Run `command` through the platform shell
+fn shell_command(command: &str) -> std::process::Command {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = std::process::Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = std::process::Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    cmd.arg(command);
+    cmd
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             api_token: String::new(),
+            api_token_command: None,
             user_id: String::new(),
             auto_refresh: true,
+            confirm_actions: true,
+            api_base: None,
+            workspace_id: None,
+            request_timeout_secs: default_request_timeout_secs(),
+            status_timeout_secs: default_status_timeout_secs(),
+            dense: false,
+            clipboard_template: None,
+            color_by_list: false,
+            preview_max_lines: None,
+            show_unassigned_ancestors: true,
+            pin_to_top: true,
+            hide_done_subtasks: false,
+            done_recent_days: default_done_recent_days(),
+            notifications: false,
+            refresh_interval_secs: None,
+            status_groups: HashMap::new(),
+            visible_groups: Vec::new(),
+            default_group: None,
+            sort: HashMap::new(),
+            keybindings: HashMap::new(),
+            theme: ThemeConfig::default(),
+            backlog: BacklogConfig::default(),
         }
     }
 }
 
 impl Config {
-    /// Get the config directory path (~/.config/clickup-tui on all platforms)
+    /// Get the config directory path
+    ///
+    /// Resolution order: `$XDG_CONFIG_HOME/clickup-tui`, then `%APPDATA%\clickup-tui`
+    /// on Windows, then `~/.config/clickup-tui` (home from `HOME` or `USERPROFILE`).
     pub fn config_dir() -> Result<PathBuf> {
-        let home = std::env::var("HOME")
-            .or_else(|_| std::env::var("USERPROFILE"))
+        let non_empty = |var: &str| std::env::var(var).ok().filter(|v| !v.is_empty());
+
+        if let Some(xdg) = non_empty("XDG_CONFIG_HOME") {
+            return Ok(PathBuf::from(xdg).join("clickup-tui"));
+        }
+
+        if cfg!(windows) {
+            if let Some(appdata) = non_empty("APPDATA") {
+                return Ok(PathBuf::from(appdata).join("clickup-tui"));
+            }
+        }
+
+        let home = non_empty("HOME")
+            .or_else(|| non_empty("USERPROFILE"))
             .context("Could not determine home directory")?;
         Ok(PathBuf::from(home).join(".config").join("clickup-tui"))
     }
 
-    /// Get the config file path
-    pub fn config_path() -> Result<PathBuf> {
-        Ok(Self::config_dir()?.join("config.toml"))
+    /// Get the config file path (`config.toml`, or `<profile>.toml` for a named profile)
+    pub fn config_path(profile: Option<&str>) -> Result<PathBuf> {
+        Self::profile_file(profile, "config.toml", "toml")
     }
 
     /// Get the local state file path (for pins, snoozes, etc.)
-    pub fn state_path() -> Result<PathBuf> {
-        Ok(Self::config_dir()?.join("local_state.json"))
+    pub fn state_path(profile: Option<&str>) -> Result<PathBuf> {
+        Self::profile_file(profile, "local_state.json", "local_state.json")
     }
 
     /// Get the cache file path (for cached tasks)
-    pub fn cache_path() -> Result<PathBuf> {
-        Ok(Self::config_dir()?.join("tasks_cache.json"))
+    pub fn cache_path(profile: Option<&str>) -> Result<PathBuf> {
+        Self::profile_file(profile, "tasks_cache.json", "tasks_cache.json")
+    }
+
+    /// Resolve a file in the config dir, namespaced as `<profile>.<suffix>` for named profiles
+    fn profile_file(profile: Option<&str>, default_name: &str, suffix: &str) -> Result<PathBuf> {
+        let dir = Self::config_dir()?;
+        match profile {
+            None => Ok(dir.join(default_name)),
+            Some(name) => {
+                if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+                    anyhow::bail!("Invalid profile name: {:?}", name);
+                }
+                Ok(dir.join(format!("{}.{}", name, suffix)))
+            }
+        }
     }
 
     /// Load config from file, or create default if not exists
-    pub fn load() -> Result<Self> {
-        let path = Self::config_path()?;
+    ///
+    /// `CLICKUP_API_TOKEN` and `CLICKUP_USER_ID` override the file values when set.
+    /// The error says which kind of problem it was (see [`ConfigError::exit_code`]).
+    pub fn load(profile: Option<&str>) -> std::result::Result<Self, ConfigError> {
+        let path = Self::config_path(profile)?;
 
-        if !path.exists() {
+        let mut config = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read config from {}", path.display()))?;
+
+            toml::from_str(&content)
+                .with_context(|| format!("Failed to parse config from {}", path.display()))?
+        } else {
             // Create default config
-            let config = Self::default();
-            config.save()?;
+            let mut config = Self::default();
+            config.save(profile)?;
 
-            anyhow::bail!(
-                "Config file created at {}. Please edit it to add your ClickUp API token and user ID.",
+            config.apply_env_overrides();
+            if !config.has_credentials() {
+                return Err(ConfigError::NotFound(path));
+            }
+            config
+        };
+
+        config
+            .apply_token_command()
+            .with_context(|| format!("Invalid api_token_command in {}", path.display()))?;
+        config.apply_env_overrides();
+
+        // Validate required fields
+        if config.api_token.is_empty() {
+            return Err(ConfigError::MissingField(format!(
+                "api_token is required: set {} or api_token in {} (the environment variable takes precedence)",
+                API_TOKEN_ENV,
                 path.display()
-            );
+            )));
         }
+        if config.user_id.is_empty() {
+            return Err(ConfigError::MissingField(format!(
+                "user_id is required: set {} or user_id in {} (the environment variable takes precedence)",
+                USER_ID_ENV,
+                path.display()
+            )));
+        }
+        config.validate(&path)?;
 
+        Ok(config)
+    }
+
+    /// Re-read the config file while the app is running
+    ///
+    /// Unlike `load`, this doesn't run `api_token_command` (it may prompt, and the
+    /// TUI owns the terminal) or write a default file when there isn't one.
+    pub fn reload(profile: Option<&str>) -> Result<Self> {
+        let path = Self::config_path(profile)?;
+        if !path.exists() {
+            anyhow::bail!("no config file at {}", path.display());
+        }
+        let mut config = Self::load_raw(profile)?;
+        config.apply_env_overrides();
+        config.validate(&path)?;
+        Ok(config)
+    }
+
+    /// Check the tables that only fail when resolved, naming the file
+    fn validate(&self, path: &std::path::Path) -> Result<()> {
+        self.status_group_overrides()
+            .with_context(|| format!("Invalid [status_groups] in {}", path.display()))?;
+        self.visible_groups()
+            .with_context(|| format!("Invalid visible_groups in {}", path.display()))?;
+        self.group_sorts()
+            .with_context(|| format!("Invalid [sort] in {}", path.display()))?;
+        self.keymap()
+            .with_context(|| format!("Invalid [keybindings] in {}", path.display()))?;
+        self.theme()
+            .with_context(|| format!("Invalid [theme] in {}", path.display()))?;
+        self.backlog()
+            .with_context(|| format!("Invalid [backlog] in {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Read the config file as written (defaults if it doesn't exist), without
+    /// environment overrides or validation
+    pub fn load_raw(profile: Option<&str>) -> Result<Self> {
+        let path = Self::config_path(profile)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
         let content = fs::read_to_string(&path)
             .with_context(|| format!("Failed to read config from {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config from {}", path.display()))
+    }
 
-        let config: Config = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config from {}", path.display()))?;
+    /// Whether the API token or user ID is missing from both the config file and
+    /// the environment (i.e. first-run setup is needed)
+    ///
+    /// An unreadable config is left for `load` to report.
+    pub fn needs_setup(profile: Option<&str>) -> bool {
+        Self::load_raw(profile)
+            .map(|mut config| {
+                config.apply_env_overrides();
+                !config.has_credentials()
+            })
+            .unwrap_or(false)
+    }
+
+    /// Whether both the API token (or a command for it) and user ID are set
+    pub fn has_credentials(&self) -> bool {
+        (!self.api_token.is_empty() || self.api_token_command.is_some()) && !self.user_id.is_empty()
+    }
+
+    /// Read the API token without requiring the rest of the config (for `--show-user-id`)
+    ///
+    /// Uses `CLICKUP_API_TOKEN` if set, otherwise `api_token_command` or `api_token`
+    /// from the config file.
+    pub fn load_api_token(profile: Option<&str>) -> Result<String> {
+        let path = Self::config_path(profile)?;
+        let mut config = Self::load_raw(profile)?;
+        config.apply_token_command()?;
+        config.apply_env_overrides();
 
-        // Validate required fields
         if config.api_token.is_empty() {
-            anyhow::bail!("api_token is required in config file: {}", path.display());
+            anyhow::bail!(
+                "api_token is required: set {} or api_token in {}",
+                API_TOKEN_ENV,
+                path.display()
+            );
         }
-        if config.user_id.is_empty() {
-            anyhow::bail!("user_id is required in config file: {}", path.display());
+        Ok(config.api_token)
+    }
+
+    /// Replace `api_token` with the trimmed output of `api_token_command`, if one is set
+    ///
+    /// Skipped when `CLICKUP_API_TOKEN` is set, since that takes precedence anyway.
+    /// Fails if the command can't run, exits nonzero, or prints nothing.
+    pub fn apply_token_command(&mut self) -> Result<()> {
+        let Some(command) = self
+            .api_token_command
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+        else {
+            return Ok(());
+        };
+        if std::env::var(API_TOKEN_ENV).is_ok_and(|v| !v.is_empty()) {
+            return Ok(());
         }
 
-        Ok(config)
+        let output = shell_command(command)
+            .stdin(std::process::Stdio::inherit())
+            .stderr(std::process::Stdio::piped())
+            .output()
+            .with_context(|| format!("Failed to run `{}`", command))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(
+                "`{}` failed ({}): {}",
+                command,
+                output.status,
+                stderr.trim()
+            );
+        }
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if token.is_empty() {
+            anyhow::bail!("`{}` printed no token", command);
+        }
+        self.api_token = token;
+        Ok(())
+    }
+
+    /// Replace credentials with `CLICKUP_API_TOKEN` / `CLICKUP_USER_ID` when those are set
+    pub fn apply_env_overrides(&mut self) {
+        if let Some(token) = std::env::var(API_TOKEN_ENV).ok().filter(|v| !v.is_empty()) {
+            self.api_token = token;
+        }
+        if let Some(user_id) = std::env::var(USER_ID_ENV).ok().filter(|v| !v.is_empty()) {
+            self.user_id = user_id;
+        }
+    }
+
+    /// How long an API request may take (at least a second)
+    pub fn request_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.request_timeout_secs.max(1))
+    }
+
+    /// How long status messages stay up, if they expire at all
+    pub fn status_timeout(&self) -> Option<std::time::Duration> {
+        Some(self.status_timeout_secs)
+            .filter(|&secs| secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    /// How many days of completed tasks the Done tab shows, if it's limited at all
+    pub fn done_recent_days(&self) -> Option<u32> {
+        Some(self.done_recent_days).filter(|&days| days > 0)
+    }
+
+    /// Background refresh interval, if polling is enabled
+    pub fn refresh_interval(&self) -> Option<std::time::Duration> {
+        self.refresh_interval_secs
+            .filter(|&secs| secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    /// Resolve the `[status_groups]` table into group overrides keyed by lowercased status
+    pub fn status_group_overrides(&self) -> Result<StatusGroups> {
+        self.status_groups
+            .iter()
+            .map(|(status, name)| match TaskGroup::from_name(name) {
+                Some(
+                    TaskGroup::Snoozed | TaskGroup::Person | TaskGroup::Today | TaskGroup::Watching,
+                )
+                | None => anyhow::bail!(
+                    "unknown group \"{}\" for status \"{}\" (expected my_action, waiting, backlog, or done)",
+                    name,
+                    status
+                ),
+                Some(group) => Ok((status.to_lowercase(), group)),
+            })
+            .collect()
+    }
+
+    /// Resolve `visible_groups` into tab order (all groups when unset)
+    pub fn visible_groups(&self) -> Result<Vec<TaskGroup>> {
+        if self.visible_groups.is_empty() {
+            return Ok(TaskGroup::all().to_vec());
+        }
+        let mut groups = Vec::new();
+        for name in &self.visible_groups {
+            let Some(group) = TaskGroup::from_name(name) else {
+                anyhow::bail!(
+                    "unknown group \"{}\" (expected my_action, waiting, backlog, done, snoozed, person, today, or watching)",
+                    name
+                );
+            };
+            if groups.contains(&group) {
+                anyhow::bail!("group \"{}\" is listed more than once", name);
+            }
+            groups.push(group);
+        }
+        Ok(groups)
+    }
+
+    /// Resolve `default_group` into the tab to open on, if one is set
+    pub fn default_group(&self) -> Result<Option<TaskGroup>> {
+        let Some(name) = &self.default_group else {
+            return Ok(None);
+        };
+        match TaskGroup::from_name(name) {
+            Some(group) => Ok(Some(group)),
+            None => anyhow::bail!(
+                "unknown default_group \"{}\" (expected my_action, waiting, backlog, done, snoozed, person, today, or watching)",
+                name
+            ),
+        }
+    }
+
+    /// Resolve the `[sort]` table into each tab's default sort mode
+    pub fn group_sorts(&self) -> Result<HashMap<TaskGroup, SortMode>> {
+        self.sort
+            .iter()
+            .map(|(group, mode)| {
+                let Some(group_key) = TaskGroup::from_name(group) else {
+                    anyhow::bail!(
+                        "unknown group \"{}\" (expected my_action, waiting, backlog, done, snoozed, person, today, or watching)",
+                        group
+                    );
+                };
+                let Some(mode) = SortMode::from_name(mode) else {
+                    anyhow::bail!(
+                        "unknown sort \"{}\" for group \"{}\" (expected priority, due_date, name, status, or updated)",
+                        mode,
+                        group
+                    );
+                };
+                Ok((group_key, mode))
+            })
+            .collect()
+    }
+
+    /// Build the Normal-mode keymap from defaults plus the `[keybindings]` table
+    pub fn keymap(&self) -> Result<Keymap> {
+        Keymap::from_config(&self.keybindings)
+    }
+
+    /// The `[backlog]` age thresholds, checked to be in order
+    pub fn backlog(&self) -> Result<BacklogConfig> {
+        if self.backlog.aging_days > self.backlog.stale_days {
+            anyhow::bail!(
+                "[backlog] aging_days ({}) is more than stale_days ({})",
+                self.backlog.aging_days,
+                self.backlog.stale_days
+            );
+        }
+        Ok(self.backlog)
+    }
+
+    /// Resolve the `[theme]` table into a color palette (dark by default)
+    pub fn theme(&self) -> Result<Theme> {
+        self.theme.resolve()
     }
 
     /// Save config to file
-    pub fn save(&self) -> Result<()> {
-        let path = Self::config_path()?;
+    pub fn save(&self, profile: Option<&str>) -> Result<()> {
+        let path = Self::config_path(profile)?;
         let dir = path.parent().unwrap();
 
         // Create directory if needed
@@ -118,15 +643,375 @@ impl Config {
     }
 }
 
+/// Serializes tests that mutate process-wide environment variables
+#[cfg(test)]
+static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Holds `ENV_LOCK` and puts the given environment variables back when dropped,
+/// even if the test panics
+#[cfg(test)]
+pub(crate) struct EnvGuard {
+    saved: Vec<(&'static str, Option<std::ffi::OsString>)>,
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+#[cfg(test)]
+impl EnvGuard {
+    pub(crate) fn new(vars: &[&'static str]) -> Self {
+        // A test that panicked still restored its variables, so the lock is safe to reuse
+        let lock = ENV_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let saved = vars
+            .iter()
+            .map(|&var| (var, std::env::var_os(var)))
+            .collect();
+        Self { saved, _lock: lock }
+    }
+}
+
+#[cfg(test)]
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (var, value) in &self.saved {
+            match value {
+                Some(v) => std::env::set_var(var, v),
+                None => std::env::remove_var(var),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_config_dir_prefers_xdg_config_home() {
+        let _env = EnvGuard::new(&["XDG_CONFIG_HOME"]);
+
+        std::env::set_var("XDG_CONFIG_HOME", "/tmp/xdg-test");
+        assert_eq!(
+            Config::config_dir().unwrap(),
+            PathBuf::from("/tmp/xdg-test/clickup-tui")
+        );
+    }
+
+    #[test]
+    fn test_profile_paths() {
+        let _env = EnvGuard::new(&[]);
+        let dir = Config::config_dir().unwrap();
+
+        assert_eq!(Config::config_path(None).unwrap(), dir.join("config.toml"));
+        assert_eq!(
+            Config::config_path(Some("work")).unwrap(),
+            dir.join("work.toml")
+        );
+        assert_eq!(
+            Config::state_path(Some("work")).unwrap(),
+            dir.join("work.local_state.json")
+        );
+        assert_eq!(
+            Config::cache_path(Some("work")).unwrap(),
+            dir.join("work.tasks_cache.json")
+        );
+        assert!(Config::config_path(Some("../evil")).is_err());
+    }
+
+    #[test]
+    fn test_env_overrides_credentials() {
+        let _env = EnvGuard::new(&[API_TOKEN_ENV, USER_ID_ENV]);
+
+        let mut config: Config = toml::from_str(r#"api_token = "pk_file""#).unwrap();
+        std::env::set_var(API_TOKEN_ENV, "pk_env");
+        std::env::set_var(USER_ID_ENV, "42");
+        config.apply_env_overrides();
+        assert_eq!(config.api_token, "pk_env");
+        assert_eq!(config.user_id, "42");
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_api_token_command() {
+        let _env = EnvGuard::new(&[API_TOKEN_ENV]);
+        std::env::remove_var(API_TOKEN_ENV);
+
+        let mut config: Config = toml::from_str(
+            r#"
+            api_token = "pk_file"
+            api_token_command = "echo '  pk_secret  '"
+            "#,
+        )
+        .unwrap();
+        config.apply_token_command().unwrap();
+        assert_eq!(config.api_token, "pk_secret");
+
+        config.api_token_command = Some("echo locked >&2; exit 3".to_string());
+        let err = config.apply_token_command().unwrap_err().to_string();
+        assert!(err.contains("failed") && err.contains("locked"), "{}", err);
+
+        config.api_token_command = Some("true".to_string());
+        assert!(config.apply_token_command().is_err());
+
+        // The environment variable wins without running the command
+        std::env::set_var(API_TOKEN_ENV, "pk_env");
+        config.api_token_command = Some("exit 1".to_string());
+        assert!(config.apply_token_command().is_ok());
+    }
+
+    #[test]
+    fn test_needs_setup() {
+        let _env = EnvGuard::new(&["XDG_CONFIG_HOME", API_TOKEN_ENV, USER_ID_ENV]);
+
+        let root = std::env::temp_dir().join(format!("clickup-tui-setup-{}", std::process::id()));
+        std::env::set_var("XDG_CONFIG_HOME", &root);
+        std::env::remove_var(API_TOKEN_ENV);
+        std::env::remove_var(USER_ID_ENV);
+        let write = |content: &str| {
+            let path = Config::config_path(Some("setup")).unwrap();
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+
+        assert!(Config::needs_setup(Some("setup")), "missing file");
+        write(r#"api_token = "pk_file""#);
+        assert!(Config::needs_setup(Some("setup")));
+        std::env::set_var(USER_ID_ENV, "42");
+        assert!(!Config::needs_setup(Some("setup")));
+        write("api_token = ");
+        assert!(
+            !Config::needs_setup(Some("setup")),
+            "left for load to report"
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_load_error_exit_codes() {
+        let _env = EnvGuard::new(&["XDG_CONFIG_HOME", API_TOKEN_ENV, USER_ID_ENV]);
+
+        let root = std::env::temp_dir().join(format!("clickup-tui-load-{}", std::process::id()));
+        std::env::set_var("XDG_CONFIG_HOME", &root);
+        std::env::remove_var(API_TOKEN_ENV);
+        std::env::remove_var(USER_ID_ENV);
+        let path = Config::config_path(Some("load")).unwrap();
+        let exit_code = || Config::load(Some("load")).err().map(|e| e.exit_code());
+
+        assert_eq!(exit_code(), Some(2), "missing file");
+        fs::write(&path, "api_token = ").unwrap();
+        assert_eq!(exit_code(), Some(3), "parse error");
+        fs::write(&path, r#"api_token = "pk_file""#).unwrap();
+        assert_eq!(exit_code(), Some(4), "no user_id");
+        fs::write(
+            &path,
+            "api_token = \"pk_file\"\nuser_id = \"42\"\n[backlog]\naging_days = 60",
+        )
+        .unwrap();
+        assert_eq!(exit_code(), Some(3), "reversed backlog thresholds");
+        fs::write(&path, "api_token = \"pk_file\"\nuser_id = \"42\"").unwrap();
+        assert_eq!(exit_code(), None);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_reload_skips_token_command_and_missing_file() {
+        let _env = EnvGuard::new(&["XDG_CONFIG_HOME", API_TOKEN_ENV]);
+
+        let root = std::env::temp_dir().join(format!("clickup-tui-reload-{}", std::process::id()));
+        std::env::set_var("XDG_CONFIG_HOME", &root);
+        std::env::remove_var(API_TOKEN_ENV);
+        let path = Config::config_path(Some("reload")).unwrap();
+
+        // No file: an error, and none is written
+        assert!(Config::reload(Some("reload")).is_err());
+        assert!(!path.exists());
+
+        // The command would fail if it ran
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            "api_token_command = \"exit 1\"\nuser_id = \"42\"\ndense = true",
+        )
+        .unwrap();
+        let config = Config::reload(Some("reload")).unwrap();
+        assert!(config.dense);
+        assert!(config.api_token.is_empty());
+
+        fs::write(&path, "[sort]\ndone = \"size\"").unwrap();
+        let err = format!("{:#}", Config::reload(Some("reload")).unwrap_err());
+        assert!(err.contains("[sort]"), "{}", err);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_config_dir_falls_back_to_home() {
+        let _env = EnvGuard::new(&["XDG_CONFIG_HOME", "HOME"]);
+
+        std::env::set_var("XDG_CONFIG_HOME", "");
+        std::env::set_var("HOME", "/tmp/home-test");
+        assert_eq!(
+            Config::config_dir().unwrap(),
+            PathBuf::from("/tmp/home-test/.config/clickup-tui")
+        );
+    }
+
     #[test]
     fn test_default_config() {
         let config = Config::default();
         assert!(config.api_token.is_empty());
         assert!(config.user_id.is_empty());
         assert!(config.auto_refresh);
+        assert!(config.confirm_actions);
+        assert!(config.status_groups.is_empty());
+        assert!(config.refresh_interval().is_none());
+        assert_eq!(config.request_timeout(), std::time::Duration::from_secs(30));
+        assert_eq!(
+            config.status_timeout(),
+            Some(std::time::Duration::from_secs(3))
+        );
+        assert_eq!(config.done_recent_days(), Some(7));
+        assert!(config.show_unassigned_ancestors);
+        assert!(config.pin_to_top);
+    }
+
+    #[test]
+    fn test_status_group_overrides() {
+        let config: Config = toml::from_str(
+            r#"
+            api_token = "pk_test"
+            user_id = "1"
+
+            [status_groups]
+            "Ready for QA" = "waiting"
+            "Awaiting Client" = "My Action"
+            "#,
+        )
+        .unwrap();
+
+        let overrides = config.status_group_overrides().unwrap();
+        assert_eq!(overrides.get("ready for qa"), Some(&TaskGroup::Waiting));
+        assert_eq!(overrides.get("awaiting client"), Some(&TaskGroup::MyAction));
+    }
+
+    #[test]
+    fn test_status_group_overrides_rejects_unknown_group() {
+        let mut config = Config::default();
+        config
+            .status_groups
+            .insert("ready for qa".to_string(), "later".to_string());
+        assert!(config.status_group_overrides().is_err());
+    }
+
+    #[test]
+    fn test_keybindings_table() {
+        let config: Config = toml::from_str(
+            r#"
+            api_token = "pk_test"
+            user_id = "1"
+
+            [keybindings]
+            refresh = "R"
+            next_task = ["n", "down"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.keybindings.get("refresh"),
+            Some(&KeySpec::One("R".to_string()))
+        );
+        assert!(config.keymap().is_ok());
+
+        let mut config = config;
+        config
+            .keybindings
+            .insert("refresh".to_string(), KeySpec::One("hyper-r".to_string()));
+        let err = config.keymap().unwrap_err().to_string();
+        assert!(err.contains("refresh"), "{}", err);
+    }
+
+    #[test]
+    fn test_visible_groups() {
+        let mut config = Config::default();
+        assert_eq!(config.visible_groups().unwrap(), TaskGroup::all());
+
+        config.visible_groups = vec!["Today".to_string(), "my_action".to_string()];
+        assert_eq!(
+            config.visible_groups().unwrap(),
+            vec![TaskGroup::Today, TaskGroup::MyAction]
+        );
+
+        config.visible_groups = vec!["later".to_string()];
+        assert!(config.visible_groups().is_err());
+        config.visible_groups = vec!["done".to_string(), "Done".to_string()];
+        assert!(config.visible_groups().is_err());
+    }
+
+    #[test]
+    fn test_default_group() {
+        let mut config = Config::default();
+        assert_eq!(config.default_group().unwrap(), None);
+        config.default_group = Some("Today".to_string());
+        assert_eq!(config.default_group().unwrap(), Some(TaskGroup::Today));
+        config.default_group = Some("later".to_string());
+        assert!(config.default_group().is_err());
+    }
+
+    #[test]
+    fn test_sort_table() {
+        let config: Config = toml::from_str(
+            r#"
+            [sort]
+            backlog = "priority"
+            Done = "updated"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.group_sorts().unwrap(),
+            HashMap::from([
+                (TaskGroup::Backlog, SortMode::Priority),
+                (TaskGroup::Done, SortMode::Updated),
+            ])
+        );
+
+        let mut config = config;
+        config.sort.insert("later".to_string(), "name".to_string());
+        assert!(config.group_sorts().is_err());
+        config.sort = HashMap::from([("done".to_string(), "size".to_string())]);
+        let err = config.group_sorts().unwrap_err().to_string();
+        assert!(err.contains("\"size\""), "{}", err);
+    }
+
+    #[test]
+    fn test_backlog_table() {
+        assert_eq!(
+            Config::default().backlog().unwrap(),
+            BacklogConfig::default()
+        );
+
+        let config: Config = toml::from_str(
+            r#"
+            [backlog]
+            stale_days = 60
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.backlog().unwrap(),
+            BacklogConfig {
+                aging_days: 14,
+                stale_days: 60,
+            }
+        );
+
+        let mut config = config;
+        config.backlog.aging_days = 90;
+        let err = config.backlog().unwrap_err().to_string();
+        assert!(err.contains("aging_days"), "{}", err);
     }
 }
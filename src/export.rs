@@ -0,0 +1,138 @@
+//! Task export for external tooling (`--export csv|json`)
+
+use crate::models::Task;
+use anyhow::{bail, Result};
+use chrono::DateTime;
+
+/// Output format for `--export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Parse a format name (`csv` or `json`, case-insensitive)
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            _ => bail!("Unknown export format: {} (expected csv or json)", name),
+        }
+    }
+}
+
+/// CSV column headers, in output order
+const CSV_COLUMNS: [&str; 9] = [
+    "id",
+    "custom_id",
+    "name",
+    "status",
+    "list",
+    "priority",
+    "due_date",
+    "tags",
+    "url",
+];
+
+/// Serialize tasks in the given format
+pub fn export(tasks: &[Task], format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(to_csv(tasks)),
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(tasks)? + "\n"),
+    }
+}
+
+/// Serialize tasks as CSV (RFC 4180 quoting; tags joined with `;`, due date as RFC 3339)
+pub fn to_csv(tasks: &[Task]) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push('\n');
+
+    for task in tasks {
+        let due_date = task
+            .due_date
+            .and_then(DateTime::from_timestamp_millis)
+            .map(|d| d.to_rfc3339())
+            .unwrap_or_default();
+        let fields = [
+            task.id.as_str(),
+            task.custom_id.as_deref().unwrap_or(""),
+            task.name.as_str(),
+            task.status.as_str(),
+            task.list_name.as_str(),
+            task.priority_label().unwrap_or(""),
+            due_date.as_str(),
+            &task.tags.join(";"),
+            task.url.as_str(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task() -> Task {
+        Task {
+            name: "Fix \"login\", again".to_string(),
+            status: "in progress".to_string(),
+            list_name: "Backend".to_string(),
+            list_id: Some("901".to_string()),
+            team_id: Some("42".to_string()),
+            due_date: Some(0),
+            priority: Some(2),
+            url: "https://app.clickup.com/t/abc".to_string(),
+            tags: vec!["api".to_string(), "auth".to_string()],
+            description: Some("multi\nline".to_string()),
+            custom_id: Some("PROJ-7".to_string()),
+            assignee_ids: vec![1],
+            ..Task::test("abc")
+        }
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(ExportFormat::parse("CSV").unwrap(), ExportFormat::Csv);
+        assert_eq!(ExportFormat::parse("json").unwrap(), ExportFormat::Json);
+        assert!(ExportFormat::parse("xml").is_err());
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = to_csv(&[task()]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "id,custom_id,name,status,list,priority,due_date,tags,url"
+        );
+        assert_eq!(
+            lines[1],
+            "abc,PROJ-7,\"Fix \"\"login\"\", again\",in progress,Backend,High,\
+             1970-01-01T00:00:00+00:00,api;auth,https://app.clickup.com/t/abc"
+        );
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_json_round_trips() {
+        let json = export(&[task()], ExportFormat::Json).unwrap();
+        let tasks: Vec<Task> = serde_json::from_str(&json).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].name, "Fix \"login\", again");
+        assert_eq!(tasks[0].tags, vec!["api", "auth"]);
+    }
+}
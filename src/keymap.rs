@@ -0,0 +1,622 @@
+//! Configurable Normal-mode keybindings
+//!
+//! Maps key presses to named actions. Defaults match the built-in bindings; the
+//! `[keybindings]` config table replaces the keys for individual actions. The `g`
+//! prefix (`gg`, `g{letter}`) and digit counts are built in and can't be rebound.
+
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A Normal-mode command that can be bound to keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    NextTask,
+    PrevTask,
+    LastTask,
+    HalfPageDown,
+    HalfPageUp,
+    NextPane,
+    PrevPane,
+    NextTab,
+    PrevTab,
+    TogglePin,
+    ToggleSelect,
+    ToggleCollapse,
+    ToggleWrap,
+    ToggleDense,
+    ToggleDoneSubtasks,
+    Snooze,
+    Unsnooze,
+    Undo,
+    CycleSort,
+    SetPriority,
+    AssignMe,
+    UnassignMe,
+    ToggleDoneAll,
+    QrCode,
+    JumpToId,
+    TagFilter,
+    ListPicker,
+    PersonView,
+    ClearFilters,
+    SnoozeTomorrow,
+    SnoozeWeek,
+    SnoozeMonth,
+    EditNote,
+    WaitingOn,
+    ChecklistNext,
+    ChecklistPrev,
+    ToggleChecklistItem,
+    FetchSubtasks,
+    Comment,
+    Export,
+    ToggleTimer,
+    Open,
+    OpenList,
+    Copy,
+    CopyUrl,
+    CopyLink,
+    Search,
+    Refresh,
+    FullRefresh,
+    ReloadConfig,
+    MessageLog,
+    Help,
+}
+
+impl Action {
+    pub fn all() -> &'static [Action] {
+        &[
+            Action::Quit,
+            Action::NextTask,
+            Action::PrevTask,
+            Action::LastTask,
+            Action::HalfPageDown,
+            Action::HalfPageUp,
+            Action::NextPane,
+            Action::PrevPane,
+            Action::NextTab,
+            Action::PrevTab,
+            Action::TogglePin,
+            Action::ToggleSelect,
+            Action::ToggleCollapse,
+            Action::ToggleWrap,
+            Action::ToggleDense,
+            Action::ToggleDoneSubtasks,
+            Action::Snooze,
+            Action::Unsnooze,
+            Action::Undo,
+            Action::CycleSort,
+            Action::SetPriority,
+            Action::AssignMe,
+            Action::UnassignMe,
+            Action::ToggleDoneAll,
+            Action::QrCode,
+            Action::JumpToId,
+            Action::TagFilter,
+            Action::ListPicker,
+            Action::PersonView,
+            Action::ClearFilters,
+            Action::SnoozeTomorrow,
+            Action::SnoozeWeek,
+            Action::SnoozeMonth,
+            Action::EditNote,
+            Action::WaitingOn,
+            Action::ChecklistNext,
+            Action::ChecklistPrev,
+            Action::ToggleChecklistItem,
+            Action::FetchSubtasks,
+            Action::Comment,
+            Action::Export,
+            Action::ToggleTimer,
+            Action::Open,
+            Action::OpenList,
+            Action::Copy,
+            Action::CopyUrl,
+            Action::CopyLink,
+            Action::Search,
+            Action::Refresh,
+            Action::FullRefresh,
+            Action::ReloadConfig,
+            Action::MessageLog,
+            Action::Help,
+        ]
+    }
+
+    /// Name used in the `[keybindings]` config table
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::NextTask => "next_task",
+            Action::PrevTask => "prev_task",
+            Action::LastTask => "last_task",
+            Action::HalfPageDown => "half_page_down",
+            Action::HalfPageUp => "half_page_up",
+            Action::NextPane => "next_pane",
+            Action::PrevPane => "prev_pane",
+            Action::NextTab => "next_tab",
+            Action::PrevTab => "prev_tab",
+            Action::TogglePin => "toggle_pin",
+            Action::ToggleSelect => "toggle_select",
+            Action::ToggleCollapse => "toggle_collapse",
+            Action::ToggleWrap => "toggle_wrap",
+            Action::ToggleDense => "toggle_dense",
+            Action::ToggleDoneSubtasks => "toggle_done_subtasks",
+            Action::Snooze => "snooze",
+            Action::Unsnooze => "unsnooze",
+            Action::Undo => "undo",
+            Action::CycleSort => "cycle_sort",
+            Action::SetPriority => "set_priority",
+            Action::AssignMe => "assign_me",
+            Action::UnassignMe => "unassign_me",
+            Action::ToggleDoneAll => "toggle_done_all",
+            Action::QrCode => "qr_code",
+            Action::JumpToId => "jump_to_id",
+            Action::TagFilter => "tag_filter",
+            Action::ListPicker => "list_picker",
+            Action::PersonView => "person_view",
+            Action::ClearFilters => "clear_filters",
+            Action::SnoozeTomorrow => "snooze_tomorrow",
+            Action::SnoozeWeek => "snooze_week",
+            Action::SnoozeMonth => "snooze_month",
+            Action::EditNote => "edit_note",
+            Action::WaitingOn => "waiting_on",
+            Action::ChecklistNext => "checklist_next",
+            Action::ChecklistPrev => "checklist_prev",
+            Action::ToggleChecklistItem => "toggle_checklist_item",
+            Action::FetchSubtasks => "fetch_subtasks",
+            Action::Comment => "comment",
+            Action::Export => "export",
+            Action::ToggleTimer => "toggle_timer",
+            Action::Open => "open",
+            Action::OpenList => "open_list",
+            Action::Copy => "copy",
+            Action::CopyUrl => "copy_url",
+            Action::CopyLink => "copy_link",
+            Action::Search => "search",
+            Action::Refresh => "refresh",
+            Action::FullRefresh => "full_refresh",
+            Action::ReloadConfig => "reload_config",
+            Action::MessageLog => "message_log",
+            Action::Help => "help",
+        }
+    }
+
+    /// Look up an action by its config name
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::all().iter().copied().find(|a| a.name() == name)
+    }
+
+    /// Built-in keys for this action
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::NextTask => &["j", "down"],
+            Action::PrevTask => &["k", "up"],
+            Action::LastTask => &["G"],
+            Action::HalfPageDown => &["ctrl-d"],
+            Action::HalfPageUp => &["ctrl-u"],
+            Action::NextPane => &["tab"],
+            Action::PrevPane => &["backtab"],
+            Action::NextTab => &["l"],
+            Action::PrevTab => &["h"],
+            Action::TogglePin => &["p"],
+            Action::ToggleSelect => &["v"],
+            Action::ToggleCollapse => &["space"],
+            Action::ToggleWrap => &["W"],
+            Action::ToggleDense => &["z"],
+            Action::ToggleDoneSubtasks => &["H"],
+            Action::Snooze => &["s"],
+            Action::Unsnooze => &["S"],
+            Action::Undo => &["u"],
+            Action::CycleSort => &["O"],
+            Action::SetPriority => &["!"],
+            Action::AssignMe => &["a"],
+            Action::UnassignMe => &["A"],
+            Action::ToggleDoneAll => &["D"],
+            Action::QrCode => &["Q"],
+            Action::JumpToId => &["#"],
+            Action::TagFilter => &["f"],
+            Action::ListPicker => &["L"],
+            Action::PersonView => &["P"],
+            Action::ClearFilters => &["esc"],
+            Action::SnoozeTomorrow => &["t"],
+            Action::SnoozeWeek => &["w"],
+            Action::SnoozeMonth => &["m"],
+            Action::EditNote => &["n"],
+            Action::WaitingOn => &["b"],
+            Action::ChecklistNext => &["J"],
+            Action::ChecklistPrev => &["K"],
+            Action::ToggleChecklistItem => &["X"],
+            Action::FetchSubtasks => &["E"],
+            Action::Comment => &["c"],
+            Action::Export => &["e"],
+            Action::ToggleTimer => &["T"],
+            Action::Open => &["o", "enter"],
+            Action::OpenList => &["ctrl-o"],
+            Action::Copy => &["y"],
+            Action::CopyUrl => &["Y"],
+            Action::CopyLink => &["ctrl-y"],
+            Action::Search => &["/"],
+            Action::Refresh => &["r"],
+            Action::FullRefresh => &["R"],
+            Action::ReloadConfig => &["C"],
+            Action::MessageLog => &["M"],
+            Action::Help => &["?"],
+        }
+    }
+}
+
+/// Keys for one action in the config: a single key or a list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeySpec {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeySpec {
+    fn keys(&self) -> &[String] {
+        match self {
+            KeySpec::One(key) => std::slice::from_ref(key),
+            KeySpec::Many(keys) => keys,
+        }
+    }
+}
+
+/// A key plus modifiers, normalized so it compares equal to the matching key event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is already reflected in the character (`G`) or key (`BackTab`)
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Parse a key string like `j`, `G`, `ctrl-d`, `alt-x`, `enter`, or `shift-tab`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec;
+        loop {
+            let lower = rest.to_lowercase();
+            let prefix = ["ctrl-", "c-", "alt-", "a-", "shift-", "s-"]
+                .iter()
+                .find(|p| lower.starts_with(*p) && rest.len() > p.len());
+            match prefix {
+                Some(p) => {
+                    modifiers |= match p.chars().next() {
+                        Some('c') => KeyModifiers::CONTROL,
+                        Some('a') => KeyModifiers::ALT,
+                        _ => KeyModifiers::SHIFT,
+                    };
+                    rest = &rest[p.len()..];
+                }
+                None => break,
+            }
+        }
+
+        let code = match rest.to_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        KeyCode::Char(c.to_ascii_uppercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => bail!("unknown key \"{}\"", spec),
+                }
+            }
+        };
+
+        if matches!(code, KeyCode::Char(c) if c == 'g' || c.is_ascii_digit())
+            && !modifiers.contains(KeyModifiers::CONTROL)
+            && !modifiers.contains(KeyModifiers::ALT)
+        {
+            bail!(
+                "key \"{}\" is reserved (g prefix and counts are built in)",
+                spec
+            );
+        }
+
+        Ok(Self::new(code, modifiers))
+    }
+
+    /// Short display form for the help overlay, like `j`, `Ctrl+d`, `Enter`, or `↓`
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "S-Tab".to_string(),
+            KeyCode::Backspace => "Bksp".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            other => format!("{:?}", other),
+        };
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push_str("Shift+");
+        }
+        label.push_str(&key);
+        label
+    }
+}
+
+/// Lookup from key presses to Normal-mode actions
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<KeyBinding, Action>,
+    /// Keys for each action, in the order they were configured
+    keys: HashMap<Action, Vec<KeyBinding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&HashMap::new()).expect("default keybindings are valid")
+    }
+}
+
+impl Keymap {
+    /// Build the keymap from the `[keybindings]` table, falling back to defaults per action
+    ///
+    /// Configured keys win over defaults: an action loses a default key that the
+    /// table gives to another action. Two configured actions can't share a key.
+    pub fn from_config(overrides: &HashMap<String, KeySpec>) -> Result<Self> {
+        if let Some(name) = overrides.keys().find(|n| Action::from_name(n).is_none()) {
+            bail!("unknown action \"{}\"", name);
+        }
+
+        let mut bindings = HashMap::new();
+        let mut action_keys: HashMap<Action, Vec<KeyBinding>> = HashMap::new();
+        for &action in Action::all() {
+            let Some(spec) = overrides.get(action.name()) else {
+                continue;
+            };
+            for key in spec.keys() {
+                let binding = KeyBinding::parse(key).map_err(|e| {
+                    anyhow::anyhow!("invalid keybinding for \"{}\": {}", action.name(), e)
+                })?;
+                if let Some(other) = bindings.insert(binding, action) {
+                    bail!(
+                        "key \"{}\" is bound to both \"{}\" and \"{}\"",
+                        key,
+                        other.name(),
+                        action.name()
+                    );
+                }
+                action_keys.entry(action).or_default().push(binding);
+            }
+        }
+        for &action in Action::all() {
+            if overrides.contains_key(action.name()) {
+                continue;
+            }
+            for key in action.default_keys() {
+                let binding = KeyBinding::parse(key).expect("default keybindings are valid");
+                if bindings.contains_key(&binding) {
+                    continue;
+                }
+                bindings.insert(binding, action);
+                action_keys.entry(action).or_default().push(binding);
+            }
+        }
+
+        Ok(Self {
+            bindings,
+            keys: action_keys,
+        })
+    }
+
+    /// Action bound to a key press, if any
+    pub fn action_for(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .get(&KeyBinding::new(key.code, key.modifiers))
+            .copied()
+    }
+
+    /// Keys bound to an action, in config order (empty if unbound)
+    pub fn keys_for(&self, action: Action) -> &[KeyBinding] {
+        self.keys.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_default_bindings() {
+        let keymap = Keymap::default();
+        let action = |code, mods| keymap.action_for(&press(code, mods));
+        assert_eq!(
+            action(KeyCode::Char('j'), KeyModifiers::NONE),
+            Some(Action::NextTask)
+        );
+        assert_eq!(
+            action(KeyCode::Down, KeyModifiers::NONE),
+            Some(Action::NextTask)
+        );
+        // Terminals report uppercase letters and BackTab with SHIFT set
+        assert_eq!(
+            action(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            Some(Action::LastTask)
+        );
+        assert_eq!(
+            action(KeyCode::BackTab, KeyModifiers::SHIFT),
+            Some(Action::PrevPane)
+        );
+        assert_eq!(
+            action(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            Some(Action::HalfPageDown)
+        );
+        assert_eq!(action(KeyCode::Char('d'), KeyModifiers::NONE), None);
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        let parse = |s| KeyBinding::parse(s).unwrap();
+        assert_eq!(
+            parse("C-d"),
+            KeyBinding::new(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse("alt-enter"),
+            KeyBinding::new(KeyCode::Enter, KeyModifiers::ALT)
+        );
+        assert_eq!(parse("shift-x"), parse("X"));
+        assert_eq!(parse("shift-tab"), parse("backtab"));
+        assert_eq!(
+            parse("space"),
+            KeyBinding::new(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert!(KeyBinding::parse("ctrl-").is_err());
+        assert!(KeyBinding::parse("foo").is_err());
+        assert!(KeyBinding::parse("g").is_err());
+        assert!(KeyBinding::parse("5").is_err());
+        assert!(KeyBinding::parse("ctrl-g").is_ok());
+    }
+
+    #[test]
+    fn test_overrides_replace_defaults() {
+        let overrides = HashMap::from([
+            ("quit".to_string(), KeySpec::One("x".to_string())),
+            (
+                "refresh".to_string(),
+                KeySpec::Many(vec!["R".to_string(), "ctrl-r".to_string()]),
+            ),
+        ]);
+        let keymap = Keymap::from_config(&overrides).unwrap();
+        let action = |code, mods| keymap.action_for(&press(code, mods));
+        assert_eq!(
+            action(KeyCode::Char('x'), KeyModifiers::NONE),
+            Some(Action::Quit)
+        );
+        assert_eq!(action(KeyCode::Char('q'), KeyModifiers::NONE), None);
+        assert_eq!(
+            action(KeyCode::Char('R'), KeyModifiers::SHIFT),
+            Some(Action::Refresh)
+        );
+        // `R` was full_refresh's default
+        assert!(keymap.keys_for(Action::FullRefresh).is_empty());
+        assert_eq!(
+            action(KeyCode::Char('r'), KeyModifiers::CONTROL),
+            Some(Action::Refresh)
+        );
+    }
+
+    #[test]
+    fn test_invalid_bindings_name_the_action() {
+        let bad_key = HashMap::from([("search".to_string(), KeySpec::One("nope".to_string()))]);
+        let err = Keymap::from_config(&bad_key).unwrap_err().to_string();
+        assert!(err.contains("\"search\""), "{}", err);
+
+        let bad_action = HashMap::from([("fly".to_string(), KeySpec::One("x".to_string()))]);
+        let err = Keymap::from_config(&bad_action).unwrap_err().to_string();
+        assert!(err.contains("\"fly\""), "{}", err);
+
+        // Two configured actions can't share a key
+        let conflict = HashMap::from([
+            ("quit".to_string(), KeySpec::One("p".to_string())),
+            ("toggle_pin".to_string(), KeySpec::One("p".to_string())),
+        ]);
+        let err = Keymap::from_config(&conflict).unwrap_err().to_string();
+        assert!(
+            err.contains("\"quit\"") && err.contains("\"toggle_pin\""),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_configured_key_takes_over_a_default() {
+        // `n` is edit_note's default; binding it elsewhere leaves edit_note unbound
+        let overrides = HashMap::from([(
+            "next_task".to_string(),
+            KeySpec::Many(vec!["n".to_string(), "down".to_string()]),
+        )]);
+        let keymap = Keymap::from_config(&overrides).unwrap();
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('n'), KeyModifiers::NONE)),
+            Some(Action::NextTask)
+        );
+        assert!(keymap.keys_for(Action::EditNote).is_empty());
+        // Other defaults are untouched
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('k'), KeyModifiers::NONE)),
+            Some(Action::PrevTask)
+        );
+    }
+
+    #[test]
+    fn test_keys_for_follows_overrides() {
+        let labels = |keymap: &Keymap, action| {
+            keymap
+                .keys_for(action)
+                .iter()
+                .map(KeyBinding::label)
+                .collect::<Vec<_>>()
+        };
+        let keymap = Keymap::default();
+        assert_eq!(labels(&keymap, Action::NextTask), ["j", "↓"]);
+        assert_eq!(labels(&keymap, Action::Open), ["o", "Enter"]);
+        assert_eq!(labels(&keymap, Action::HalfPageDown), ["Ctrl+d"]);
+        assert_eq!(labels(&keymap, Action::PrevPane), ["S-Tab"]);
+
+        let overrides = HashMap::from([
+            (
+                "refresh".to_string(),
+                KeySpec::Many(vec!["F".to_string(), "alt-r".to_string()]),
+            ),
+            ("search".to_string(), KeySpec::Many(vec![])),
+        ]);
+        let keymap = Keymap::from_config(&overrides).unwrap();
+        assert_eq!(labels(&keymap, Action::Refresh), ["F", "Alt+r"]);
+        assert!(keymap.keys_for(Action::Search).is_empty());
+    }
+}
@@ -5,27 +5,150 @@
 mod api;
 mod app;
 mod config;
+mod export;
+mod keymap;
+mod markdown;
 mod models;
 mod theme;
 mod ui;
 
-use anyhow::Result;
-use app::{App, FocusedPane, InputMode};
+use anyhow::{Context, Result};
+use app::{App, Command, InputMode};
 use config::Config;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use models::TaskGroup;
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
-use std::time::Duration;
+use std::io::{self, IsTerminal};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::time::{Interval, MissedTickBehavior};
+
+/// How often to look for tasks that have just become overdue (with `notifications` on)
+const OVERDUE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Command-line arguments
+#[derive(Debug, Default)]
+struct CliArgs {
+    /// Named config profile (`--profile <name>`)
+    profile: Option<String>,
+    /// Print the token owner's user ID and exit (`--show-user-id`)
+    show_user_id: bool,
+    /// Export tasks in this format and exit (`--export <csv|json>`)
+    export: Option<export::ExportFormat>,
+    /// Write the export here instead of stdout (`--out <path>`)
+    out: Option<std::path::PathBuf>,
+    /// Drop local data for tasks that no longer exist and exit (`--prune-local`)
+    prune_local: bool,
+    /// Use only cached tasks and make no network calls (`--offline`)
+    offline: bool,
+    /// Jump to tasks newly assigned to you on each refresh (`--triage`)
+    triage: bool,
+}
+
+impl CliArgs {
+    /// Parse arguments from the process command line
+    fn parse() -> Result<Self> {
+        let mut cli = Self::default();
+        let mut args = std::env::args().skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--profile" => {
+                    cli.profile = Some(args.next().context("--profile requires a name")?);
+                }
+                "--show-user-id" => cli.show_user_id = true,
+                "--prune-local" => cli.prune_local = true,
+                "--offline" => cli.offline = true,
+                "--triage" => cli.triage = true,
+                "--export" => {
+                    let format = args.next().context("--export requires csv or json")?;
+                    cli.export = Some(export::ExportFormat::parse(&format)?);
+                }
+                "--out" => {
+                    cli.out = Some(args.next().context("--out requires a path")?.into());
+                }
+                "-h" | "--help" => {
+                    println!(
+                        "Usage: clickup-tui [--profile <name>] [--offline] [--triage] [--show-user-id] [--prune-local] [--export <csv|json> [--out <path>]]"
+                    );
+                    println!();
+                    println!("Options:");
+                    println!(
+                        "  --profile <name>     Use <name>.toml and separate state/cache files"
+                    );
+                    println!(
+                        "  --offline            Work from cached tasks only, without network calls"
+                    );
+                    println!(
+                        "  --triage             Jump to tasks newly assigned to you on each refresh"
+                    );
+                    println!(
+                        "  --show-user-id       Print the user ID for your API token and exit"
+                    );
+                    println!(
+                        "  --prune-local        Forget pins, snoozes, and notes of deleted tasks and exit"
+                    );
+                    println!("  --export <csv|json>  Print all tasks in this format and exit");
+                    println!("  --out <path>         Write the export to a file instead of stdout");
+                    println!("  -h, --help           Show this help");
+                    std::process::exit(0);
+                }
+                _ => match arg.strip_prefix("--profile=") {
+                    Some(name) => cli.profile = Some(name.to_string()),
+                    None => anyhow::bail!("Unknown argument: {} (see --help)", arg),
+                },
+            }
+        }
+
+        if cli.out.is_some() && cli.export.is_none() {
+            anyhow::bail!("--out requires --export");
+        }
+        if cli.offline && cli.show_user_id {
+            anyhow::bail!("--show-user-id needs the network and can't be used with --offline");
+        }
+        if cli.offline && cli.triage {
+            anyhow::bail!("--triage watches refreshes and can't be used with --offline");
+        }
+        if cli.offline && cli.prune_local {
+            anyhow::bail!("--prune-local needs the network and can't be used with --offline");
+        }
+
+        Ok(cli)
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = match CliArgs::parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            // EX_USAGE, kept apart from the config exit codes (2-4)
+            eprintln!("Error: {}", e);
+            std::process::exit(64);
+        }
+    };
+    let profile = cli.profile.as_deref();
+
+    if cli.show_user_id {
+        return show_user_id(profile).await;
+    }
+
+    // First run: ask for credentials instead of bailing, when someone can answer
+    if Config::needs_setup(profile) && io::stdin().is_terminal() && io::stdout().is_terminal() {
+        if let Err(e) = run_setup(profile).await {
+            eprintln!("Setup failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+
     // Load config
-    let config = match Config::load() {
+    let mut config = match Config::load(profile) {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Configuration error: {}", e);
@@ -33,26 +156,65 @@ async fn main() -> Result<()> {
             eprintln!("Please ensure your config file exists and contains:");
             eprintln!("  api_token = \"your_clickup_api_token\"");
             eprintln!("  user_id = \"your_user_id\"");
+            eprintln!(
+                "(or set {} / {} in the environment)",
+                config::API_TOKEN_ENV,
+                config::USER_ID_ENV
+            );
             eprintln!();
-            if let Ok(path) = Config::config_path() {
+            if let Ok(path) = Config::config_path(profile) {
                 eprintln!("Config file location: {}", path.display());
             }
-            std::process::exit(1);
+            std::process::exit(e.exit_code());
         }
     };
 
+    // Several workspaces and none configured: ask which one, once
+    if !cli.offline
+        && config.workspace_id.is_none()
+        && io::stdin().is_terminal()
+        && io::stdout().is_terminal()
+    {
+        if let Err(e) = choose_workspace(&mut config, profile).await {
+            eprintln!("Warning: Could not list workspaces: {}", e);
+        }
+    }
+
+    if cli.prune_local {
+        return prune_local_state(&config, profile).await;
+    }
+
+    if let Some(format) = cli.export {
+        return export_tasks(&config, profile, format, cli.out.as_deref(), cli.offline).await;
+    }
+
     // Initialize app
     let mut app = App::new();
-    app.set_user_id(&config.user_id);
+    app.set_profile(cli.profile.clone());
+    if let Err(e) = app.set_user_id(&config.user_id) {
+        app.set_status(format!("Warning: {}", e));
+    }
+    apply_config(&mut app, &config)?;
+    match config.default_group() {
+        Ok(Some(group)) => app.set_default_group(group),
+        Ok(None) => {}
+        Err(e) => app.set_status(format!("Warning: {}", e)),
+    }
 
     // Load local state
     if let Err(e) = app.load_local_state() {
         eprintln!("Warning: Could not load local state: {}", e);
     }
+    app.wake_expired_snoozes();
 
     // Try to load cached tasks first
     let _ = app.load_cached_tasks();
 
+    // Reopen where the last session left off (a configured default_group wins)
+    if config.default_group.is_none() {
+        app.restore_position();
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -60,28 +222,21 @@ async fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Initial refresh if auto_refresh enabled or no cached tasks
-    if config.auto_refresh || app.tasks.is_empty() {
-        app.is_loading = true;
-        terminal.draw(|f| ui::render(f, &app))?;
-
-        match fetch_tasks(&config).await {
-            Ok(tasks) => {
-                app.set_tasks(tasks);
-                app.is_loading = false;
-                app.status_message = Some(format!("Loaded {} tasks", app.tasks.len()));
-                let _ = app.save_tasks_cache();
-                let _ = app.save_local_state();
-            }
-            Err(e) => {
-                app.is_loading = false;
-                app.status_message = Some(format!("Failed to load: {}", e));
-            }
-        }
+    // Run event loop (refreshing first if auto_refresh enabled or no cached tasks)
+    app.set_offline(cli.offline);
+    app.set_triage(cli.triage);
+    if cli.offline {
+        app.set_status(if app.tasks.is_empty() {
+            "Offline mode: no cached tasks"
+        } else {
+            "Offline mode: showing cached tasks"
+        });
     }
+    let initial_refresh = !cli.offline && (config.auto_refresh || app.tasks.is_empty());
+    let res = run_app(&mut terminal, &mut app, &mut config, initial_refresh).await;
 
-    // Run event loop
-    let res = run_app(&mut terminal, &mut app, &config).await;
+    app.remember_position();
+    let _ = app.save_local_state();
 
     // Restore terminal
     disable_raw_mode()?;
@@ -99,136 +254,577 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Interactive first-run setup: ask for an API token, verify it, and save it
+/// along with the token owner's user ID
+///
+/// A token already set in the config file or environment is reused rather than
+/// asked for; only values that were entered or discovered are written.
+async fn run_setup(profile: Option<&str>) -> Result<()> {
+    let path = Config::config_path(profile)?;
+    let mut file_config = Config::load_raw(profile)?;
+    let mut config = file_config.clone();
+    config.apply_token_command()?;
+    config.apply_env_overrides();
+
+    println!("Welcome to clickup-tui! Let's connect your ClickUp account.");
+    println!("Settings will be saved to {}", path.display());
+    println!();
+
+    let mut entered_token = false;
+    let user = loop {
+        if config.api_token.is_empty() {
+            println!("Create a personal API token under ClickUp Settings > Apps.");
+            let token = prompt("API token (blank to quit): ")?;
+            if token.is_empty() {
+                anyhow::bail!("no API token entered");
+            }
+            config.api_token = token;
+            entered_token = true;
+        }
+
+        match api_client(&config)?.get_authorized_user().await {
+            Ok(user) => break user,
+            Err(e) => {
+                eprintln!("Couldn't verify that token: {}", e);
+                eprintln!();
+                config.api_token.clear();
+            }
+        }
+    };
+
+    if entered_token {
+        file_config.api_token = config.api_token.clone();
+    }
+    file_config.user_id = user.id.to_string();
+    file_config.save(profile)?;
+
+    match &user.username {
+        Some(username) => println!("Authenticated as {} (user ID {})", username, user.id),
+        None => println!("Authenticated as user ID {}", user.id),
+    }
+    println!("Saved {}", path.display());
+    Ok(())
+}
+
+/// Ask which workspace to use when the token can see several, and save the
+/// answer to the config file as `workspace_id`
+///
+/// Does nothing when there's only one workspace. A blank answer picks the first.
+async fn choose_workspace(config: &mut Config, profile: Option<&str>) -> Result<()> {
+    let teams = api_client(config)?.get_teams().await?;
+    if teams.len() < 2 {
+        return Ok(());
+    }
+
+    println!("Your ClickUp account is in several workspaces:");
+    for (i, team) in teams.iter().enumerate() {
+        println!("  {}. {}", i + 1, team.name);
+    }
+    let team = loop {
+        let answer = prompt(&format!("Workspace [1-{}, default 1]: ", teams.len()))?;
+        if answer.is_empty() {
+            break &teams[0];
+        }
+        match answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| teams.get(n.wrapping_sub(1)))
+        {
+            Some(team) => break team,
+            None => eprintln!("Enter a number from 1 to {}", teams.len()),
+        }
+    };
+
+    let mut file_config = Config::load_raw(profile)?;
+    file_config.workspace_id = Some(team.id.clone());
+    file_config.save(profile)?;
+    config.workspace_id = Some(team.id.clone());
+    println!("Using {} (saved as workspace_id)", team.name);
+    Ok(())
+}
+
+/// Print a prompt and read one trimmed line from stdin
+fn prompt(label: &str) -> Result<String> {
+    use std::io::Write;
+
+    print!("{}", label);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin()
+        .read_line(&mut line)
+        .context("Failed to read from stdin")?;
+    Ok(line.trim().to_string())
+}
+
+/// Print the numeric user ID that owns the configured API token
+async fn show_user_id(profile: Option<&str>) -> Result<()> {
+    // Only the token is required, but the base URL and timeout still apply
+    let mut config = Config::load_raw(profile)?;
+    config.api_token = Config::load_api_token(profile)?;
+    let user = api_client(&config)?.get_authorized_user().await?;
+
+    if let Some(username) = &user.username {
+        eprintln!("Authenticated as {}", username);
+    }
+    println!("{}", user.id);
+    Ok(())
+}
+
+/// Remove local overlays for tasks that no longer exist in ClickUp
+///
+/// Always fetches the full task set first: pruning against a stale or empty
+/// cache would throw away data for tasks that still exist.
+async fn prune_local_state(config: &Config, profile: Option<&str>) -> Result<()> {
+    let mut app = App::new();
+    app.set_profile(profile.map(str::to_string));
+    app.load_local_state()?;
+
+    let tasks = fetch_tasks(config, None)
+        .await
+        .context("Failed to fetch tasks (nothing was pruned)")?;
+    if tasks.is_empty() {
+        anyhow::bail!("ClickUp returned no tasks; refusing to prune everything");
+    }
+    app.set_tasks(tasks);
+    let _ = app.save_tasks_cache();
+
+    let existing = app.tasks.iter().map(|t| t.id.as_str()).collect();
+    let pruned = app.local_state.prune(&existing);
+    app.save_local_state()?;
+    println!(
+        "Pruned {} local {} for deleted tasks",
+        pruned,
+        if pruned == 1 { "entry" } else { "entries" }
+    );
+    Ok(())
+}
+
+/// Write all tasks (fresh when possible, else cached) to stdout or a file, without the TUI
+///
+/// `offline` exports the cache without trying to fetch.
+async fn export_tasks(
+    config: &Config,
+    profile: Option<&str>,
+    format: export::ExportFormat,
+    out: Option<&std::path::Path>,
+    offline: bool,
+) -> Result<()> {
+    let mut app = App::new();
+    app.set_profile(profile.map(str::to_string));
+    let _ = app.load_cached_tasks();
+
+    // Same policy as startup: fetch if auto_refresh is on or nothing is cached
+    if !offline && (config.auto_refresh || app.tasks.is_empty()) {
+        match fetch_tasks(config, None).await {
+            Ok(tasks) => {
+                app.set_tasks(tasks);
+                let _ = app.save_tasks_cache();
+            }
+            Err(e) if !app.tasks.is_empty() => {
+                eprintln!("Warning: fetch failed ({}), exporting cached tasks", e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let output = export::export(&app.tasks, format)?;
+    match out {
+        Some(path) => {
+            std::fs::write(path, output)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Exported {} tasks to {}", app.tasks.len(), path.display());
+        }
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+/// What triggered a task refresh (picks its status message)
+#[derive(Debug, Clone, Copy)]
+enum RefreshKind {
+    Initial,
+    Manual,
+    Auto,
+    /// Refetch everything rather than only tasks changed since the last fetch
+    Full,
+}
+
+/// A finished background refresh, and whether it only fetched changed tasks
+type RefreshResult = (RefreshKind, bool, Result<Vec<models::Task>>);
+
+/// A task's fetched comments (`None` if they couldn't be loaded)
+type CommentsResult = (String, Option<Vec<models::Comment>>);
+
+/// A task's status history (`None` if the workspace doesn't expose it)
+type HistoryResult = (String, Option<Vec<models::StatusChange>>);
+
+/// A change sent to ClickUp on a background task, with its outcome
+enum ActionResult {
+    /// Whether the running timer stopped, the task a timer started on, and what failed
+    Timer {
+        stopped: bool,
+        started: Option<(String, String)>,
+        error: Option<anyhow::Error>,
+    },
+    /// A comment posted on a task (already shown in its comments)
+    Comment {
+        task_id: String,
+        text: String,
+        result: Result<()>,
+    },
+    /// A checklist item's new state (already applied locally)
+    ChecklistItem {
+        task_id: String,
+        item_id: String,
+        resolved: bool,
+        result: Result<()>,
+    },
+    /// A task's whole subtask tree
+    Subtasks {
+        task_id: String,
+        result: Result<Vec<models::Task>>,
+    },
+    /// A task's new priority (`None` cleared it)
+    Priority {
+        task_id: String,
+        priority: Option<u8>,
+        result: Result<()>,
+    },
+    /// The user added to (or removed from) a task's assignees
+    Assigned {
+        task_id: String,
+        assigned: bool,
+        result: Result<()>,
+    },
+}
+
+/// Apply a finished background change to the app
+fn finish_action(app: &mut App, result: ActionResult) {
+    match result {
+        ActionResult::Timer {
+            stopped,
+            started,
+            error,
+        } => {
+            if stopped {
+                app.finish_timer();
+            }
+            if let Some((id, name)) = started {
+                app.begin_timer(&id, &name);
+            }
+            if let Some(e) = error {
+                app.set_status(format!("Timer failed: {}", e));
+            }
+        }
+        ActionResult::Comment {
+            task_id,
+            text,
+            result,
+        } => match result {
+            Ok(()) => app.set_status("Comment posted"),
+            Err(e) => {
+                app.comment_failed(&task_id, &text);
+                app.set_status(format!("Comment failed: {}", e));
+            }
+        },
+        ActionResult::ChecklistItem {
+            task_id,
+            item_id,
+            resolved,
+            result,
+        } => {
+            if let Err(e) = result {
+                app.set_checklist_item_resolved(&task_id, &item_id, !resolved);
+                app.set_status(format!("Checklist update failed: {}", e));
+            }
+        }
+        ActionResult::Subtasks { task_id, result } => match result {
+            Ok(subtasks) => app.add_subtasks(&task_id, subtasks),
+            Err(e) => app.set_status(format!("Fetching subtasks failed: {}", e)),
+        },
+        ActionResult::Priority {
+            task_id,
+            priority,
+            result,
+        } => match result {
+            Ok(()) => app.apply_priority(&task_id, priority),
+            Err(e) => app.set_status(format!("Priority change failed: {}", e)),
+        },
+        ActionResult::Assigned {
+            task_id,
+            assigned,
+            result,
+        } => match result {
+            Ok(()) => app.apply_assignment(&task_id, assigned),
+            Err(e) => app.set_status(format!("Assignee change failed: {}", e)),
+        },
+    }
+}
+
+/// Fetch tasks on a background task, reporting back through `tx`
+///
+/// Only tasks updated since the last fetch are requested, unless this is a
+/// full refresh, there's nothing to merge into, or it's time to reconcile the
+/// whole set. Does nothing if a refresh is
+/// already in flight.
+fn spawn_refresh(
+    app: &mut App,
+    config: &Config,
+    kind: RefreshKind,
+    tx: &mpsc::UnboundedSender<RefreshResult>,
+) {
+    if app.offline {
+        app.set_status("Offline mode");
+        return;
+    }
+    if !app.start_loading() {
+        return;
+    }
+    let updated_after = match kind {
+        RefreshKind::Full => None,
+        _ => app.refresh_cursor(Instant::now()),
+    };
+    let config = config.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = fetch_tasks(&config, updated_after).await;
+        let _ = tx.send((kind, updated_after.is_some(), result));
+    });
+}
+
+/// Apply a finished refresh to the app
+fn finish_refresh(app: &mut App, (kind, incremental, result): RefreshResult) {
+    app.finish_loading();
+    match result {
+        Ok(tasks) => {
+            // Summarize changes, unless there was nothing to compare against
+            let had_tasks = !app.tasks.is_empty();
+            let assigned_before = app.assigned_task_ids();
+            let diff = if incremental {
+                app.merge_tasks(tasks)
+            } else {
+                app.mark_full_refresh(Instant::now());
+                app.set_tasks(tasks)
+            };
+            let changes = diff.summary().filter(|_| had_tasks);
+            app.comments.clear();
+            app.status_history.clear();
+            let count = app.tasks.len();
+            app.set_status(match (kind, changes) {
+                (RefreshKind::Auto, Some(changes)) => format!("Auto-refreshed: {}", changes),
+                (RefreshKind::Auto, None) => format!("Auto-refreshed {} tasks", count),
+                (_, Some(changes)) => format!("Refreshed: {}", changes),
+                (_, None) => format!("Loaded {} tasks", count),
+            });
+            if had_tasks {
+                app.triage_new_assignments(&assigned_before);
+            }
+            let _ = app.save_tasks_cache();
+            let _ = app.save_local_state();
+        }
+        Err(e) => app.set_status(match kind {
+            // Say whether what's on screen is the cache or nothing at all
+            RefreshKind::Initial if app.tasks.is_empty() => {
+                format!("No tasks (refresh failed: {})", e)
+            }
+            RefreshKind::Initial => format!(
+                "Showing {} cached tasks (refresh failed: {})",
+                app.tasks.len(),
+                e
+            ),
+            RefreshKind::Manual | RefreshKind::Full => format!("Failed: {}", e),
+            RefreshKind::Auto => format!("Auto-refresh failed: {}", e),
+        }),
+    }
+}
+
+/// Apply the config's display and behavior settings to the app (at startup and
+/// on reload)
+fn apply_config(app: &mut App, config: &Config) -> Result<()> {
+    app.set_status_groups(config.status_group_overrides()?);
+    app.set_confirm_actions(config.confirm_actions);
+    app.set_status_timeout(config.status_timeout());
+    app.set_done_recent_days(config.done_recent_days());
+    app.set_show_unassigned_ancestors(config.show_unassigned_ancestors);
+    app.set_pin_to_top(config.pin_to_top);
+    app.set_backlog_age(config.backlog()?);
+    app.set_hide_done_subtasks(config.hide_done_subtasks);
+    app.set_dense(config.dense);
+    app.set_color_by_list(config.color_by_list);
+    app.set_preview_max_lines(config.preview_max_lines);
+    app.set_clipboard_template(config.clipboard_template.clone());
+    app.set_visible_groups(config.visible_groups()?);
+    app.set_group_sorts(config.group_sorts()?);
+    app.set_keymap(config.keymap()?);
+    app.set_theme(config.theme()?);
+    Ok(())
+}
+
+/// Re-read the config file and apply what can change while running
+///
+/// Connection settings (token, user ID, workspace, API root, refresh interval)
+/// keep their current values until restart; the status says when one changed.
+fn reload_config(app: &mut App, config: &mut Config) {
+    let mut reloaded = match Config::reload(app.profile.as_deref()) {
+        Ok(c) => c,
+        Err(e) => {
+            app.set_status(format!("Config not reloaded: {:#}", e));
+            return;
+        }
+    };
+    if let Err(e) = apply_config(app, &reloaded) {
+        app.set_status(format!("Config not reloaded: {:#}", e));
+        return;
+    }
+
+    // The token command isn't re-run, so only a changed command (or, without one,
+    // a changed token) says the token changed
+    let token_changed = reloaded.api_token_command != config.api_token_command
+        || (reloaded.api_token_command.is_none() && reloaded.api_token != config.api_token);
+    let needs_restart = token_changed
+        || reloaded.user_id != config.user_id
+        || reloaded.workspace_id != config.workspace_id
+        || reloaded.api_base != config.api_base
+        || reloaded.refresh_interval_secs != config.refresh_interval_secs;
+    reloaded.api_token = std::mem::take(&mut config.api_token);
+    reloaded.api_token_command = config.api_token_command.take();
+    reloaded.user_id = std::mem::take(&mut config.user_id);
+    reloaded.workspace_id = config.workspace_id.take();
+    reloaded.api_base = config.api_base.take();
+    reloaded.refresh_interval_secs = config.refresh_interval_secs;
+    *config = reloaded;
+
+    app.set_status(if needs_restart {
+        "Config reloaded (restart to apply connection settings)"
+    } else {
+        "Config reloaded"
+    });
+}
+
 /// Main event loop
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
-    config: &Config,
+    config: &mut Config,
+    initial_refresh: bool,
 ) -> Result<()> {
+    // Fetches run in the background so the UI keeps drawing (and animating) meanwhile
+    let (refresh_tx, mut refresh_rx) = mpsc::unbounded_channel::<RefreshResult>();
+    let (comments_tx, mut comments_rx) = mpsc::unbounded_channel::<CommentsResult>();
+    let (history_tx, mut history_rx) = mpsc::unbounded_channel::<HistoryResult>();
+    let (action_tx, mut action_rx) = mpsc::unbounded_channel::<ActionResult>();
+    if initial_refresh {
+        spawn_refresh(app, config, RefreshKind::Initial, &refresh_tx);
+    }
+
+    // Overdue checks for desktop notifications (the first tick is immediate)
+    let mut overdue_timer = config.notifications.then(|| {
+        let mut timer = tokio::time::interval(OVERDUE_CHECK_INTERVAL);
+        timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        timer
+    });
+
+    // Background polling timer (first tick is one full interval from now; none offline)
+    let mut refresh_timer = config
+        .refresh_interval()
+        .filter(|_| !app.offline)
+        .map(|period| {
+            let mut timer = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            timer
+        });
+
     loop {
+        app.expire_status(Instant::now());
+        app.expire_count(Instant::now());
         terminal.draw(|f| ui::render(f, app))?;
 
+        // Swap in refreshed tasks, but never mid-input (it would move the selection)
+        if app.input_mode == InputMode::Normal {
+            if let Ok(refresh) = refresh_rx.try_recv() {
+                finish_refresh(app, refresh);
+                continue;
+            }
+            // Auto-refresh quietly in the background
+            if timer_due(&mut refresh_timer).await {
+                spawn_refresh(app, config, RefreshKind::Auto, &refresh_tx);
+            }
+        }
+
+        // Announce tasks that have become overdue since the last check
+        if timer_due(&mut overdue_timer).await {
+            notify_overdue(app.take_newly_overdue(chrono::Utc::now()));
+        }
+
+        // Changes sent to ClickUp report back here
+        if let Ok(result) = action_rx.try_recv() {
+            finish_action(app, result);
+            continue;
+        }
+
+        // Load comments and status history for the previewed task once the selection settles
+        if let Ok((task_id, comments)) = comments_rx.try_recv() {
+            app.set_comments(&task_id, comments);
+            continue;
+        }
+        if let Ok((task_id, history)) = history_rx.try_recv() {
+            app.set_status_history(&task_id, history);
+            continue;
+        }
+        if let Some(task_id) = app.comments_due(Instant::now()) {
+            let config = config.clone();
+            let tx = comments_tx.clone();
+            let history_tx = history_tx.clone();
+            tokio::spawn(async move {
+                let history = fetch_task_history(&config, &task_id).await.ok();
+                let _ = history_tx.send((task_id.clone(), history));
+                let comments = fetch_comments(&config, &task_id).await.ok();
+                let _ = tx.send((task_id, comments));
+            });
+        }
+
         // Poll for events with timeout to allow status message clearing
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+
+            // Mouse: click to select/focus, wheel to move (Normal mode only)
+            if let Event::Mouse(mouse) = event {
+                if app.input_mode == InputMode::Normal {
+                    match mouse.kind {
+                        MouseEventKind::Down(MouseButton::Left) => {
+                            app.click(mouse.column, mouse.row);
+                        }
+                        MouseEventKind::ScrollDown => {
+                            app.scroll_wheel(mouse.column, mouse.row, true);
+                        }
+                        MouseEventKind::ScrollUp => {
+                            app.scroll_wheel(mouse.column, mouse.row, false);
+                        }
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+
+            if let Event::Key(key) = event {
                 // Only handle key press events (not release)
                 if key.kind != KeyEventKind::Press {
                     continue;
                 }
 
-                // Clear status message on any key press
-                app.clear_status();
+                // Set by keys that need the network; run once the key is handled
+                let mut command = None;
 
                 match app.input_mode {
                     InputMode::Normal => {
-                        match key.code {
-                            KeyCode::Char('q') => {
-                                app.should_quit = true;
-                            }
-                            KeyCode::Char('j') | KeyCode::Down => match app.focused_pane {
-                                FocusedPane::TaskList => {
-                                    app.select_next();
-                                    app.reset_preview_scroll();
-                                }
-                                FocusedPane::Preview => app.scroll_preview_down(),
-                            },
-                            KeyCode::Char('k') | KeyCode::Up => match app.focused_pane {
-                                FocusedPane::TaskList => {
-                                    app.select_prev();
-                                    app.reset_preview_scroll();
-                                }
-                                FocusedPane::Preview => app.scroll_preview_up(),
-                            },
-                            KeyCode::Char('1') => {
-                                app.switch_group(TaskGroup::MyAction);
-                            }
-                            KeyCode::Char('2') => {
-                                app.switch_group(TaskGroup::Waiting);
-                            }
-                            KeyCode::Char('3') => {
-                                app.switch_group(TaskGroup::Backlog);
-                            }
-                            KeyCode::Char('4') => {
-                                app.switch_group(TaskGroup::Done);
-                            }
-                            KeyCode::Char('5') => {
-                                app.switch_group(TaskGroup::Snoozed);
-                            }
-                            KeyCode::Char('6') => {
-                                app.switch_group(TaskGroup::Person);
-                            }
-                            KeyCode::Tab => {
-                                app.focus_next_pane();
-                            }
-                            KeyCode::BackTab => {
-                                app.focus_prev_pane();
-                            }
-                            KeyCode::Char('l') => {
-                                app.next_tab();
-                            }
-                            KeyCode::Char('h') => {
-                                app.prev_tab();
-                            }
-                            KeyCode::Char('p') => {
-                                app.toggle_pin();
-                            }
-                            KeyCode::Char('s') => {
-                                app.start_snooze();
-                            }
-                            KeyCode::Char('S') => {
-                                app.unsnooze();
-                            }
-                            KeyCode::Char('o') | KeyCode::Enter => {
-                                app.open_in_browser();
-                            }
-                            KeyCode::Char('y') => {
-                                app.copy_to_clipboard();
-                            }
-                            KeyCode::Char('/') => {
-                                app.start_search();
-                            }
-                            KeyCode::Char('r') => {
-                                // Refresh tasks
-                                app.is_loading = true;
-                                app.status_message = Some("Refreshing...".to_string());
-                                terminal.draw(|f| ui::render(f, app))?;
-
-                                match fetch_tasks(config).await {
-                                    Ok(tasks) => {
-                                        app.set_tasks(tasks);
-                                        app.is_loading = false;
-                                        app.status_message =
-                                            Some(format!("Loaded {} tasks", app.tasks.len()));
-                                        let _ = app.save_tasks_cache();
-                                        let _ = app.save_local_state();
-                                    }
-                                    Err(e) => {
-                                        app.is_loading = false;
-                                        app.status_message = Some(format!("Failed: {}", e));
-                                    }
-                                }
-                            }
-                            KeyCode::Char('?') => {
-                                app.show_help = true;
-                                app.input_mode = InputMode::Help;
-                            }
-                            _ => {}
-                        }
+                        command = app.handle_key(key);
                     }
                     InputMode::Search => match key.code {
                         KeyCode::Esc => {
                             app.cancel_input();
                         }
                         KeyCode::Enter => {
-                            // Open selected search result in browser
-                            if let Some(task) = app.selected_search_result() {
-                                if let Err(e) = open::that(&task.task.url) {
-                                    app.status_message = Some(format!("Failed to open: {}", e));
-                                } else {
-                                    app.status_message = Some("Opened in browser".to_string());
-                                }
-                            }
-                            app.input_mode = InputMode::Normal;
+                            app.open_search_result();
                         }
                         KeyCode::Down | KeyCode::Char('j')
                             if key.modifiers.is_empty() || app.search_query.is_empty() =>
@@ -272,14 +868,180 @@ async fn run_app(
                         }
                         _ => {}
                     },
+                    InputMode::Note => match key.code {
+                        KeyCode::Esc => {
+                            app.cancel_input();
+                        }
+                        // Alt+Enter starts a new line; Enter saves
+                        KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
+                            app.handle_char('\n');
+                        }
+                        KeyCode::Enter => {
+                            app.confirm_note();
+                        }
+                        KeyCode::Backspace => {
+                            app.handle_backspace();
+                        }
+                        KeyCode::Char(c) => {
+                            app.handle_char(c);
+                        }
+                        _ => {}
+                    },
+                    InputMode::WaitingReason => match key.code {
+                        KeyCode::Esc => {
+                            app.cancel_input();
+                        }
+                        KeyCode::Enter => {
+                            app.confirm_waiting_reason();
+                        }
+                        KeyCode::Backspace => {
+                            app.handle_backspace();
+                        }
+                        KeyCode::Char(c) => {
+                            app.handle_char(c);
+                        }
+                        _ => {}
+                    },
+                    InputMode::Comment => match key.code {
+                        KeyCode::Esc => {
+                            app.cancel_input();
+                        }
+                        // Alt+Enter starts a new line; Enter posts
+                        KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
+                            app.handle_char('\n');
+                        }
+                        KeyCode::Enter => {
+                            if let Some((task_id, text)) = app.submit_comment() {
+                                let config = config.clone();
+                                let tx = action_tx.clone();
+                                tokio::spawn(async move {
+                                    let result = post_comment(&config, &task_id, &text).await;
+                                    let _ = tx.send(ActionResult::Comment {
+                                        task_id,
+                                        text,
+                                        result,
+                                    });
+                                });
+                            }
+                        }
+                        KeyCode::Backspace => {
+                            app.handle_backspace();
+                        }
+                        KeyCode::Char(c) => {
+                            app.handle_char(c);
+                        }
+                        _ => {}
+                    },
+                    InputMode::TagFilter => match key.code {
+                        KeyCode::Esc => {
+                            app.cancel_input();
+                        }
+                        KeyCode::Enter => {
+                            app.confirm_tag_filter();
+                        }
+                        KeyCode::Backspace => {
+                            app.handle_backspace();
+                        }
+                        KeyCode::Char(c) => {
+                            app.handle_char(c);
+                        }
+                        _ => {}
+                    },
+                    InputMode::JumpToId => match key.code {
+                        KeyCode::Esc => {
+                            app.cancel_input();
+                        }
+                        KeyCode::Enter => {
+                            app.confirm_jump_to_id();
+                        }
+                        KeyCode::Backspace => {
+                            app.handle_backspace();
+                        }
+                        KeyCode::Char(c) => {
+                            app.handle_char(c);
+                        }
+                        _ => {}
+                    },
+                    InputMode::ListPicker => match key.code {
+                        KeyCode::Esc => {
+                            app.cancel_input();
+                        }
+                        KeyCode::Enter => {
+                            app.confirm_list_picker();
+                        }
+                        KeyCode::Down => {
+                            app.list_picker_next();
+                        }
+                        KeyCode::Up => {
+                            app.list_picker_prev();
+                        }
+                        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.list_picker_next();
+                        }
+                        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.list_picker_prev();
+                        }
+                        KeyCode::Backspace => {
+                            app.handle_backspace();
+                        }
+                        KeyCode::Char(c) => {
+                            app.handle_char(c);
+                        }
+                        _ => {}
+                    },
+                    InputMode::PriorityPicker => match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => {
+                            app.cancel_input();
+                        }
+                        KeyCode::Enter => {
+                            command = app.confirm_priority_picker();
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            app.priority_picker_next();
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.priority_picker_prev();
+                        }
+                        KeyCode::Char(c) => {
+                            command = app.priority_picker_digit(c);
+                        }
+                        _ => {}
+                    },
+                    InputMode::Confirm => match key.code {
+                        KeyCode::Char('y') | KeyCode::Enter => {
+                            command = app.confirm_action();
+                        }
+                        KeyCode::Char('n') | KeyCode::Esc => {
+                            app.cancel_action();
+                        }
+                        _ => {}
+                    },
+                    InputMode::QrCode => match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+                            app.input_mode = InputMode::Normal;
+                        }
+                        _ => {}
+                    },
+                    InputMode::MessageLog => match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+                            app.input_mode = InputMode::Normal;
+                        }
+                        _ => {}
+                    },
                     InputMode::Help => match key.code {
                         KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
                             app.show_help = false;
                             app.input_mode = InputMode::Normal;
                         }
+                        KeyCode::Char('j') | KeyCode::Down => app.scroll_help(1),
+                        KeyCode::Char('k') | KeyCode::Up => app.scroll_help(-1),
                         _ => {}
                     },
                 }
+
+                if let Some(command) = command {
+                    run_command(app, config, command, &refresh_tx, &action_tx);
+                }
             }
         }
 
@@ -291,9 +1053,224 @@ async fn run_app(
     Ok(())
 }
 
-/// Fetch tasks from ClickUp API
-async fn fetch_tasks(config: &Config) -> Result<Vec<models::Task>> {
-    let client = api::ClickUpClient::new(config.api_token.clone());
-    let team_id = client.get_team_id().await?;
-    client.fetch_tasks(&team_id, &config.user_id).await
+/// Carry out a command from a key press; anything that talks to ClickUp runs on
+/// a background task and reports back through `action_tx`
+fn run_command(
+    app: &mut App,
+    config: &mut Config,
+    command: Command,
+    refresh_tx: &mpsc::UnboundedSender<RefreshResult>,
+    action_tx: &mpsc::UnboundedSender<ActionResult>,
+) {
+    match command {
+        Command::Refresh => {
+            spawn_refresh(app, config, RefreshKind::Manual, refresh_tx);
+        }
+        Command::FullRefresh => {
+            spawn_refresh(app, config, RefreshKind::Full, refresh_tx);
+        }
+        Command::ToggleTimer => {
+            spawn_toggle_timer(app, config, action_tx);
+        }
+        Command::ReloadConfig => {
+            reload_config(app, config);
+        }
+        Command::SetChecklistItem {
+            task_id,
+            checklist_id,
+            item_id,
+            resolved,
+        } => {
+            let config = config.clone();
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let result = set_checklist_item(&config, &checklist_id, &item_id, resolved).await;
+                let _ = tx.send(ActionResult::ChecklistItem {
+                    task_id,
+                    item_id,
+                    resolved,
+                    result,
+                });
+            });
+        }
+        Command::FetchSubtasks { task_id } => {
+            let config = config.clone();
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let result = fetch_subtasks(&config, &task_id).await;
+                let _ = tx.send(ActionResult::Subtasks { task_id, result });
+            });
+        }
+        Command::SetPriority { task_id, priority } => {
+            let config = config.clone();
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let result = set_priority(&config, &task_id, priority).await;
+                let _ = tx.send(ActionResult::Priority {
+                    task_id,
+                    priority,
+                    result,
+                });
+            });
+        }
+        Command::SetAssigned { task_id, assigned } => {
+            // set_assigned_to_me refuses without a user ID, but a config reload
+            // while the dialog was open could have cleared it
+            let Some(user_id) = app.user_id else {
+                app.set_status("Set a numeric user_id to change assignees");
+                return;
+            };
+            let config = config.clone();
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let result = set_assigned(&config, &task_id, user_id, assigned).await;
+                let _ = tx.send(ActionResult::Assigned {
+                    task_id,
+                    assigned,
+                    result,
+                });
+            });
+        }
+    }
+}
+
+/// Non-blocking check whether a periodic timer (if enabled) has ticked
+async fn timer_due(timer: &mut Option<Interval>) -> bool {
+    match timer {
+        Some(timer) => tokio::time::timeout(Duration::ZERO, timer.tick())
+            .await
+            .is_ok(),
+        None => false,
+    }
+}
+
+/// Show a desktop notification for each newly overdue task (best effort, off the UI thread)
+fn notify_overdue(tasks: Vec<models::Task>) {
+    if tasks.is_empty() {
+        return;
+    }
+    tokio::task::spawn_blocking(move || {
+        for task in tasks {
+            let _ = notify_rust::Notification::new()
+                .appname("clickup-tui")
+                .summary("Task overdue")
+                .body(&task.name)
+                .show();
+        }
+    });
+}
+
+/// Stop the running timer and start one on the selected task unless it was the
+/// one running, on a background task reporting back through `tx`
+fn spawn_toggle_timer(app: &mut App, config: &Config, tx: &mpsc::UnboundedSender<ActionResult>) {
+    let running = app.timer.as_ref().map(|t| t.task_id.clone());
+    let start = app
+        .selected_task()
+        .map(|dt| (dt.task.id, dt.task.name))
+        .filter(|(id, _)| running.as_deref() != Some(id.as_str()));
+    app.set_status("Updating timer...");
+    let config = config.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let result = toggle_timer(&config, running.is_some(), start).await;
+        let _ = tx.send(result);
+    });
+}
+
+/// Stop the running timer (if `stop`) and start one on `start` via ClickUp API
+async fn toggle_timer(
+    config: &Config,
+    stop: bool,
+    start: Option<(String, String)>,
+) -> ActionResult {
+    let mut stopped = false;
+    let result: Result<()> = async {
+        let client = api_client(config)?;
+        let team_id = client.get_team_id(config.workspace_id.as_deref()).await?;
+        if stop {
+            client.stop_timer(&team_id).await?;
+            stopped = true;
+        }
+        if let Some((id, _)) = &start {
+            client.start_timer(&team_id, id).await?;
+        }
+        Ok(())
+    }
+    .await;
+    ActionResult::Timer {
+        stopped,
+        started: start.filter(|_| result.is_ok()),
+        error: result.err(),
+    }
+}
+
+/// Build an API client from the config (token, timeout, and base URL)
+fn api_client(config: &Config) -> Result<api::ClickUpClient> {
+    let client = api::ClickUpClient::new(config.api_token.clone(), config.request_timeout())?;
+    Ok(match &config.api_base {
+        Some(base) => client.with_base_url(base),
+        None => client,
+    })
+}
+
+/// Fetch a task's status history from ClickUp API
+async fn fetch_task_history(config: &Config, task_id: &str) -> Result<Vec<models::StatusChange>> {
+    let client = api_client(config)?;
+    client.fetch_task_history(task_id).await
+}
+
+/// Fetch a task's comments from ClickUp API
+async fn fetch_comments(config: &Config, task_id: &str) -> Result<Vec<models::Comment>> {
+    let client = api_client(config)?;
+    client.fetch_comments(task_id).await
+}
+
+/// Check off (or un-check) a checklist item via ClickUp API
+async fn set_checklist_item(
+    config: &Config,
+    checklist_id: &str,
+    item_id: &str,
+    resolved: bool,
+) -> Result<()> {
+    let client = api_client(config)?;
+    client
+        .set_checklist_item(checklist_id, item_id, resolved)
+        .await
+}
+
+/// Fetch a task's whole subtask tree from ClickUp API
+async fn fetch_subtasks(config: &Config, task_id: &str) -> Result<Vec<models::Task>> {
+    let client = api_client(config)?;
+    client.fetch_subtasks(task_id).await
+}
+
+/// Set a task's priority via ClickUp API
+async fn set_priority(config: &Config, task_id: &str, priority: Option<u8>) -> Result<()> {
+    let client = api_client(config)?;
+    client.set_priority(task_id, priority).await
+}
+
+/// Add or remove the user as an assignee via ClickUp API
+async fn set_assigned(config: &Config, task_id: &str, user_id: u64, assigned: bool) -> Result<()> {
+    let client = api_client(config)?;
+    if assigned {
+        client.update_assignees(task_id, &[user_id], &[]).await
+    } else {
+        client.update_assignees(task_id, &[], &[user_id]).await
+    }
+}
+
+/// Post a comment on a task via ClickUp API
+async fn post_comment(config: &Config, task_id: &str, text: &str) -> Result<()> {
+    let client = api_client(config)?;
+    client.post_comment(task_id, text).await
+}
+
+/// Fetch tasks from ClickUp API (only those updated after `updated_after` ms, if given)
+async fn fetch_tasks(config: &Config, updated_after: Option<i64>) -> Result<Vec<models::Task>> {
+    let client = api_client(config)?;
+    let team_id = client.get_team_id(config.workspace_id.as_deref()).await?;
+    client
+        .fetch_tasks(&team_id, &config.user_id, updated_after)
+        .await
 }
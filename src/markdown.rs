@@ -0,0 +1,212 @@
+//! Lightweight Markdown rendering for task descriptions
+//!
+//! Converts the subset of Markdown that shows up in ClickUp descriptions
+//! (headings, bullet lists, inline bold/italic/code, fenced code blocks)
+//! into styled ratatui lines. Anything unrecognized is passed through as text.
+
+use crate::theme::Theme;
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Render Markdown text into styled lines
+pub fn render(text: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for raw in text.lines() {
+        let trimmed = raw.trim_start();
+
+        // Fenced code blocks: toggle on ``` and render contents verbatim
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(Line::from(Span::styled(raw.to_string(), code_style(theme))));
+            continue;
+        }
+
+        if let Some((level, heading)) = parse_heading(trimmed) {
+            let style = Style::default().fg(theme.blue).add_modifier(Modifier::BOLD);
+            let style = if level == 1 {
+                style.add_modifier(Modifier::UNDERLINED)
+            } else {
+                style
+            };
+            lines.push(Line::from(Span::styled(heading.to_string(), style)));
+            continue;
+        }
+
+        if let Some(item) = parse_bullet(trimmed) {
+            let indent = " ".repeat(raw.len() - trimmed.len());
+            let mut spans = vec![Span::styled(
+                format!("{}• ", indent),
+                Style::default().fg(theme.cyan),
+            )];
+            spans.extend(render_inline(item, theme));
+            lines.push(Line::from(spans));
+            continue;
+        }
+
+        lines.push(Line::from(render_inline(raw, theme)));
+    }
+
+    lines
+}
+
+/// Style for code blocks and inline code
+fn code_style(theme: &Theme) -> Style {
+    Style::default().fg(theme.fg).bg(theme.muted)
+}
+
+/// Parse an ATX heading (`# Title`), returning its level and text
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if rest.is_empty() {
+        return Some((level, ""));
+    }
+    rest.strip_prefix(' ')
+        .map(|text| (level, text.trim_end_matches('#').trim()))
+}
+
+/// Parse a bullet list item (`- item`, `* item`, `+ item`)
+fn parse_bullet(line: &str) -> Option<&str> {
+    ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+}
+
+/// Render inline emphasis (`**bold**`, `*italic*`, `_italic_`, `` `code` ``)
+fn render_inline(text: &str, theme: &Theme) -> Vec<Span<'static>> {
+    let base = Style::default().fg(theme.fg);
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let rest = &chars[i..];
+        let emphasis = if rest.starts_with(&['*', '*']) || rest.starts_with(&['_', '_']) {
+            find_closing(&chars, i + 2, &rest[..2])
+                .map(|end| (i + 2, end, end + 2, base.add_modifier(Modifier::BOLD)))
+        } else if rest[0] == '`' {
+            find_closing(&chars, i + 1, &['`']).map(|end| (i + 1, end, end + 1, code_style(theme)))
+        } else if (rest[0] == '*' || rest[0] == '_') && is_word_start(&chars, i) {
+            find_closing(&chars, i + 1, &rest[..1])
+                .map(|end| (i + 1, end, end + 1, base.add_modifier(Modifier::ITALIC)))
+        } else {
+            None
+        };
+
+        match emphasis {
+            Some((start, end, next, style)) if end > start => {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), base));
+                }
+                spans.push(Span::styled(
+                    chars[start..end].iter().collect::<String>(),
+                    style,
+                ));
+                i = next;
+            }
+            _ => {
+                plain.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+
+    if !plain.is_empty() || spans.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+/// Find the next occurrence of `marker` at or after `from`
+fn find_closing(chars: &[char], from: usize, marker: &[char]) -> Option<usize> {
+    (from..chars.len()).find(|&idx| chars[idx..].starts_with(marker))
+}
+
+/// Whether position `idx` starts a word (so `snake_case` isn't treated as italic)
+fn is_word_start(chars: &[char], idx: usize) -> bool {
+    idx == 0 || !chars[idx - 1].is_alphanumeric()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme;
+
+    fn span_texts(line: &Line) -> Vec<String> {
+        line.spans.iter().map(|s| s.content.to_string()).collect()
+    }
+
+    #[test]
+    fn test_heading() {
+        let lines = render("## Steps to reproduce", &Theme::dark());
+        assert_eq!(span_texts(&lines[0]), vec!["Steps to reproduce"]);
+        assert_eq!(lines[0].spans[0].style.fg, Some(theme::BLUE));
+        assert!(lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_bullets() {
+        let lines = render("- first\n  * nested **item**", &Theme::dark());
+        assert_eq!(span_texts(&lines[0]), vec!["• ", "first"]);
+        assert_eq!(span_texts(&lines[1]), vec!["  • ", "nested ", "item"]);
+        assert!(lines[1].spans[2]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_inline_emphasis() {
+        let lines = render("a **bold** and *italic* with `code`", &Theme::dark());
+        let spans = &lines[0].spans;
+        assert_eq!(
+            span_texts(&lines[0]),
+            vec!["a ", "bold", " and ", "italic", " with ", "code"]
+        );
+        assert!(spans[1].style.add_modifier.contains(Modifier::BOLD));
+        assert!(spans[3].style.add_modifier.contains(Modifier::ITALIC));
+        assert_eq!(spans[5].style.bg, Some(theme::MUTED));
+    }
+
+    #[test]
+    fn test_snake_case_and_unclosed_markers_pass_through() {
+        let lines = render("use snake_case_names and a lone * star", &Theme::dark());
+        assert_eq!(
+            span_texts(&lines[0]),
+            vec!["use snake_case_names and a lone * star"]
+        );
+    }
+
+    #[test]
+    fn test_code_block() {
+        let lines = render(
+            "before\n```rust\nlet x = **1**;\n```\nafter",
+            &Theme::dark(),
+        );
+        assert_eq!(lines.len(), 3);
+        assert_eq!(span_texts(&lines[1]), vec!["let x = **1**;"]);
+        assert_eq!(lines[1].spans[0].style.bg, Some(theme::MUTED));
+        assert_eq!(span_texts(&lines[2]), vec!["after"]);
+    }
+
+    #[test]
+    fn test_empty_line() {
+        let lines = render("one\n\ntwo", &Theme::dark());
+        assert_eq!(lines.len(), 3);
+        assert_eq!(span_texts(&lines[1]), vec![""]);
+    }
+}
@@ -1,11 +1,12 @@
 //! Data models for tasks and local state
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Task group based on responsibility
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TaskGroup {
     #[default]
     MyAction,
@@ -15,6 +16,10 @@ pub enum TaskGroup {
     Snoozed,
     /// Long-standing role/person type tasks (custom_item_id = 1020)
     Person,
+    /// Due today or overdue, regardless of status (excludes done and snoozed)
+    Today,
+    /// Open tasks the user watches but isn't assigned to
+    Watching,
 }
 
 impl TaskGroup {
@@ -26,6 +31,8 @@ impl TaskGroup {
             TaskGroup::Done,
             TaskGroup::Snoozed,
             TaskGroup::Person,
+            TaskGroup::Today,
+            TaskGroup::Watching,
         ]
     }
 
@@ -37,36 +44,41 @@ impl TaskGroup {
             TaskGroup::Done => "Done",
             TaskGroup::Snoozed => "Snoozed",
             TaskGroup::Person => "Person",
+            TaskGroup::Today => "Today",
+            TaskGroup::Watching => "Watching",
         }
     }
 
-    pub fn index(&self) -> usize {
-        match self {
-            TaskGroup::MyAction => 0,
-            TaskGroup::Waiting => 1,
-            TaskGroup::Backlog => 2,
-            TaskGroup::Done => 3,
-            TaskGroup::Snoozed => 4,
-            TaskGroup::Person => 5,
-        }
-    }
-
-    pub fn from_index(idx: usize) -> Option<TaskGroup> {
-        match idx {
-            0 => Some(TaskGroup::MyAction),
-            1 => Some(TaskGroup::Waiting),
-            2 => Some(TaskGroup::Backlog),
-            3 => Some(TaskGroup::Done),
-            4 => Some(TaskGroup::Snoozed),
-            5 => Some(TaskGroup::Person),
+    /// Parse a group from a config name (e.g. "my_action", "My Action", "waiting")
+    pub fn from_name(name: &str) -> Option<TaskGroup> {
+        let normalized: String = name
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "myaction" | "action" => Some(TaskGroup::MyAction),
+            "waiting" => Some(TaskGroup::Waiting),
+            "backlog" => Some(TaskGroup::Backlog),
+            "done" => Some(TaskGroup::Done),
+            "snoozed" => Some(TaskGroup::Snoozed),
+            "person" => Some(TaskGroup::Person),
+            "today" => Some(TaskGroup::Today),
+            "watching" | "watched" => Some(TaskGroup::Watching),
             _ => None,
         }
     }
 }
 
-/// Map ClickUp status to task group
-pub fn status_to_group(status: &str) -> TaskGroup {
+/// User-configured status overrides, keyed by lowercased status name
+pub type StatusGroups = HashMap<String, TaskGroup>;
+
+/// Map ClickUp status to task group, consulting user overrides first
+pub fn status_to_group(status: &str, overrides: &StatusGroups) -> TaskGroup {
     let status_lower = status.to_lowercase();
+    if let Some(&group) = overrides.get(&status_lower) {
+        return group;
+    }
     match status_lower.as_str() {
         // My Action - I need to do something
         "in progress" | "to do" | "to-do" | "todo" => TaskGroup::MyAction,
@@ -91,6 +103,98 @@ pub fn status_to_group(status: &str) -> TaskGroup {
     }
 }
 
+/// A person assigned to a task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Assignee {
+    /// ClickUp user ID
+    pub id: u64,
+    /// Display name
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Initials as provided by ClickUp (e.g., "AB")
+    #[serde(default)]
+    pub initials: Option<String>,
+}
+
+impl Assignee {
+    /// Short label for badges: ClickUp's initials, else derived from the username
+    pub fn initials(&self) -> String {
+        if let Some(initials) = self.initials.as_deref().filter(|i| !i.is_empty()) {
+            return initials.to_uppercase();
+        }
+        match self.username.as_deref() {
+            Some(name) if !name.trim().is_empty() => name
+                .split_whitespace()
+                .filter_map(|word| word.chars().next())
+                .take(2)
+                .flat_map(char::to_uppercase)
+                .collect(),
+            _ => "?".to_string(),
+        }
+    }
+}
+
+/// A checklist on a task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checklist {
+    /// ClickUp checklist ID (empty in caches from before checklists could be edited)
+    #[serde(default)]
+    pub id: String,
+    /// Checklist name (ClickUp's default is "Checklist")
+    pub name: String,
+    /// Items in display order
+    #[serde(default)]
+    pub items: Vec<ChecklistItem>,
+}
+
+/// One item on a task checklist
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    /// ClickUp checklist item ID (empty in older caches)
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// Whether the item is checked off
+    #[serde(default)]
+    pub resolved: bool,
+}
+
+impl Checklist {
+    /// Number of checked-off items
+    pub fn resolved_count(&self) -> usize {
+        self.items.iter().filter(|i| i.resolved).count()
+    }
+}
+
+/// A comment on a task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    /// Author's display name
+    pub author: String,
+    /// Comment body (plain text)
+    pub text: String,
+}
+
+/// A status a task entered, from its status history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusChange {
+    /// Status name
+    pub status: String,
+    /// When the task entered the status (ms since epoch), if known
+    pub since: Option<i64>,
+}
+
+/// Name of a ClickUp priority (1=Urgent .. 4=Low)
+pub fn priority_label(priority: Option<u8>) -> Option<&'static str> {
+    match priority {
+        Some(1) => Some("Urgent"),
+        Some(2) => Some("High"),
+        Some(3) => Some("Normal"),
+        Some(4) => Some("Low"),
+        _ => None,
+    }
+}
+
 /// A task from ClickUp with local overlay data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -102,8 +206,20 @@ pub struct Task {
     pub status: String,
     /// List name the task belongs to
     pub list_name: String,
+    /// ID of the list the task belongs to
+    #[serde(default)]
+    pub list_id: Option<String>,
+    /// Workspace (team) ID, for building list URLs
+    #[serde(default)]
+    pub team_id: Option<String>,
     /// Due date (Unix timestamp in ms)
     pub due_date: Option<i64>,
+    /// Creation time (Unix timestamp in ms)
+    #[serde(default)]
+    pub date_created: Option<i64>,
+    /// Last update time (Unix timestamp in ms)
+    #[serde(default)]
+    pub date_updated: Option<i64>,
     /// Priority (1=Urgent, 2=High, 3=Normal, 4=Low)
     pub priority: Option<u8>,
     /// URL to open in browser
@@ -123,26 +239,78 @@ pub struct Task {
     /// Parent task ID (if this is a subtask)
     #[serde(default)]
     pub parent_id: Option<String>,
+    /// User linked to a Person task (from a "users" custom field)
+    #[serde(default)]
+    pub person_id: Option<u64>,
+    /// Non-empty custom fields as (name, display value), e.g. ("Story Points", "3")
+    #[serde(default)]
+    pub custom_fields: Vec<(String, String)>,
     /// Assignee user IDs
     #[serde(default)]
     pub assignee_ids: Vec<u64>,
+    /// Watcher (follower) user IDs
+    #[serde(default)]
+    pub watcher_ids: Vec<u64>,
+    /// Assignee details (names/initials) for display
+    #[serde(default)]
+    pub assignees: Vec<Assignee>,
+    /// Checklists, whose items can be checked off from the preview
+    #[serde(default)]
+    pub checklists: Vec<Checklist>,
+    /// Fetched on demand with its parent's subtask tree rather than by a refresh
+    /// (kept across full refreshes while the parent is)
+    #[serde(default)]
+    pub fetched_as_subtask: bool,
 }
 
 impl Task {
     /// Get the task group based on status
-    pub fn group(&self) -> TaskGroup {
-        status_to_group(&self.status)
+    pub fn group(&self, overrides: &StatusGroups) -> TaskGroup {
+        status_to_group(&self.status, overrides)
+    }
+
+    /// Determine the effective group given the task's overlay (considering snooze)
+    pub fn effective_group(
+        &self,
+        overlay: Option<&TaskOverlay>,
+        overrides: &StatusGroups,
+    ) -> TaskGroup {
+        let snoozed = overlay
+            .and_then(|o| o.snoozed_until)
+            .map(|u| u > Utc::now())
+            .unwrap_or(false);
+        if snoozed {
+            TaskGroup::Snoozed
+        } else {
+            self.group(overrides)
+        }
     }
 
     /// Get priority label
     pub fn priority_label(&self) -> Option<&'static str> {
-        match self.priority {
-            Some(1) => Some("Urgent"),
-            Some(2) => Some("High"),
-            Some(3) => Some("Normal"),
-            Some(4) => Some("Low"),
-            _ => None,
-        }
+        priority_label(self.priority)
+    }
+
+    /// Whether the task is due on or before `date` (local time)
+    pub fn is_due_by(&self, date: NaiveDate) -> bool {
+        self.due_date
+            .and_then(|ms| Local.timestamp_millis_opt(ms).single())
+            .map(|due| due.date_naive() <= date)
+            .unwrap_or(false)
+    }
+
+    /// Check if this is a long-standing role/person task
+    pub fn is_person(&self) -> bool {
+        self.custom_item_id == Some(1020)
+    }
+
+    /// Browser URL of the list the task belongs to (needs the list and team IDs)
+    pub fn list_url(&self) -> Option<String> {
+        Some(format!(
+            "https://app.clickup.com/{}/v/li/{}",
+            self.team_id.as_ref()?,
+            self.list_id.as_ref()?
+        ))
     }
 
     /// Check if this task is a subtask
@@ -155,6 +323,20 @@ impl Task {
         self.assignee_ids.contains(&user_id)
     }
 
+    /// Check if a user watches this task without being assigned to it
+    pub fn is_only_watched_by(&self, user_id: u64) -> bool {
+        self.watcher_ids.contains(&user_id) && !self.is_assigned_to(user_id)
+    }
+
+    /// Initials of assignees other than `user_id` (all assignees if unknown)
+    pub fn other_assignee_initials(&self, user_id: Option<u64>) -> Vec<String> {
+        self.assignees
+            .iter()
+            .filter(|a| Some(a.id) != user_id)
+            .map(Assignee::initials)
+            .collect()
+    }
+
     /// Get task type label based on custom_item_id
     pub fn task_type_label(&self) -> Option<&'static str> {
         match self.custom_item_id {
@@ -172,6 +354,38 @@ impl Task {
     }
 }
 
+#[cfg(test)]
+impl Task {
+    /// A plain "to do" task for tests; override fields with struct update syntax
+    pub(crate) fn test(id: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            name: format!("Task {}", id),
+            status: "to do".to_string(),
+            list_name: "List".to_string(),
+            list_id: None,
+            team_id: None,
+            due_date: None,
+            date_created: None,
+            date_updated: None,
+            priority: None,
+            url: String::new(),
+            tags: Vec::new(),
+            description: None,
+            custom_item_id: None,
+            custom_id: None,
+            parent_id: None,
+            person_id: None,
+            custom_fields: Vec::new(),
+            assignee_ids: Vec::new(),
+            watcher_ids: Vec::new(),
+            assignees: Vec::new(),
+            checklists: Vec::new(),
+            fetched_as_subtask: false,
+        }
+    }
+}
+
 /// Local task overlay data (persisted separately from ClickUp data)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TaskOverlay {
@@ -182,6 +396,22 @@ pub struct TaskOverlay {
     pub snoozed_until: Option<DateTime<Utc>>,
     /// Custom sort order within group
     pub sort_order: Option<u32>,
+    /// Personal note (local only, never synced to ClickUp)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Who or what the task is waiting on (local only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waiting_reason: Option<String>,
+    /// Times opened in the browser from the TUI (boosts search ranking)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub open_count: u32,
+    /// When the task was last opened in the browser
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_opened: Option<DateTime<Utc>>,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
 }
 
 /// Local state for all tasks
@@ -190,8 +420,20 @@ pub struct LocalState {
     /// Overlay data keyed by task ID
     #[serde(default)]
     pub overlays: HashMap<String, TaskOverlay>,
+    /// Parent task IDs whose subtasks are hidden in the task list
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub collapsed: HashSet<String>,
     /// Last refresh timestamp
     pub last_refresh: Option<DateTime<Utc>>,
+    /// Newest `date_updated` (ms) among fetched tasks, for incremental refreshes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<i64>,
+    /// Tab open when the app last quit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_group: Option<TaskGroup>,
+    /// Task selected when the app last quit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_selected_task_id: Option<String>,
 }
 
 impl LocalState {
@@ -212,6 +454,25 @@ impl LocalState {
         overlay.snoozed_until = Some(until);
     }
 
+    /// Set or clear (`None`) a task's personal note
+    pub fn set_note(&mut self, task_id: &str, note: Option<String>) {
+        let overlay = self.overlays.entry(task_id.to_string()).or_default();
+        overlay.note = note;
+    }
+
+    /// Set or clear (`None`) what a task is waiting on
+    pub fn set_waiting_reason(&mut self, task_id: &str, reason: Option<String>) {
+        let overlay = self.overlays.entry(task_id.to_string()).or_default();
+        overlay.waiting_reason = reason;
+    }
+
+    /// Record that a task was opened (for search frecency)
+    pub fn record_open(&mut self, task_id: &str, at: DateTime<Utc>) {
+        let overlay = self.overlays.entry(task_id.to_string()).or_default();
+        overlay.open_count = overlay.open_count.saturating_add(1);
+        overlay.last_opened = Some(at);
+    }
+
     /// Unsnooze a task
     pub fn unsnooze(&mut self, task_id: &str) {
         if let Some(overlay) = self.overlays.get_mut(task_id) {
@@ -219,6 +480,27 @@ impl LocalState {
         }
     }
 
+    /// Clear snoozes that ended before `now`, returning how many were cleared
+    pub fn wake_expired(&mut self, now: DateTime<Utc>) -> usize {
+        let mut woken = 0;
+        for overlay in self.overlays.values_mut() {
+            if overlay.snoozed_until.is_some_and(|until| until <= now) {
+                overlay.snoozed_until = None;
+                woken += 1;
+            }
+        }
+        woken
+    }
+
+    /// Drop overlays and collapsed entries for tasks that no longer exist,
+    /// returning how many overlays were removed
+    pub fn prune(&mut self, existing: &HashSet<&str>) -> usize {
+        let before = self.overlays.len();
+        self.overlays.retain(|id, _| existing.contains(id.as_str()));
+        self.collapsed.retain(|id| existing.contains(id.as_str()));
+        before - self.overlays.len()
+    }
+
     /// Check if a task is pinned
     pub fn is_pinned(&self, task_id: &str) -> bool {
         self.overlays
@@ -240,17 +522,191 @@ impl DisplayTask {
         Self { task, overlay }
     }
 
-    /// Determine the effective group (considering snooze)
-    pub fn effective_group(&self) -> TaskGroup {
-        if self
-            .overlay
-            .snoozed_until
-            .map(|u| u > Utc::now())
-            .unwrap_or(false)
-        {
-            TaskGroup::Snoozed
-        } else {
-            self.task.group()
+    /// Markdown link to the task, labelled with its custom ID (if any) and name
+    ///
+    /// e.g. `[PROJ-123 Fix login](url)`; brackets in the label are escaped.
+    pub fn markdown_link(&self) -> String {
+        let label = match &self.task.custom_id {
+            Some(id) => format!("{} {}", id, self.task.name),
+            None => self.task.name.clone(),
+        };
+        let label = label.replace('[', "\\[").replace(']', "\\]");
+        format!("[{}]({})", label, self.task.url)
+    }
+
+    /// Fill a template's placeholders with this task's fields
+    ///
+    /// Placeholders: `{name}`, `{custom_id}`, `{id}`, `{url}`, `{status}`, `{list}`.
+    /// Fields the task doesn't have render empty and the result is trimmed;
+    /// unknown placeholders are left as written.
+    pub fn render_template(&self, template: &str) -> String {
+        let mut out = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('}') else {
+                rest = &rest[start..];
+                break;
+            };
+            let value = match &after[..end] {
+                "name" => Some(self.task.name.as_str()),
+                "custom_id" => Some(self.task.custom_id.as_deref().unwrap_or("")),
+                "id" => Some(self.task.id.as_str()),
+                "url" => Some(self.task.url.as_str()),
+                "status" => Some(self.task.status.as_str()),
+                "list" => Some(self.task.list_name.as_str()),
+                _ => None,
+            };
+            match value {
+                Some(value) => out.push_str(value),
+                None => out.push_str(&rest[start..start + end + 2]),
+            }
+            rest = &after[end + 1..];
         }
+        out.push_str(rest);
+        out.trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display_task(name: &str, custom_id: Option<&str>) -> DisplayTask {
+        let task = Task {
+            name: name.to_string(),
+            url: "https://app.clickup.com/t/abc".to_string(),
+            custom_id: custom_id.map(str::to_string),
+            ..Task::test("abc")
+        };
+        DisplayTask::new(task, TaskOverlay::default())
+    }
+
+    #[test]
+    fn test_prune_keeps_existing_tasks() {
+        let mut state = LocalState::default();
+        state.toggle_pin("kept");
+        state.toggle_pin("gone");
+        state.set_note("also-gone", Some("stale".to_string()));
+        state.collapsed.insert("gone".to_string());
+        state.collapsed.insert("kept".to_string());
+
+        assert_eq!(state.prune(&HashSet::from(["kept", "other"])), 2);
+        assert!(state.is_pinned("kept"));
+        assert_eq!(state.overlays.len(), 1);
+        assert_eq!(state.collapsed, HashSet::from(["kept".to_string()]));
+    }
+
+    #[test]
+    fn test_wake_expired_snoozes() {
+        let now = Utc::now();
+        let mut state = LocalState::default();
+        state.snooze("past", now - chrono::Duration::hours(1));
+        state.snooze("future", now + chrono::Duration::hours(1));
+
+        assert_eq!(state.wake_expired(now), 1);
+        assert_eq!(state.get_overlay("past").snoozed_until, None);
+        assert!(state.get_overlay("future").snoozed_until.is_some());
+        assert_eq!(state.wake_expired(now), 0);
+    }
+
+    #[test]
+    fn test_list_url() {
+        let mut dt = display_task("Fix login", None);
+        assert_eq!(dt.task.list_url(), None);
+        dt.task.list_id = Some("901".to_string());
+        assert_eq!(dt.task.list_url(), None, "needs the team too");
+        dt.task.team_id = Some("42".to_string());
+        assert_eq!(
+            dt.task.list_url().as_deref(),
+            Some("https://app.clickup.com/42/v/li/901")
+        );
+    }
+
+    #[test]
+    fn test_markdown_link_with_custom_id() {
+        assert_eq!(
+            display_task("Fix login", Some("PROJ-123")).markdown_link(),
+            "[PROJ-123 Fix login](https://app.clickup.com/t/abc)"
+        );
+    }
+
+    #[test]
+    fn test_markdown_link_without_custom_id() {
+        assert_eq!(
+            display_task("Fix [urgent] login", None).markdown_link(),
+            "[Fix \\[urgent\\] login](https://app.clickup.com/t/abc)"
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let dt = display_task("Fix login", Some("PROJ-123"));
+        assert_eq!(
+            dt.render_template("{custom_id}: {name} ({status}) {url}"),
+            "PROJ-123: Fix login (to do) https://app.clickup.com/t/abc"
+        );
+        assert_eq!(
+            dt.render_template("{name} {unknown} {"),
+            "Fix login {unknown} {"
+        );
+    }
+
+    #[test]
+    fn test_render_template_without_custom_id() {
+        let dt = display_task("Fix login", None);
+        assert_eq!(dt.render_template("{custom_id} {name}"), "Fix login");
+        assert_eq!(dt.render_template("{name} [{custom_id}]"), "Fix login []");
+    }
+
+    #[test]
+    fn test_checklists_default_for_old_cache() {
+        let mut value = serde_json::to_value(&display_task("Fix login", None).task).unwrap();
+        value.as_object_mut().unwrap().remove("checklists");
+        let task: Task = serde_json::from_value(value).unwrap();
+        assert!(task.checklists.is_empty());
+
+        let checklist = Checklist {
+            id: "c1".to_string(),
+            name: "Checklist".to_string(),
+            items: vec![
+                ChecklistItem {
+                    id: "i1".to_string(),
+                    name: "Write tests".to_string(),
+                    resolved: true,
+                },
+                ChecklistItem {
+                    id: "i2".to_string(),
+                    name: "Ship".to_string(),
+                    resolved: false,
+                },
+            ],
+        };
+        assert_eq!(checklist.resolved_count(), 1);
+    }
+
+    #[test]
+    fn test_other_assignee_initials() {
+        let mut t = Task::test("a");
+        t.assignees = vec![
+            Assignee {
+                id: 1,
+                username: Some("Me Myself".to_string()),
+                initials: Some("MM".to_string()),
+            },
+            Assignee {
+                id: 2,
+                username: Some("ada lovelace".to_string()),
+                initials: None,
+            },
+            Assignee {
+                id: 3,
+                username: None,
+                initials: None,
+            },
+        ];
+        assert_eq!(t.other_assignee_initials(Some(1)), vec!["AL", "?"]);
+        assert_eq!(t.other_assignee_initials(None), vec!["MM", "AL", "?"]);
     }
 }
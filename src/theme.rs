@@ -1,8 +1,13 @@
-//! Spaceduck theme colors for the TUI
+//! Theme colors for the TUI
 //!
-//! Based on https://github.com/pineapplegiant/spaceduck
+//! The default dark palette is Spaceduck (https://github.com/pineapplegiant/spaceduck).
+//! A light palette is built in, and the `[theme]` config table can override
+//! individual colors with hex values.
 
+use anyhow::{bail, Context, Result};
 use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // Spaceduck palette
 pub const FG: Color = Color::Rgb(236, 240, 193); // #ecf0c1
@@ -18,16 +23,252 @@ pub const YELLOW: Color = Color::Rgb(179, 161, 230); // #b3a1e6
 pub const SELECTED_BG: Color = Color::Rgb(30, 34, 54); // Slightly lighter bg
 pub const MUTED: Color = Color::Rgb(100, 100, 120);
 
-// Status colors
-pub const STATUS_IN_PROGRESS: Color = BLUE;
-pub const STATUS_TODO: Color = YELLOW;
-pub const STATUS_BLOCKED: Color = ORANGE;
-pub const STATUS_TESTING: Color = CYAN;
-pub const STATUS_VALIDATE: Color = PINK;
-pub const STATUS_BACKLOG: Color = MUTED;
-pub const STATUS_DONE: Color = GREEN;
-pub const STATUS_CANCELLED: Color = MUTED;
-
-// Tab colors
-pub const TAB_ACTIVE: Color = BLUE;
-pub const TAB_INACTIVE: Color = MUTED;
+/// Named colors used by the renderer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub fg: Color,
+    pub purple: Color,
+    pub pink: Color,
+    pub green: Color,
+    pub orange: Color,
+    pub blue: Color,
+    pub cyan: Color,
+    pub yellow: Color,
+    pub selected_bg: Color,
+    pub muted: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Spaceduck (the default)
+    pub fn dark() -> Self {
+        Self {
+            fg: FG,
+            purple: PURPLE,
+            pink: PINK,
+            green: GREEN,
+            orange: ORANGE,
+            blue: BLUE,
+            cyan: CYAN,
+            yellow: YELLOW,
+            selected_bg: SELECTED_BG,
+            muted: MUTED,
+        }
+    }
+
+    /// Palette for light terminal backgrounds (same roles as the dark palette)
+    pub fn light() -> Self {
+        Self {
+            fg: Color::Rgb(76, 79, 105),            // #4c4f69
+            purple: Color::Rgb(223, 142, 29),       // #df8e1d
+            pink: Color::Rgb(200, 60, 140),         // #c83c8c
+            green: Color::Rgb(64, 160, 43),         // #40a02b
+            orange: Color::Rgb(210, 15, 57),        // #d20f39
+            blue: Color::Rgb(30, 102, 245),         // #1e66f5
+            cyan: Color::Rgb(136, 57, 239),         // #8839ef
+            yellow: Color::Rgb(114, 135, 253),      // #7287fd
+            selected_bg: Color::Rgb(220, 224, 232), // #dce0e8
+            muted: Color::Rgb(140, 143, 161),       // #8c8fa1
+        }
+    }
+
+    /// Mutable access to a color by its config name
+    fn color_mut(&mut self, name: &str) -> Option<&mut Color> {
+        match name {
+            "fg" => Some(&mut self.fg),
+            "purple" => Some(&mut self.purple),
+            "pink" => Some(&mut self.pink),
+            "green" => Some(&mut self.green),
+            "orange" => Some(&mut self.orange),
+            "blue" => Some(&mut self.blue),
+            "cyan" => Some(&mut self.cyan),
+            "yellow" => Some(&mut self.yellow),
+            "selected_bg" => Some(&mut self.selected_bg),
+            "muted" => Some(&mut self.muted),
+            _ => None,
+        }
+    }
+
+    // Status colors
+    pub fn status_in_progress(&self) -> Color {
+        self.blue
+    }
+    pub fn status_todo(&self) -> Color {
+        self.yellow
+    }
+    pub fn status_blocked(&self) -> Color {
+        self.orange
+    }
+    pub fn status_testing(&self) -> Color {
+        self.cyan
+    }
+    pub fn status_validate(&self) -> Color {
+        self.pink
+    }
+    pub fn status_backlog(&self) -> Color {
+        self.muted
+    }
+    pub fn status_done(&self) -> Color {
+        self.green
+    }
+    pub fn status_cancelled(&self) -> Color {
+        self.muted
+    }
+
+    // Tab colors
+    pub fn tab_active(&self) -> Color {
+        self.blue
+    }
+    pub fn tab_inactive(&self) -> Color {
+        self.muted
+    }
+
+    /// Priority marker (2 chars wide) and color, shared by the task list, search, and help
+    pub fn priority_indicator(&self, priority: Option<u8>) -> (&'static str, Color) {
+        match priority {
+            Some(1) => ("!!", self.orange),
+            Some(2) => ("! ", self.purple),
+            Some(3) => ("- ", self.yellow),
+            Some(4) => ("· ", self.muted),
+            _ => ("  ", self.muted),
+        }
+    }
+
+    /// Colors handed out to lists by `list_color`
+    pub fn list_palette(&self) -> [Color; 7] {
+        [
+            self.blue,
+            self.green,
+            self.pink,
+            self.cyan,
+            self.purple,
+            self.yellow,
+            self.orange,
+        ]
+    }
+
+    /// A list's color from the palette, the same for a given name on every run
+    pub fn list_color(&self, list_name: &str) -> Color {
+        let palette = self.list_palette();
+        palette[(stable_hash(list_name) % palette.len() as u64) as usize]
+    }
+}
+
+/// FNV-1a hash of a string, stable across runs and Rust versions (unlike `DefaultHasher`)
+fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The `[theme]` config table: a base palette plus per-color hex overrides
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThemeConfig {
+    /// "dark" (default) or "light"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Color name -> hex value (e.g. blue = "#1e66f5")
+    #[serde(flatten)]
+    pub colors: HashMap<String, String>,
+}
+
+impl ThemeConfig {
+    pub fn is_empty(&self) -> bool {
+        self.base.is_none() && self.colors.is_empty()
+    }
+
+    /// Build the theme: base palette, then overrides
+    pub fn resolve(&self) -> Result<Theme> {
+        let mut theme = match self.base.as_deref().map(str::to_lowercase).as_deref() {
+            None | Some("dark") => Theme::dark(),
+            Some("light") => Theme::light(),
+            Some(other) => bail!("unknown base \"{}\" (expected dark or light)", other),
+        };
+        for (name, hex) in &self.colors {
+            let color =
+                parse_hex(hex).with_context(|| format!("invalid color for \"{}\"", name))?;
+            match theme.color_mut(name) {
+                Some(slot) => *slot = color,
+                None => bail!("unknown theme color \"{}\"", name),
+            }
+        }
+        Ok(theme)
+    }
+}
+
+/// Parse a `#rrggbb` (or `rrggbb`) hex color
+fn parse_hex(hex: &str) -> Result<Color> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("expected #rrggbb, got \"{}\"", hex);
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16);
+    Ok(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_indicator() {
+        let theme = Theme::dark();
+        assert_eq!(theme.priority_indicator(Some(1)), ("!!", ORANGE));
+        assert_eq!(theme.priority_indicator(Some(2)), ("! ", PURPLE));
+        assert_eq!(theme.priority_indicator(Some(3)), ("- ", YELLOW));
+        assert_eq!(theme.priority_indicator(Some(4)), ("· ", MUTED));
+        assert_eq!(theme.priority_indicator(None), ("  ", MUTED));
+        assert_eq!(theme.priority_indicator(Some(9)), ("  ", MUTED));
+    }
+
+    #[test]
+    fn test_list_color_is_stable() {
+        // Known FNV-1a values, so colors don't shift between releases
+        assert_eq!(stable_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stable_hash("a"), 0xaf63_dc4c_8601_ec8c);
+
+        let theme = Theme::dark();
+        assert_eq!(theme.list_color("Sprint"), theme.list_color("Sprint"));
+        assert!(theme.list_palette().contains(&theme.list_color("Sprint")));
+        let colors: std::collections::HashSet<_> = ["Sprint", "Backlog", "Bugs", "Ops", "Docs"]
+            .iter()
+            .map(|name| format!("{:?}", theme.list_color(name)))
+            .collect();
+        assert!(colors.len() > 1, "lists should spread over the palette");
+    }
+
+    #[test]
+    fn test_theme_config() {
+        assert_eq!(ThemeConfig::default().resolve().unwrap(), Theme::dark());
+
+        let config: ThemeConfig = toml::from_str(
+            r##"
+            base = "light"
+            blue = "#102030"
+            "##,
+        )
+        .unwrap();
+        let theme = config.resolve().unwrap();
+        assert_eq!(theme.blue, Color::Rgb(0x10, 0x20, 0x30));
+        assert_eq!(theme.fg, Theme::light().fg);
+    }
+
+    #[test]
+    fn test_theme_config_errors() {
+        let resolve = |toml: &str| {
+            toml::from_str::<ThemeConfig>(toml)
+                .unwrap()
+                .resolve()
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(resolve(r#"base = "neon""#).contains("neon"));
+        assert!(resolve(r##"teal = "#000000""##).contains("teal"));
+        assert!(resolve(r#"blue = "blue""#).contains("blue"));
+    }
+}
@@ -1,19 +1,34 @@
 //! TUI rendering with ratatui
 
-use crate::app::{App, FocusedPane, InputMode};
-use crate::models::DisplayTask;
-use crate::models::TaskGroup;
-use crate::theme;
+use crate::app::{
+    format_age, format_wake, match_positions, substring_positions, App, FocusedPane, InputMode,
+    TaskAge, PRIORITY_CHOICES,
+};
+use crate::keymap::{Action, KeyBinding, Keymap};
+use crate::markdown;
+use crate::models::{priority_label, Comment, DisplayTask, StatusChange, TaskGroup};
+use crate::theme::Theme;
+use chrono::Utc;
+use qrcode::{Color as QrColor, QrCode};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Margin, Rect},
-    style::{Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Tabs, Wrap},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{
+        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Tabs, Wrap,
+    },
     Frame,
 };
+use std::time::Instant;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Render the entire UI
-pub fn render(frame: &mut Frame, app: &App) {
+///
+/// Takes `&mut App` so panes can record layout feedback (e.g. preview scroll bounds).
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let theme = app.theme;
+
     // Add outer margin for breathing room
     let outer_area = frame.area().inner(Margin {
         horizontal: 1,
@@ -22,7 +37,7 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     // In search mode, show search-specific split pane
     if app.input_mode == InputMode::Search {
-        render_search_mode(frame, app);
+        render_search_mode(frame, app, &theme);
     } else {
         // Normal mode with split pane (task list + preview)
         let main_chunks = Layout::default()
@@ -34,7 +49,7 @@ pub fn render(frame: &mut Frame, app: &App) {
             ])
             .split(outer_area);
 
-        render_tabs(frame, app, main_chunks[0]);
+        render_tabs(frame, app, &theme, main_chunks[0]);
 
         // Split content area: task list (55%) | preview (45%) with gap
         let content_chunks = Layout::default()
@@ -46,224 +61,823 @@ pub fn render(frame: &mut Frame, app: &App) {
             ])
             .split(main_chunks[1]);
 
-        render_task_list(frame, app, content_chunks[0]);
-        render_normal_preview_pane(frame, app, content_chunks[2]);
+        app.task_list_area = content_chunks[0];
+        app.preview_area = content_chunks[2];
+        render_task_list(frame, app, &theme, content_chunks[0]);
+        render_normal_preview_pane(frame, app, &theme, content_chunks[2]);
+
+        render_status_bar(frame, app, &theme, main_chunks[2]);
+    }
+
+    // Render list picker popup if active
+    if app.input_mode == InputMode::ListPicker {
+        render_list_picker(frame, app, &theme);
+    }
+
+    // Render priority picker popup if active
+    if app.input_mode == InputMode::PriorityPicker {
+        render_priority_picker(frame, app, &theme);
+    }
 
-        render_status_bar(frame, app, main_chunks[2]);
+    // Render confirmation dialog if active
+    if app.input_mode == InputMode::Confirm {
+        render_confirm_dialog(frame, app, &theme);
+    }
+
+    // Render QR code popup if active
+    if app.input_mode == InputMode::QrCode {
+        render_qr_code(frame, app, &theme);
+    }
+
+    // Render message log popup if active
+    if app.input_mode == InputMode::MessageLog {
+        render_message_log(frame, app, &theme);
     }
 
     // Render help overlay if active
     if app.show_help {
-        render_help_overlay(frame);
+        render_help_overlay(frame, app, &theme);
     }
 }
 
-/// Render help overlay with legend
-fn render_help_overlay(frame: &mut Frame) {
+/// A popup area centered in `area`, taking the given percentage of its width and
+/// height but no more than `max_w` x `max_h`
+///
+/// Pass 100 for both percentages to center a fixed-size popup, clipped to `area`.
+fn centered_rect(percent_x: u16, percent_y: u16, max_w: u16, max_h: u16, area: Rect) -> Rect {
+    let width = (area.width as u32 * percent_x.min(100) as u32 / 100) as u16;
+    let height = (area.height as u32 * percent_y.min(100) as u32 / 100) as u16;
+    let width = width.min(max_w);
+    let height = height.min(max_h);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Light modules around a QR code so scanners can find its edges
+const QR_QUIET_ZONE: usize = 2;
+
+/// Draw a QR code with half blocks, two module rows per line (`None` if `data` is too long)
+///
+/// Colors are explicit (black on white) so the code scans on dark terminals too.
+fn qr_code_lines(data: &str) -> Option<Vec<Line<'static>>> {
+    let code = QrCode::new(data.as_bytes()).ok()?;
+    let width = code.width();
+    let colors = code.to_colors();
+    let size = width + QR_QUIET_ZONE * 2;
+    let is_dark = |x: usize, y: usize| -> bool {
+        let (Some(x), Some(y)) = (x.checked_sub(QR_QUIET_ZONE), y.checked_sub(QR_QUIET_ZONE))
+        else {
+            return false;
+        };
+        x < width && y < width && colors[y * width + x] == QrColor::Dark
+    };
+    let shade = |dark: bool| if dark { Color::Black } else { Color::White };
+
+    Some(
+        (0..size)
+            .step_by(2)
+            .map(|y| {
+                Line::from(
+                    (0..size)
+                        .map(|x| {
+                            Span::styled(
+                                "▀",
+                                Style::default()
+                                    .fg(shade(is_dark(x, y)))
+                                    .bg(shade(is_dark(x, y + 1))),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Render the selected task's URL as a QR code popup for scanning with a phone
+fn render_qr_code(frame: &mut Frame, app: &App, theme: &Theme) {
+    let Some(dt) = app.selected_task() else {
+        return;
+    };
     let area = frame.area();
+    let lines = qr_code_lines(&dt.task.url).unwrap_or_default();
+    let code_width = lines.first().map(|l| l.width()).unwrap_or(0) as u16;
+    let fits =
+        !lines.is_empty() && code_width + 2 <= area.width && lines.len() as u16 + 3 <= area.height;
+
+    let content: Vec<Line> = if fits {
+        let mut content = lines;
+        content.push(Line::from(Span::styled(
+            dt.task.url.clone(),
+            Style::default().fg(theme.muted),
+        )));
+        content
+    } else {
+        vec![
+            Line::from(Span::styled(
+                "Terminal too small for the QR code",
+                Style::default().fg(theme.orange),
+            )),
+            Line::from(Span::styled(
+                dt.task.url.clone(),
+                Style::default().fg(theme.muted),
+            )),
+        ]
+    };
 
-    // Center the help popup (70% width, 80% height)
-    let popup_width = (area.width * 70 / 100).min(80);
-    let popup_height = (area.height * 80 / 100).min(35);
-    let popup_x = (area.width - popup_width) / 2;
-    let popup_y = (area.height - popup_height) / 2;
-    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+    // Center the popup around the code (or the message if it doesn't fit)
+    let popup_width =
+        (content.iter().map(|l| l.width()).max().unwrap_or(0) as u16 + 2).max(code_width + 2);
+    let popup_area = centered_rect(100, 100, popup_width, content.len() as u16 + 2, area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let qr = Paragraph::new(content).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                " Scan to open ",
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            )),
+    );
+    frame.render_widget(qr, popup_area);
+}
+
+/// Render recent status messages, newest first
+fn render_message_log(frame: &mut Frame, app: &App, theme: &Theme) {
+    let area = frame.area();
+    let content: Vec<Line> = if app.message_log.is_empty() {
+        vec![Line::from(Span::styled(
+            "No messages yet",
+            Style::default().fg(theme.muted),
+        ))]
+    } else {
+        app.message_log
+            .iter()
+            .rev()
+            .map(|(at, message)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}  ", at.format("%H:%M:%S")),
+                        Style::default().fg(theme.muted),
+                    ),
+                    Span::styled(message.clone(), Style::default().fg(theme.fg)),
+                ])
+            })
+            .collect()
+    };
+
+    let popup_area = centered_rect(80, 80, 100, content.len() as u16 + 2, area);
+    frame.render_widget(Clear, popup_area);
+
+    let log = Paragraph::new(content)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.blue))
+                .title(Span::styled(
+                    " Messages ",
+                    Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+                )),
+        )
+        .style(Style::default().bg(theme.selected_bg));
+    frame.render_widget(log, popup_area);
+}
+
+/// Render the list filter picker popup
+fn render_list_picker(frame: &mut Frame, app: &App, theme: &Theme) {
+    let area = frame.area();
+
+    // Center the picker popup (50% width, 60% height)
+    let popup_width = (area.width * 50 / 100).clamp(30.min(area.width), 60);
+    let popup_area = centered_rect(100, 60, popup_width, 20, area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(popup_area);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(" 🔍 ", Style::default().fg(theme.blue)),
+        Span::styled(&app.list_picker_query, Style::default().fg(theme.fg)),
+        Span::styled("│", Style::default().fg(theme.blue)), // cursor
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                " Filter by List ",
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            )),
+    )
+    .style(Style::default().bg(theme.selected_bg));
+    frame.render_widget(input, chunks[0]);
+
+    let matches = app.list_picker_matches();
+    let items: Vec<ListItem> = matches
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            let is_selected = idx == app.list_picker_index;
+            let is_active = app
+                .active_list_filter
+                .as_ref()
+                .is_some_and(|list| list.to_lowercase() == name.to_lowercase());
+            let style = if is_selected {
+                Style::default().fg(theme.fg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.fg)
+            };
+            let marker = if is_active { "● " } else { "  " };
+            let item = ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(theme.cyan)),
+                Span::styled(name.clone(), style),
+            ]));
+            if is_selected {
+                item.style(Style::default().bg(theme.selected_bg))
+            } else {
+                item
+            }
+        })
+        .collect();
+
+    let title = if matches.is_empty() {
+        " No matches ".to_string()
+    } else {
+        format!(" {} lists ", matches.len())
+    };
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(title, Style::default().fg(theme.fg))),
+    );
+    frame.render_widget(list, chunks[1]);
+}
+
+/// Render the priority picker popup for the selected task
+fn render_priority_picker(frame: &mut Frame, app: &App, theme: &Theme) {
+    let area = frame.area();
+    let popup_area = centered_rect(100, 100, 24, PRIORITY_CHOICES.len() as u16 + 2, area);
 
-    // Clear the area behind the popup
     frame.render_widget(Clear, popup_area);
 
-    let help_content: Vec<Line<'static>> = vec![
+    let current = app.selected_task().and_then(|dt| dt.task.priority);
+    let items: Vec<ListItem> = PRIORITY_CHOICES
+        .iter()
+        .enumerate()
+        .map(|(idx, &priority)| {
+            let (indicator, color) = theme.priority_indicator(priority);
+            let key = if priority.is_some() { idx + 1 } else { 0 };
+            let marker = if priority == current { "● " } else { "  " };
+            let item = ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", key), Style::default().fg(theme.muted)),
+                Span::styled(indicator, Style::default().fg(color)),
+                Span::styled(" ", Style::default()),
+                Span::styled(
+                    priority_label(priority).unwrap_or("None"),
+                    Style::default().fg(theme.fg),
+                ),
+                Span::styled(format!(" {}", marker), Style::default().fg(theme.cyan)),
+            ]));
+            if idx == app.priority_picker_index {
+                item.style(Style::default().bg(theme.selected_bg))
+            } else {
+                item
+            }
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.blue))
+            .title(Span::styled(
+                " Priority ",
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+            )),
+    );
+    frame.render_widget(list, popup_area);
+}
+
+/// Help legend entry for a priority marker
+fn priority_legend_line(theme: &Theme, priority: u8, label: &'static str) -> Line<'static> {
+    let (indicator, color) = theme.priority_indicator(Some(priority));
+    Line::from(vec![
+        Span::styled(format!("  {}  ", indicator), Style::default().fg(color)),
+        Span::styled(label, Style::default().fg(theme.fg)),
+    ])
+}
+
+/// Keys shown in a help overlay row
+enum HelpKeys {
+    /// Every key bound to one action, or the first key of each of several actions
+    Bound(&'static [Action]),
+    /// A built-in key sequence that can't be rebound
+    Fixed(&'static str),
+    /// The first key of each action, preceded by a count
+    Counted(&'static [Action]),
+}
+
+/// Help overlay sections and rows; key labels come from the active keymap
+const HELP_SECTIONS: &[(&str, &[(HelpKeys, &str)])] = &[
+    (
+        "NAVIGATION",
+        &[
+            (
+                HelpKeys::Bound(&[Action::NextTask, Action::PrevTask]),
+                "Navigate tasks",
+            ),
+            (HelpKeys::Fixed("gg"), "Jump to first task"),
+            (HelpKeys::Bound(&[Action::LastTask]), "Jump to last task"),
+            (
+                HelpKeys::Bound(&[Action::HalfPageDown, Action::HalfPageUp]),
+                "Scroll details half a page",
+            ),
+            (
+                HelpKeys::Bound(&[Action::NextPane, Action::PrevPane]),
+                "Switch pane focus",
+            ),
+            (
+                HelpKeys::Bound(&[Action::PrevTab, Action::NextTab]),
+                "Previous / next tab",
+            ),
+            (
+                HelpKeys::Fixed("g{letter}"),
+                "Tab by first letter (gm, gw, gb, gd, gs, gp, gt; repeat to cycle)",
+            ),
+            (HelpKeys::Fixed("1-8"), "Switch to tab N (in tab bar order)"),
+            (
+                HelpKeys::Counted(&[Action::NextTask, Action::PrevTask, Action::LastTask]),
+                "Move N tasks down / up, or jump to task N",
+            ),
+            (
+                HelpKeys::Bound(&[Action::JumpToId]),
+                "Jump to a task by custom ID",
+            ),
+            (HelpKeys::Bound(&[Action::Search]), "Global fuzzy search"),
+        ],
+    ),
+    (
+        "TASKS",
+        &[
+            (HelpKeys::Bound(&[Action::Open]), "Open task in browser"),
+            (
+                HelpKeys::Bound(&[Action::OpenList]),
+                "Open the task's list in browser",
+            ),
+            (HelpKeys::Bound(&[Action::Copy]), "Copy task to clipboard"),
+            (HelpKeys::Bound(&[Action::CopyUrl]), "Copy task URL"),
+            (
+                HelpKeys::Bound(&[Action::CopyLink]),
+                "Copy task as a Markdown link",
+            ),
+            (
+                HelpKeys::Bound(&[Action::QrCode]),
+                "Show task link as a QR code",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleTimer]),
+                "Start / stop a ClickUp timer on the task",
+            ),
+            (HelpKeys::Bound(&[Action::TogglePin]), "Toggle pin"),
+            (
+                HelpKeys::Bound(&[Action::ToggleSelect]),
+                "Mark task; pin or snooze then acts on all marked",
+            ),
+            (HelpKeys::Bound(&[Action::Snooze]), "Snooze task"),
+            (
+                HelpKeys::Bound(&[
+                    Action::SnoozeTomorrow,
+                    Action::SnoozeWeek,
+                    Action::SnoozeMonth,
+                ]),
+                "Snooze until tomorrow / for a week / for a month",
+            ),
+            (HelpKeys::Bound(&[Action::Unsnooze]), "Unsnooze task"),
+            (
+                HelpKeys::Bound(&[Action::Undo]),
+                "Undo last pin/snooze/unsnooze",
+            ),
+            (HelpKeys::Bound(&[Action::SetPriority]), "Change priority"),
+            (
+                HelpKeys::Bound(&[Action::AssignMe, Action::UnassignMe]),
+                "Assign / unassign yourself",
+            ),
+            (
+                HelpKeys::Bound(&[Action::Comment]),
+                "Post a comment on the task",
+            ),
+            (
+                HelpKeys::Bound(&[Action::EditNote]),
+                "Edit personal note (local only)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::WaitingOn]),
+                "Note who/what the task is waiting on",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ChecklistNext, Action::ChecklistPrev]),
+                "Move through the task's checklist",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleChecklistItem]),
+                "Check off / un-check the checklist item",
+            ),
+            (
+                HelpKeys::Bound(&[Action::FetchSubtasks]),
+                "Fetch all subtasks, including others'",
+            ),
+            (
+                HelpKeys::Bound(&[Action::Export]),
+                "Copy current view as a Markdown checklist",
+            ),
+        ],
+    ),
+    (
+        "VIEW",
+        &[
+            (
+                HelpKeys::Bound(&[Action::CycleSort]),
+                "Cycle sort (priority, due, name, status, updated)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleDoneAll]),
+                "Done tab: recent / all completed",
+            ),
+            (
+                HelpKeys::Bound(&[Action::TagFilter, Action::ClearFilters]),
+                "Filter by tag / clear filter",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ListPicker]),
+                "Filter by ClickUp list",
+            ),
+            (
+                HelpKeys::Bound(&[Action::PersonView]),
+                "Show a Person task's user's tasks / back out",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleCollapse]),
+                "Collapse / expand the task's subtasks",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleWrap]),
+                "Wrap / truncate long task names",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleDense]),
+                "Dense rows (hide status, type, and ID tags)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleDoneSubtasks]),
+                "Hide done subtasks of open parents",
+            ),
+        ],
+    ),
+    (
+        "GENERAL",
+        &[
+            (
+                HelpKeys::Bound(&[Action::Refresh]),
+                "Refresh tasks changed since the last fetch",
+            ),
+            (
+                HelpKeys::Bound(&[Action::FullRefresh]),
+                "Refetch all tasks from ClickUp",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ReloadConfig]),
+                "Reload the config file (theme, keys, tabs, ...)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::MessageLog]),
+                "Show recent status and error messages",
+            ),
+            (HelpKeys::Bound(&[Action::Help]), "Toggle this help"),
+            (HelpKeys::Bound(&[Action::Quit]), "Quit"),
+        ],
+    ),
+];
+
+impl HelpKeys {
+    /// Key column text for this row under the given keymap
+    fn label(&self, keymap: &Keymap) -> String {
+        let first = |action: &Action| {
+            keymap
+                .keys_for(*action)
+                .first()
+                .map(KeyBinding::label)
+                .unwrap_or_else(|| "-".to_string())
+        };
+        match self {
+            HelpKeys::Bound([action]) => {
+                let keys: Vec<String> = keymap
+                    .keys_for(*action)
+                    .iter()
+                    .map(KeyBinding::label)
+                    .collect();
+                if keys.is_empty() {
+                    "-".to_string()
+                } else {
+                    keys.join(", ")
+                }
+            }
+            HelpKeys::Bound(actions) => actions.iter().map(first).collect::<Vec<_>>().join("/"),
+            HelpKeys::Fixed(keys) => keys.to_string(),
+            HelpKeys::Counted(actions) => {
+                format!(
+                    "{{N}}{}",
+                    actions.iter().map(first).collect::<Vec<_>>().join("/")
+                )
+            }
+        }
+    }
+}
+
+/// Help overlay key sections, with the key column sized to the longest label
+fn help_key_lines(keymap: &Keymap, theme: &Theme) -> Vec<Line<'static>> {
+    let sections: Vec<(&str, Vec<(String, &str)>)> = HELP_SECTIONS
+        .iter()
+        .map(|(title, rows)| {
+            let rows = rows
+                .iter()
+                .map(|(keys, description)| (keys.label(keymap), *description))
+                .collect();
+            (*title, rows)
+        })
+        .collect();
+    let key_width = sections
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(keys, _)| keys.width()))
+        .max()
+        .unwrap_or(0)
+        .max(10);
+
+    let mut lines = Vec::new();
+    for (i, (title, rows)) in sections.into_iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            title,
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
+        for (keys, description) in rows {
+            let padding = " ".repeat(key_width - keys.width());
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}{}", keys, padding),
+                    Style::default().fg(theme.cyan),
+                ),
+                Span::styled(format!("  {}", description), Style::default().fg(theme.fg)),
+            ]));
+        }
+    }
+    lines
+}
+
+/// Render the yes/no confirmation dialog for a pending action
+fn render_confirm_dialog(frame: &mut Frame, app: &App, theme: &Theme) {
+    let Some(confirmation) = &app.confirmation else {
+        return;
+    };
+    let area = frame.area();
+
+    // Center a small popup sized to the prompt
+    let popup_width = (confirmation.prompt.chars().count() as u16 + 6).max(30);
+    let popup_area = centered_rect(100, 100, popup_width, 6, area);
+
+    frame.render_widget(Clear, popup_area);
+
+    let content = vec![
+        Line::from(""),
         Line::from(Span::styled(
-            "KEYBINDINGS",
-            Style::default()
-                .fg(theme::BLUE)
-                .add_modifier(Modifier::BOLD),
+            confirmation.prompt.clone(),
+            Style::default().fg(theme.fg),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  j/k, ↑/↓  ", Style::default().fg(theme::CYAN)),
-            Span::styled("Navigate tasks", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  h/l, Tab  ", Style::default().fg(theme::CYAN)),
-            Span::styled("Switch tabs", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  1-6       ", Style::default().fg(theme::CYAN)),
-            Span::styled(
-                "Jump to tab (My Action, Waiting, Backlog, Done, Snoozed, Person)",
-                Style::default().fg(theme::FG),
-            ),
-        ]),
-        Line::from(vec![
-            Span::styled("  o, Enter  ", Style::default().fg(theme::CYAN)),
-            Span::styled("Open task in browser", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  y         ", Style::default().fg(theme::CYAN)),
-            Span::styled("Copy task to clipboard", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  p         ", Style::default().fg(theme::CYAN)),
-            Span::styled("Toggle pin", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  s         ", Style::default().fg(theme::CYAN)),
-            Span::styled("Snooze task", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  S         ", Style::default().fg(theme::CYAN)),
-            Span::styled("Unsnooze task", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  /         ", Style::default().fg(theme::CYAN)),
-            Span::styled("Global fuzzy search", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  r         ", Style::default().fg(theme::CYAN)),
-            Span::styled("Refresh tasks from ClickUp", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ?         ", Style::default().fg(theme::CYAN)),
-            Span::styled("Toggle this help", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  q         ", Style::default().fg(theme::CYAN)),
-            Span::styled("Quit", Style::default().fg(theme::FG)),
+            Span::styled("[y]", Style::default().fg(theme.green)),
+            Span::styled("es  ", Style::default().fg(theme.muted)),
+            Span::styled("[n]", Style::default().fg(theme.orange)),
+            Span::styled("o", Style::default().fg(theme.muted)),
         ]),
+    ];
+
+    let dialog = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.yellow))
+                .title(Span::styled(
+                    " Confirm ",
+                    Style::default()
+                        .fg(theme.yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+        )
+        .style(Style::default().bg(theme.selected_bg));
+    frame.render_widget(dialog, popup_area);
+}
+
+/// Render help overlay with legend
+fn render_help_overlay(frame: &mut Frame, app: &mut App, theme: &Theme) {
+    let area = frame.area();
+
+    // Center the help popup (70% width, 80% height)
+    let popup_area = centered_rect(70, 80, 80, 35, area);
+
+    // Clear the area behind the popup
+    frame.render_widget(Clear, popup_area);
+
+    let mut help_content = help_key_lines(&app.keymap, theme);
+    help_content.extend([
         Line::from(""),
         Line::from(Span::styled(
             "PRIORITY INDICATORS",
-            Style::default()
-                .fg(theme::BLUE)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("  !!  ", Style::default().fg(theme::ORANGE)),
-            Span::styled("Urgent", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  !   ", Style::default().fg(theme::PURPLE)),
-            Span::styled("High", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  -   ", Style::default().fg(theme::YELLOW)),
-            Span::styled("Normal", Style::default().fg(theme::FG)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ·   ", Style::default().fg(theme::MUTED)),
-            Span::styled("Low", Style::default().fg(theme::FG)),
-        ]),
+        priority_legend_line(theme, 1, "Urgent"),
+        priority_legend_line(theme, 2, "High"),
+        priority_legend_line(theme, 3, "Normal"),
+        priority_legend_line(theme, 4, "Low"),
         Line::from(""),
         Line::from(Span::styled(
             "SYMBOLS",
-            Style::default()
-                .fg(theme::BLUE)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  📌  ", Style::default().fg(theme::YELLOW)),
-            Span::styled("Pinned task", Style::default().fg(theme::FG)),
+            Span::styled("  📌  ", Style::default().fg(theme.yellow)),
+            Span::styled("Pinned task", Style::default().fg(theme.fg)),
         ]),
         Line::from(vec![
-            Span::styled("  └   ", Style::default().fg(theme::MUTED)),
+            Span::styled("  └   ", Style::default().fg(theme.muted)),
             Span::styled(
                 "Subtask (child of another task)",
-                Style::default().fg(theme::FG),
+                Style::default().fg(theme.fg),
             ),
         ]),
         Line::from(""),
         Line::from(Span::styled(
             "STATUS COLORS",
-            Style::default()
-                .fg(theme::BLUE)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  ████  ", Style::default().fg(theme::STATUS_IN_PROGRESS)),
-            Span::styled("In Progress", Style::default().fg(theme::FG)),
+            Span::styled("  ████  ", Style::default().fg(theme.status_in_progress())),
+            Span::styled("In Progress", Style::default().fg(theme.fg)),
         ]),
         Line::from(vec![
-            Span::styled("  ████  ", Style::default().fg(theme::STATUS_TODO)),
-            Span::styled("To Do", Style::default().fg(theme::FG)),
+            Span::styled("  ████  ", Style::default().fg(theme.status_todo())),
+            Span::styled("To Do", Style::default().fg(theme.fg)),
         ]),
         Line::from(vec![
-            Span::styled("  ████  ", Style::default().fg(theme::STATUS_BLOCKED)),
-            Span::styled("Blocked", Style::default().fg(theme::FG)),
+            Span::styled("  ████  ", Style::default().fg(theme.status_blocked())),
+            Span::styled("Blocked", Style::default().fg(theme.fg)),
         ]),
         Line::from(vec![
-            Span::styled("  ████  ", Style::default().fg(theme::STATUS_TESTING)),
-            Span::styled("In Testing", Style::default().fg(theme::FG)),
+            Span::styled("  ████  ", Style::default().fg(theme.status_testing())),
+            Span::styled("In Testing", Style::default().fg(theme.fg)),
         ]),
         Line::from(vec![
-            Span::styled("  ████  ", Style::default().fg(theme::STATUS_VALIDATE)),
-            Span::styled("To Validate", Style::default().fg(theme::FG)),
+            Span::styled("  ████  ", Style::default().fg(theme.status_validate())),
+            Span::styled("To Validate", Style::default().fg(theme.fg)),
         ]),
         Line::from(vec![
-            Span::styled("  ████  ", Style::default().fg(theme::STATUS_DONE)),
-            Span::styled("Done / Completed", Style::default().fg(theme::FG)),
+            Span::styled("  ████  ", Style::default().fg(theme.status_done())),
+            Span::styled("Done / Completed", Style::default().fg(theme.fg)),
         ]),
         Line::from(""),
         Line::from(Span::styled(
-            "Press Esc, q, or ? to close",
-            Style::default().fg(theme::MUTED),
+            "j/k to scroll · Esc, q, or ? to close",
+            Style::default().fg(theme.muted),
         )),
-    ];
+    ]);
+
+    // Scroll within the border when the help is taller than the popup
+    let inner_height = popup_area.height.saturating_sub(2);
+    let max_scroll = help_content.len().saturating_sub(inner_height as usize);
+    app.set_help_bounds(max_scroll.min(u16::MAX as usize) as u16);
 
     let help = Paragraph::new(help_content)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(theme::BLUE))
+                .border_style(Style::default().fg(theme.blue))
                 .title(Span::styled(
                     " Help ",
-                    Style::default()
-                        .fg(theme::BLUE)
-                        .add_modifier(Modifier::BOLD),
+                    Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
                 )),
         )
-        .style(Style::default().bg(theme::SELECTED_BG));
+        .style(Style::default().bg(theme.selected_bg))
+        .scroll((app.help_scroll, 0));
 
     frame.render_widget(help, popup_area);
 }
 
 /// Render preview pane for selected task in normal mode
-fn render_normal_preview_pane(frame: &mut Frame, app: &App, area: Rect) {
+fn render_normal_preview_pane(frame: &mut Frame, app: &mut App, theme: &Theme, area: Rect) {
     let selected = app.selected_task();
 
     let content: Vec<Line> = if let Some(dt) = selected {
-        build_preview_content(&dt, area.width as usize)
+        build_preview_content(&dt, app, app.checklist_cursor_for(&dt.task.id), theme)
     } else {
         vec![Line::from(Span::styled(
             "No task selected",
-            Style::default().fg(theme::MUTED),
+            Style::default().fg(theme.muted),
         ))]
     };
 
     let border_color = if app.focused_pane == FocusedPane::Preview {
-        theme::CYAN
+        theme.cyan
     } else {
-        theme::MUTED
+        theme.muted
     };
 
-    let preview = Paragraph::new(content)
-        .wrap(Wrap { trim: true })
-        .scroll((app.preview_scroll, 0))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color))
-                .title(Span::styled(" Details ", Style::default().fg(theme::CYAN))),
-        );
+    let preview = Paragraph::new(content).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title(Span::styled(" Details ", Style::default().fg(theme.cyan))),
+    );
+
+    // Record scroll bounds so scrolling stops at the end of the content
+    let inner_height = area.height.saturating_sub(2);
+    let (content_height, max_scroll) = scroll_bounds(&preview, area);
+    app.set_preview_bounds(inner_height, max_scroll.min(u16::MAX as usize) as u16);
 
+    let preview = preview.scroll((app.preview_scroll, 0));
     frame.render_widget(preview, area);
+
+    render_scrollbar(
+        frame,
+        theme,
+        area,
+        max_scroll + 1,
+        app.preview_scroll as usize,
+        content_height,
+    );
+}
+
+/// Content lines of a bordered, wrapped paragraph and its maximum scroll offset in `area`
+///
+/// `Paragraph::line_count` already counts the borders, like `area.height` does.
+fn scroll_bounds(paragraph: &Paragraph, area: Rect) -> (usize, usize) {
+    let rendered = paragraph.line_count(area.width.saturating_sub(2));
+    (
+        rendered.saturating_sub(2),
+        rendered.saturating_sub(area.height as usize),
+    )
+}
+
+/// Render a vertical scrollbar over a bordered pane's right edge, if content overflows
+fn render_scrollbar(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    positions: usize,
+    position: usize,
+    content_height: usize,
+) {
+    let viewport = area.height.saturating_sub(2) as usize;
+    if content_height <= viewport {
+        return;
+    }
+
+    let mut state = ScrollbarState::new(positions)
+        .position(position)
+        .viewport_content_length(viewport);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Style::default().fg(theme.muted))
+        .thumb_style(Style::default().fg(theme.cyan));
+    frame.render_stateful_widget(
+        scrollbar,
+        area.inner(Margin {
+            horizontal: 0,
+            vertical: 1,
+        }),
+        &mut state,
+    );
 }
 
 /// Render search mode with split pane (results left, preview right)
-fn render_search_mode(frame: &mut Frame, app: &App) {
+fn render_search_mode(frame: &mut Frame, app: &App, theme: &Theme) {
     // Add outer margin for breathing room
     let outer_area = frame.area().inner(Margin {
         horizontal: 1,
@@ -281,19 +895,17 @@ fn render_search_mode(frame: &mut Frame, app: &App) {
 
     // Search input bar
     let search_input = Paragraph::new(Line::from(vec![
-        Span::styled(" 🔍 ", Style::default().fg(theme::BLUE)),
-        Span::styled(&app.search_query, Style::default().fg(theme::FG)),
-        Span::styled("│", Style::default().fg(theme::BLUE)), // cursor
+        Span::styled(" 🔍 ", Style::default().fg(theme.blue)),
+        Span::styled(&app.search_query, Style::default().fg(theme.fg)),
+        Span::styled("│", Style::default().fg(theme.blue)), // cursor
     ]))
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::BLUE))
+            .border_style(Style::default().fg(theme.blue))
             .title(Span::styled(
                 " Global Search ",
-                Style::default()
-                    .fg(theme::BLUE)
-                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
             )),
     );
     frame.render_widget(search_input, main_chunks[0]);
@@ -309,17 +921,17 @@ fn render_search_mode(frame: &mut Frame, app: &App) {
         .split(main_chunks[1]);
 
     // Render search results
-    render_search_results(frame, app, content_chunks[0]);
+    render_search_results(frame, app, theme, content_chunks[0]);
 
     // Render preview pane
-    render_preview_pane(frame, app, content_chunks[2]);
+    render_preview_pane(frame, app, theme, content_chunks[2]);
 
     // Status bar
-    render_status_bar(frame, app, main_chunks[2]);
+    render_status_bar(frame, app, theme, main_chunks[2]);
 }
 
 /// Render search results list
-fn render_search_results(frame: &mut Frame, app: &App, area: Rect) {
+fn render_search_results(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     let results = app.search_all_tasks();
 
     let items: Vec<ListItem> = results
@@ -329,45 +941,40 @@ fn render_search_results(frame: &mut Frame, app: &App, area: Rect) {
             let is_selected = idx == app.search_selected_index;
 
             // Priority indicator
-            let priority_style = match dt.task.priority {
-                Some(1) => Style::default().fg(theme::ORANGE),
-                Some(2) => Style::default().fg(theme::PURPLE),
-                Some(3) => Style::default().fg(theme::YELLOW),
-                _ => Style::default().fg(theme::MUTED),
-            };
-            let priority_indicator = match dt.task.priority {
-                Some(1) => "!! ",
-                Some(2) => "!  ",
-                Some(3) => "-  ",
-                _ => "   ",
-            };
+            let (priority_indicator, priority_color) = theme.priority_indicator(dt.task.priority);
 
-            let status_style = get_status_style(&dt.task.status);
+            let status_style = get_status_style(theme, &dt.task.status);
 
-            // Truncate name
-            let max_len = area.width.saturating_sub(20) as usize;
-            let name = if dt.task.name.len() > max_len {
-                format!("{}...", &dt.task.name[..max_len.saturating_sub(3)])
-            } else {
-                dt.task.name.clone()
-            };
+            // Truncate the name to the columns left beside the borders, priority, and status
+            let reserved = 2 + priority_indicator.width() + 1 + 2 + dt.task.status.width();
+            let name = truncate_to_width(
+                &dt.task.name,
+                (area.width as usize).saturating_sub(reserved),
+            );
 
             let name_style = if is_selected {
-                Style::default().fg(theme::FG).add_modifier(Modifier::BOLD)
+                Style::default().fg(theme.fg).add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(theme::FG)
+                Style::default().fg(theme.fg)
             };
 
-            let line = Line::from(vec![
-                Span::styled(priority_indicator, priority_style),
-                Span::styled(name, name_style),
-                Span::raw("  "),
-                Span::styled(&dt.task.status, status_style),
-            ]);
+            let mut spans = vec![Span::styled(
+                format!("{} ", priority_indicator),
+                Style::default().fg(priority_color),
+            )];
+            let matched = shown_positions(
+                &dt.task.name,
+                &name,
+                match_positions(&dt.task.name, &app.search_query),
+            );
+            spans.extend(highlight_matches(&name, &matched, name_style, theme));
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(dt.task.status.clone(), status_style));
+            let line = Line::from(spans);
 
             let item = ListItem::new(line);
             if is_selected {
-                item.style(Style::default().bg(theme::SELECTED_BG))
+                item.style(Style::default().bg(theme.selected_bg))
             } else {
                 item
             }
@@ -387,105 +994,200 @@ fn render_search_results(frame: &mut Frame, app: &App, area: Rect) {
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::MUTED))
-            .title(Span::styled(title, Style::default().fg(theme::FG))),
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(title, Style::default().fg(theme.fg))),
     );
 
     frame.render_widget(list, area);
 }
 
 /// Render preview pane for selected search result
-fn render_preview_pane(frame: &mut Frame, app: &App, area: Rect) {
+fn render_preview_pane(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     let selected = app.selected_search_result();
 
     let content: Vec<Line> = if let Some(dt) = selected {
-        build_preview_content(&dt, area.width as usize)
+        build_preview_content(dt, app, None, theme)
     } else {
         vec![Line::from(Span::styled(
             "No task selected",
-            Style::default().fg(theme::MUTED),
+            Style::default().fg(theme.muted),
         ))]
     };
 
-    let preview = Paragraph::new(content).wrap(Wrap { trim: true }).block(
+    let preview = Paragraph::new(content).wrap(Wrap { trim: false }).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::MUTED))
-            .title(Span::styled(" Preview ", Style::default().fg(theme::CYAN))),
+            .border_style(Style::default().fg(theme.muted))
+            .title(Span::styled(" Preview ", Style::default().fg(theme.cyan))),
     );
 
     frame.render_widget(preview, area);
 }
 
+/// Keep the first `max` lines, noting that the rest is in the browser (`None` keeps all)
+fn limit_lines(
+    mut lines: Vec<Line<'static>>,
+    max: Option<usize>,
+    keymap: &Keymap,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    if let Some(max) = max.filter(|&max| lines.len() > max) {
+        lines.truncate(max);
+        let hint = match keymap.keys_for(Action::Open).first() {
+            Some(key) => format!("… (press {} to open full in browser)", key.label()),
+            None => "… (open the task in the browser for the rest)".to_string(),
+        };
+        lines.push(Line::from(Span::styled(
+            hint,
+            Style::default().fg(theme.muted),
+        )));
+    }
+    lines
+}
+
+/// A task's loaded status history (`None` while loading or if unavailable)
+fn status_history<'a>(app: &'a App, task_id: &str) -> Option<&'a [StatusChange]> {
+    app.status_history.get(task_id).and_then(|h| h.as_deref())
+}
+
 /// Build preview content for a task (returns owned Lines)
-fn build_preview_content(dt: &DisplayTask, _width: usize) -> Vec<Line<'static>> {
+///
+/// Comments, status history, and subtask counts come from what `app` has loaded
+/// for the task; `checklist_cursor` marks the highlighted checklist item.
+fn build_preview_content(
+    dt: &DisplayTask,
+    app: &App,
+    checklist_cursor: Option<usize>,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    // Absent while loading, `None` if fetching failed
+    let comments: Option<&Option<Vec<Comment>>> = app.comments.get(&dt.task.id);
+    let history = status_history(app, &dt.task.id);
+    let subtasks = app.subtask_status_counts(&dt.task.id);
     let mut lines: Vec<Line<'static>> = Vec::new();
 
     // Custom ID if present (e.g., "PROJ-123")
     if let Some(custom_id) = &dt.task.custom_id {
         lines.push(Line::from(Span::styled(
             custom_id.clone(),
-            Style::default()
-                .fg(theme::CYAN)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.cyan).add_modifier(Modifier::BOLD),
         )));
     }
 
     // Task name (bold)
     lines.push(Line::from(Span::styled(
         dt.task.name.clone(),
-        Style::default().fg(theme::FG).add_modifier(Modifier::BOLD),
+        Style::default().fg(theme.fg).add_modifier(Modifier::BOLD),
     )));
     lines.push(Line::from(""));
 
     // Task type
     if let Some(task_type) = dt.task.task_type_label() {
         lines.push(Line::from(vec![
-            Span::styled("Type: ", Style::default().fg(theme::MUTED)),
-            Span::styled(task_type, Style::default().fg(theme::PINK)),
+            Span::styled("Type: ", Style::default().fg(theme.muted)),
+            Span::styled(task_type, Style::default().fg(theme.pink)),
         ]));
     }
 
     // Subtask indicator
     if dt.task.is_subtask() {
         lines.push(Line::from(vec![
-            Span::styled("└ ", Style::default().fg(theme::MUTED)),
-            Span::styled("Subtask", Style::default().fg(theme::MUTED)),
+            Span::styled("└ ", Style::default().fg(theme.muted)),
+            Span::styled("Subtask", Style::default().fg(theme.muted)),
         ]));
     }
 
     // Status
-    let status_style = get_status_style(&dt.task.status);
+    let status_style = get_status_style(theme, &dt.task.status);
     lines.push(Line::from(vec![
-        Span::styled("Status: ", Style::default().fg(theme::MUTED)),
+        Span::styled("Status: ", Style::default().fg(theme.muted)),
         Span::styled(dt.task.status.clone(), status_style),
     ]));
 
+    // Status timeline (e.g. "to do 5d ago → in progress 2d ago"), once there's been a change
+    if let Some(history) = history.filter(|h| h.len() > 1) {
+        let now = Utc::now();
+        let mut spans = vec![Span::styled("History: ", Style::default().fg(theme.muted))];
+        for (idx, change) in history.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::styled(" → ", Style::default().fg(theme.muted)));
+            }
+            spans.push(Span::styled(
+                change.status.clone(),
+                get_status_style(theme, &change.status),
+            ));
+            if let Some(since) = change.since {
+                spans.push(Span::styled(
+                    format!(" {}", format_age(since, now)),
+                    Style::default().fg(theme.muted),
+                ));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
     // List
     lines.push(Line::from(vec![
-        Span::styled("List: ", Style::default().fg(theme::MUTED)),
-        Span::styled(dt.task.list_name.clone(), Style::default().fg(theme::FG)),
+        Span::styled("List: ", Style::default().fg(theme.muted)),
+        Span::styled(dt.task.list_name.clone(), Style::default().fg(theme.fg)),
     ]));
 
+    // Subtasks per status (e.g. "3 to do, 1 done")
+    if !subtasks.is_empty() {
+        let breakdown = subtasks
+            .iter()
+            .map(|(status, count)| format!("{} {}", count, status))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(Line::from(vec![
+            Span::styled("Subtasks: ", Style::default().fg(theme.muted)),
+            Span::styled(breakdown, Style::default().fg(theme.fg)),
+        ]));
+    }
+
     // Priority
     if let Some(p) = dt.task.priority_label() {
-        let priority_style = match dt.task.priority {
-            Some(1) => Style::default().fg(theme::ORANGE),
-            Some(2) => Style::default().fg(theme::PURPLE),
-            Some(3) => Style::default().fg(theme::YELLOW),
-            _ => Style::default().fg(theme::MUTED),
-        };
+        let (_, priority_color) = theme.priority_indicator(dt.task.priority);
         lines.push(Line::from(vec![
-            Span::styled("Priority: ", Style::default().fg(theme::MUTED)),
-            Span::styled(p, priority_style),
+            Span::styled("Priority: ", Style::default().fg(theme.muted)),
+            Span::styled(p, Style::default().fg(priority_color)),
         ]));
     }
 
     // Tags
     if !dt.task.tags.is_empty() {
         lines.push(Line::from(vec![
-            Span::styled("Tags: ", Style::default().fg(theme::MUTED)),
-            Span::styled(dt.task.tags.join(", "), Style::default().fg(theme::CYAN)),
+            Span::styled("Tags: ", Style::default().fg(theme.muted)),
+            Span::styled(dt.task.tags.join(", "), Style::default().fg(theme.cyan)),
+        ]));
+    }
+
+    // Created / updated (relative, to spot stale tasks)
+    let now = Utc::now();
+    for (label, timestamp) in [
+        ("Created: ", dt.task.date_created),
+        ("Updated: ", dt.task.date_updated),
+    ] {
+        if let Some(ts) = timestamp {
+            lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(theme.muted)),
+                Span::styled(format_age(ts, now), Style::default().fg(theme.fg)),
+            ]));
+        }
+    }
+
+    // How often the task has been opened from here (e.g. "12×, last 2h ago")
+    if let Some(last) = dt.overlay.last_opened.filter(|_| dt.overlay.open_count > 0) {
+        lines.push(Line::from(vec![
+            Span::styled("Opened: ", Style::default().fg(theme.muted)),
+            Span::styled(
+                format!(
+                    "{}×, last {}",
+                    dt.overlay.open_count,
+                    format_age(last.timestamp_millis(), now)
+                ),
+                Style::default().fg(theme.fg),
+            ),
         ]));
     }
 
@@ -493,25 +1195,137 @@ fn build_preview_content(dt: &DisplayTask, _width: usize) -> Vec<Line<'static>>
     if dt.overlay.pinned {
         lines.push(Line::from(Span::styled(
             "📌 Pinned",
-            Style::default().fg(theme::YELLOW),
+            Style::default().fg(theme.yellow),
         )));
     }
 
+    // What it's waiting on (local only)
+    if let Some(reason) = &dt.overlay.waiting_reason {
+        lines.push(Line::from(vec![
+            Span::styled("⏳ Waiting on: ", Style::default().fg(theme.muted)),
+            Span::styled(reason.clone(), Style::default().fg(theme.yellow)),
+        ]));
+    }
+
+    // Custom fields (e.g. Story Points, Sprint)
+    if !dt.task.custom_fields.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Fields:",
+            Style::default()
+                .fg(theme.muted)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (name, value) in &dt.task.custom_fields {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}: ", name), Style::default().fg(theme.muted)),
+                Span::styled(value.clone(), Style::default().fg(theme.fg)),
+            ]));
+        }
+    }
+
+    // Personal note (local only)
+    if let Some(note) = &dt.overlay.note {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Notes:",
+            Style::default()
+                .fg(theme.yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for line in note.lines() {
+            lines.push(Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(theme.yellow),
+            )));
+        }
+    }
+
     // Description
     if let Some(desc) = &dt.task.description {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "Description:",
             Style::default()
-                .fg(theme::MUTED)
+                .fg(theme.muted)
                 .add_modifier(Modifier::BOLD),
         )));
-        // Show full description (scrollable)
-        for line in desc.lines() {
+        // Rendered Markdown (scrollable), cut short if configured
+        lines.extend(limit_lines(
+            markdown::render(desc, theme),
+            app.preview_max_lines,
+            &app.keymap,
+            theme,
+        ));
+    }
+
+    // Checklists, e.g. "Checklist (2/5)", with the highlighted item (J/K) marked
+    let mut item_idx = 0;
+    for checklist in &dt.task.checklists {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} ({}/{})",
+                checklist.name,
+                checklist.resolved_count(),
+                checklist.items.len()
+            ),
+            Style::default()
+                .fg(theme.muted)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for item in &checklist.items {
+            let (mark, color) = if item.resolved {
+                ("☑", theme.green)
+            } else {
+                ("☐", theme.fg)
+            };
+            let mut style = Style::default().fg(color);
+            if checklist_cursor == Some(item_idx) {
+                style = style.bg(theme.selected_bg).add_modifier(Modifier::BOLD);
+            }
             lines.push(Line::from(Span::styled(
-                line.to_string(),
-                Style::default().fg(theme::FG),
+                format!("{} {}", mark, item.name),
+                style,
             )));
+            item_idx += 1;
+        }
+    }
+
+    // Comments (fetched lazily once the selection settles)
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Comments:",
+        Style::default()
+            .fg(theme.muted)
+            .add_modifier(Modifier::BOLD),
+    )));
+    match comments {
+        None => lines.push(Line::from(Span::styled(
+            "Loading comments...",
+            Style::default().fg(theme.muted),
+        ))),
+        Some(None) => lines.push(Line::from(Span::styled(
+            "Comments unavailable",
+            Style::default().fg(theme.muted),
+        ))),
+        Some(Some(comments)) if comments.is_empty() => lines.push(Line::from(Span::styled(
+            "No comments",
+            Style::default().fg(theme.muted),
+        ))),
+        Some(Some(comments)) => {
+            for comment in comments {
+                lines.push(Line::from(Span::styled(
+                    comment.author.clone(),
+                    Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+                )));
+                for line in comment.text.lines() {
+                    lines.push(Line::from(Span::styled(
+                        line.to_string(),
+                        Style::default().fg(theme.fg),
+                    )));
+                }
+            }
         }
     }
 
@@ -519,10 +1333,11 @@ fn build_preview_content(dt: &DisplayTask, _width: usize) -> Vec<Line<'static>>
 }
 
 /// Render the tab bar
-fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
+fn render_tabs(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     let counts = app.group_counts();
 
-    let titles: Vec<Line> = TaskGroup::all()
+    let titles: Vec<Line> = app
+        .visible_groups
         .iter()
         .map(|&group| {
             let count = counts
@@ -533,62 +1348,73 @@ fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
 
             let style = if group == app.current_group {
                 Style::default()
-                    .fg(theme::TAB_ACTIVE)
+                    .fg(theme.tab_active())
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(theme::TAB_INACTIVE)
+                Style::default().fg(theme.tab_inactive())
             };
 
             Line::from(vec![
                 Span::styled(format!("{} ", group.label()), style),
-                Span::styled(format!("({})", count), Style::default().fg(theme::MUTED)),
+                Span::styled(format!("({})", count), Style::default().fg(theme.muted)),
             ])
         })
         .collect();
 
+    // Title, plus an overdue badge when anything is late
+    let mut title = vec![Span::styled(
+        " ClickUp Tasks ",
+        Style::default().fg(theme.blue).add_modifier(Modifier::BOLD),
+    )];
+    let overdue = app.overdue_count();
+    if overdue > 0 {
+        title.push(Span::styled(
+            format!("· ⚠ {} overdue ", overdue),
+            Style::default()
+                .fg(theme.orange)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     let tabs = Tabs::new(titles)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(theme::MUTED))
-                .title(Span::styled(
-                    " ClickUp Tasks ",
-                    Style::default()
-                        .fg(theme::BLUE)
-                        .add_modifier(Modifier::BOLD),
-                )),
+                .border_style(Style::default().fg(theme.muted))
+                .title(title),
         )
-        .select(app.current_group.index())
-        .style(Style::default().fg(theme::FG))
+        .select(app.current_tab_index())
+        .style(Style::default().fg(theme.fg))
         .highlight_style(
             Style::default()
-                .fg(theme::TAB_ACTIVE)
+                .fg(theme.tab_active())
                 .add_modifier(Modifier::BOLD),
         )
-        .divider(Span::styled(" │ ", Style::default().fg(theme::MUTED)));
+        .divider(Span::styled(" │ ", Style::default().fg(theme.muted)));
 
     frame.render_widget(tabs, area);
 }
 
 /// Get status style color
-fn get_status_style(status: &str) -> Style {
+fn get_status_style(theme: &Theme, status: &str) -> Style {
     match status.to_lowercase().as_str() {
-        "in progress" => Style::default().fg(theme::STATUS_IN_PROGRESS),
-        "to do" | "todo" | "to-do" => Style::default().fg(theme::STATUS_TODO),
-        "to review" | "in review" | "review" => Style::default().fg(theme::STATUS_IN_PROGRESS), // Actionable like in progress
-        "blocked" => Style::default().fg(theme::STATUS_BLOCKED),
-        "in testing" | "testing" => Style::default().fg(theme::STATUS_TESTING),
-        "to validate" | "validation" => Style::default().fg(theme::STATUS_VALIDATE),
-        "backlog" => Style::default().fg(theme::STATUS_BACKLOG),
-        "done" | "completed" | "released" => Style::default().fg(theme::STATUS_DONE),
-        "cancelled" | "canceled" => Style::default().fg(theme::STATUS_CANCELLED),
-        _ => Style::default().fg(theme::FG), // Default to normal text, not muted
+        "in progress" => Style::default().fg(theme.status_in_progress()),
+        "to do" | "todo" | "to-do" => Style::default().fg(theme.status_todo()),
+        "to review" | "in review" | "review" => Style::default().fg(theme.status_in_progress()), // Actionable like in progress
+        "blocked" => Style::default().fg(theme.status_blocked()),
+        "in testing" | "testing" => Style::default().fg(theme.status_testing()),
+        "to validate" | "validation" => Style::default().fg(theme.status_validate()),
+        "backlog" => Style::default().fg(theme.status_backlog()),
+        "done" | "completed" | "released" => Style::default().fg(theme.status_done()),
+        "cancelled" | "canceled" => Style::default().fg(theme.status_cancelled()),
+        _ => Style::default().fg(theme.fg), // Default to normal text, not muted
     }
 }
 
 /// Render the task list (no status sections, status shown inline)
-fn render_task_list(frame: &mut Frame, app: &App, area: Rect) {
+fn render_task_list(frame: &mut Frame, app: &mut App, theme: &Theme, area: Rect) {
     let tasks = app.current_tasks();
+    let collapsed_counts = app.collapsed_counts();
 
     // Build set of task IDs in view for subtask detection
     let visible_ids: std::collections::HashSet<String> =
@@ -599,6 +1425,9 @@ fn render_task_list(frame: &mut Frame, app: &App, area: Rect) {
         tasks.iter().map(|dt| (dt.task.id.clone(), dt)).collect();
 
     let mut items: Vec<ListItem> = Vec::new();
+    let mut heights: Vec<usize> = Vec::new();
+    let wrap_width = area.width.saturating_sub(2) as usize;
+    let now = Utc::now();
 
     for (task_index, dt) in tasks.iter().enumerate() {
         let is_selected = task_index == app.selected_index;
@@ -627,25 +1456,13 @@ fn render_task_list(frame: &mut Frame, app: &App, area: Rect) {
         let pin_icon = if dt.overlay.pinned { "📌" } else { "  " };
 
         // Priority indicator (2 chars)
-        let priority_style = match dt.task.priority {
-            Some(1) => Style::default().fg(theme::ORANGE),
-            Some(2) => Style::default().fg(theme::PURPLE),
-            Some(3) => Style::default().fg(theme::YELLOW),
-            _ => Style::default().fg(theme::MUTED),
-        };
-        let priority_indicator = match dt.task.priority {
-            Some(1) => "!!",
-            Some(2) => "! ",
-            Some(3) => "- ",
-            Some(4) => "· ",
-            _ => "  ",
-        };
+        let (priority_indicator, priority_color) = theme.priority_indicator(dt.task.priority);
 
         // Status tag - gray out if not assigned
         let status_style = if is_assigned {
-            get_status_style(&dt.task.status)
+            get_status_style(theme, &dt.task.status)
         } else {
-            Style::default().fg(theme::MUTED)
+            Style::default().fg(theme.muted)
         };
         let status_tag = format!("[{}] ", dt.task.status);
 
@@ -666,18 +1483,38 @@ fn render_task_list(frame: &mut Frame, app: &App, area: Rect) {
 
         // Name styling - gray out unassigned tasks
         let name_style = if !is_assigned {
-            Style::default().fg(theme::MUTED)
+            Style::default().fg(theme.muted)
         } else if is_selected {
-            Style::default().fg(theme::FG).add_modifier(Modifier::BOLD)
+            Style::default().fg(theme.fg).add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(theme::FG)
+            Style::default().fg(theme.fg)
         };
 
         // Build spans - all tasks start with pin+priority (4 chars), subtasks add indent after
         let mut spans: Vec<Span> = Vec::new();
 
+        // Multi-select marks, in their own column while anything is marked
+        if !app.selected_ids.is_empty() {
+            spans.push(if app.selected_ids.contains(&dt.task.id) {
+                Span::styled("✓ ", Style::default().fg(theme.green))
+            } else {
+                Span::raw("  ")
+            });
+        }
+
+        // List color bar
+        if app.color_by_list {
+            spans.push(Span::styled(
+                "▎",
+                Style::default().fg(theme.list_color(&dt.task.list_name)),
+            ));
+        }
+
         spans.push(Span::raw(pin_icon));
-        spans.push(Span::styled(priority_indicator, priority_style));
+        spans.push(Span::styled(
+            priority_indicator,
+            Style::default().fg(priority_color),
+        ));
         spans.push(Span::raw(" ")); // spacing
 
         // Add depth-based indentation for nested tasks
@@ -686,127 +1523,663 @@ fn render_task_list(frame: &mut Frame, app: &App, area: Rect) {
             let indent = "  ".repeat(depth.saturating_sub(1));
             spans.push(Span::styled(
                 format!("{}└ ", indent),
-                Style::default().fg(theme::MUTED),
+                Style::default().fg(theme.muted),
             ));
         }
 
-        // Status inline
-        spans.push(Span::styled(status_tag, status_style));
+        // Wrapped rows line up under the status tag
+        let indent: usize = spans.iter().map(|s| s.width()).sum();
 
-        // Type tag
-        if !type_tag.is_empty() {
-            spans.push(Span::styled(type_tag, Style::default().fg(theme::PINK)));
+        if app.dense {
+            // Just a bar in the status color
+            spans.push(Span::styled("▌ ", status_style));
+        } else {
+            // Status inline
+            spans.push(Span::styled(status_tag, status_style));
+
+            // Type tag
+            if !type_tag.is_empty() {
+                spans.push(Span::styled(type_tag, Style::default().fg(theme.pink)));
+            }
+
+            // Custom ID with spacing
+            if !custom_id_str.is_empty() {
+                spans.push(Span::styled(custom_id_str, Style::default().fg(theme.cyan)));
+            }
         }
 
-        // Custom ID with spacing
-        if !custom_id_str.is_empty() {
-            spans.push(Span::styled(
-                custom_id_str,
-                Style::default().fg(theme::CYAN),
+        // How long a backlog task has sat untouched
+        if let Some(age) = app.backlog_heat(&dt.task, now) {
+            let color = match age {
+                TaskAge::Fresh => theme.green,
+                TaskAge::Aging => theme.yellow,
+                TaskAge::Stale => theme.orange,
+            };
+            spans.push(Span::styled("● ", Style::default().fg(color)));
+        }
+
+        // Tags after the name: waiting reason, wake time, other assignees, collapsed count
+        let mut suffix: Vec<Span> = Vec::new();
+
+        // What it's waiting on
+        if let Some(reason) = &dt.overlay.waiting_reason {
+            suffix.push(Span::styled(
+                format!(" ⏳ {}", reason),
+                Style::default().fg(theme.yellow),
             ));
         }
 
-        // Task name
-        spans.push(Span::styled(dt.task.name.clone(), name_style));
+        // When a snoozed task comes back
+        if let Some(until) = dt
+            .overlay
+            .snoozed_until
+            .filter(|_| app.current_group == TaskGroup::Snoozed)
+        {
+            suffix.push(match format_wake(until, now) {
+                Some(wake) => {
+                    Span::styled(format!(" · {}", wake), Style::default().fg(theme.muted))
+                }
+                None => Span::styled(" · waking now", Style::default().fg(theme.green)),
+            });
+        }
+
+        // Other people on the task
+        for initials in dt.task.other_assignee_initials(app.user_id) {
+            suffix.push(Span::styled(
+                format!(" [{}]", initials),
+                Style::default().fg(theme.pink),
+            ));
+        }
 
-        let line = Line::from(spans);
+        // Collapsed subtasks
+        if let Some(&count) = collapsed_counts.get(&dt.task.id) {
+            let noun = if count == 1 { "subtask" } else { "subtasks" };
+            suffix.push(Span::styled(
+                format!(" ▸ {} {}", count, noun),
+                Style::default().fg(theme.muted),
+            ));
+        }
+
+        // Task name (with the live filter's matches highlighted); without wrapping,
+        // it's cut to the columns the rest of the row leaves it
+        let name = if app.wrap_task_names {
+            dt.task.name.clone()
+        } else {
+            let used: usize = spans.iter().chain(&suffix).map(|s| s.width()).sum();
+            let budget = wrap_width.saturating_sub(used).max(MIN_NAME_WIDTH);
+            truncate_to_width(&dt.task.name, budget)
+        };
+        let matched = shown_positions(
+            &dt.task.name,
+            &name,
+            substring_positions(&dt.task.name, &app.search_query),
+        );
+        spans.extend(highlight_matches(&name, &matched, name_style, theme));
+        spans.extend(suffix);
+
+        let text = if app.wrap_task_names {
+            Text::from(wrap_spans(spans, wrap_width, indent))
+        } else {
+            Text::from(Line::from(spans))
+        };
+        heights.push(text.height());
+        // The selection background covers every wrapped row
         let item = if is_selected {
-            ListItem::new(line).style(Style::default().bg(theme::SELECTED_BG))
+            ListItem::new(text).style(Style::default().bg(theme.selected_bg))
         } else {
-            ListItem::new(line)
+            ListItem::new(text)
         };
         items.push(item);
     }
+    app.task_list_heights = heights;
 
     let title = if app.input_mode == InputMode::Search {
-        format!(" Search: {} ", app.search_query)
+        let noun = if tasks.len() == 1 { "match" } else { "matches" };
+        format!(" Search: {} · {} {} ", app.search_query, tasks.len(), noun)
     } else if tasks.is_empty() {
         " No tasks ".to_string()
+    } else if !app.selected_ids.is_empty() {
+        format!(
+            " {} tasks · by {} · {} selected ",
+            tasks.len(),
+            app.sort_mode.label(),
+            app.selected_ids.len()
+        )
+    } else if let Some(days) = app
+        .done_window()
+        .filter(|_| app.current_group == TaskGroup::Done)
+    {
+        format!(
+            " {} tasks · by {} · last {}d ",
+            tasks.len(),
+            app.sort_mode.label(),
+            days
+        )
     } else {
-        format!(" {} tasks ", tasks.len())
+        format!(" {} tasks · by {} ", tasks.len(), app.sort_mode.label())
     };
 
     let border_color = if app.focused_pane == FocusedPane::TaskList {
-        theme::CYAN
+        theme.cyan
     } else {
-        theme::MUTED
+        theme.muted
     };
 
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color))
-            .title(Span::styled(title, Style::default().fg(theme::FG))),
+            .title(Span::styled(title, Style::default().fg(theme.fg))),
     );
 
-    frame.render_widget(list, area);
+    // Keep the selection in view, remembering the offset for mouse hit-testing
+    let mut state = ListState::default()
+        .with_offset(app.task_list_offset)
+        .with_selected(Some(app.selected_index));
+    frame.render_stateful_widget(list, area, &mut state);
+    app.task_list_offset = state.offset();
+
+    render_scrollbar(
+        frame,
+        theme,
+        area,
+        tasks.len(),
+        app.selected_index,
+        tasks.len(),
+    );
+}
+
+/// Split `text` into spans, emphasizing the chars at `positions` (char indices)
+fn highlight_matches(
+    text: &str,
+    positions: &[usize],
+    style: Style,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    if positions.is_empty() {
+        return vec![Span::styled(text.to_string(), style)];
+    }
+
+    let matched_style = style.fg(theme.cyan).add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (idx, c) in text.chars().enumerate() {
+        let is_match = positions.contains(&idx);
+        if is_match != run_matched && !run.is_empty() {
+            let run_style = if run_matched { matched_style } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), run_style));
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let run_style = if run_matched { matched_style } else { style };
+        spans.push(Span::styled(run, run_style));
+    }
+    spans
+}
+
+/// Match positions in `full` that are still on screen once it's cut to `shown`
+/// by [`truncate_to_width`] (the "..." it ends in never counts as a match)
+fn shown_positions(full: &str, shown: &str, positions: Vec<usize>) -> Vec<usize> {
+    if shown == full {
+        return positions;
+    }
+    let kept = shown.chars().count().saturating_sub(3);
+    positions.into_iter().filter(|&idx| idx < kept).collect()
+}
+
+/// Columns a task name keeps before the tags after it start getting clipped instead
+const MIN_NAME_WIDTH: usize = 12;
+
+/// Cut `text` to at most `max_width` display columns, ending in "..." when cut
+///
+/// Measures columns rather than chars, so wide (CJK, emoji) glyphs count double
+/// and are never split.
+fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let budget = max_width.saturating_sub(3);
+    let mut kept = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        kept.push(c);
+    }
+    kept.push_str(&"..."[..max_width.min(3)]);
+    kept
+}
+
+/// Break a line's spans into rows at most `width` columns wide, keeping each
+/// span's style; rows after the first start with `indent` spaces
+fn wrap_spans<'a>(spans: Vec<Span<'a>>, width: usize, indent: usize) -> Vec<Line<'a>> {
+    if width == 0 {
+        return vec![Line::from(spans)];
+    }
+    // Skip the indent when it would leave little room for the text
+    let indent = if indent * 2 > width { 0 } else { indent };
+
+    let mut lines = Vec::new();
+    let mut row: Vec<Span<'a>> = Vec::new();
+    let mut used = 0;
+    for span in spans {
+        let mut run = String::new();
+        for c in span.content.chars() {
+            let char_width = Span::raw(c.to_string()).width();
+            if used + char_width > width && used > indent {
+                if !run.is_empty() {
+                    row.push(Span::styled(std::mem::take(&mut run), span.style));
+                }
+                lines.push(Line::from(std::mem::take(&mut row)));
+                row.push(Span::raw(" ".repeat(indent)));
+                used = indent;
+            }
+            run.push(c);
+            used += char_width;
+        }
+        if !run.is_empty() {
+            row.push(Span::styled(run, span.style));
+        }
+    }
+    lines.push(Line::from(row));
+    lines
+}
+
+/// Status bar spans for the running timer (elapsed time and task)
+fn timer_spans<'a>(app: &'a App, theme: &Theme) -> Vec<Span<'a>> {
+    match (&app.timer, app.timer_elapsed()) {
+        (Some(timer), Some(elapsed)) => vec![
+            Span::styled(
+                format!("⏱ {} ", elapsed),
+                Style::default()
+                    .fg(theme.green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(&timer.task_name, Style::default().fg(theme.fg)),
+            Span::styled(" │ ", Style::default().fg(theme.muted)),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+/// Status bar spans describing active task-list filters
+fn active_filter_spans<'a>(app: &'a App, theme: &Theme) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    if let Some(list) = &app.active_list_filter {
+        spans.push(Span::styled("List: ", Style::default().fg(theme.muted)));
+        spans.push(Span::styled(list, Style::default().fg(theme.cyan)));
+        spans.push(Span::styled(" │ ", Style::default().fg(theme.muted)));
+    }
+    if let Some(view) = &app.person_view {
+        spans.push(Span::styled("Person: ", Style::default().fg(theme.muted)));
+        spans.push(Span::styled(&view.name, Style::default().fg(theme.pink)));
+        spans.push(Span::styled(" │ ", Style::default().fg(theme.muted)));
+    }
+    if let Some(tag) = &app.active_tag_filter {
+        spans.push(Span::styled("Tag: ", Style::default().fg(theme.muted)));
+        spans.push(Span::styled(tag, Style::default().fg(theme.cyan)));
+        spans.push(Span::styled(" │ ", Style::default().fg(theme.muted)));
+    }
+    if !spans.is_empty() {
+        spans.push(Span::styled("[Esc]", Style::default().fg(theme.blue)));
+        spans.push(Span::styled(" clear │ ", Style::default().fg(theme.muted)));
+    }
+    spans
 }
 
 /// Render the status bar
-fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+fn render_status_bar(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     let content = match app.input_mode {
         InputMode::Normal => {
             if let Some(msg) = &app.status_message {
-                Line::from(vec![Span::styled(msg, Style::default().fg(theme::GREEN))])
-            } else if app.is_loading {
+                Line::from(vec![Span::styled(msg, Style::default().fg(theme.green))])
+            } else if let Some(spinner) = app.spinner_frame(Instant::now()) {
                 Line::from(vec![Span::styled(
-                    "Loading...",
-                    Style::default().fg(theme::YELLOW),
+                    format!("{} Loading tasks...", spinner),
+                    Style::default().fg(theme.yellow),
                 )])
             } else {
-                // Keybinding hints
-                Line::from(vec![
-                    Span::styled("[j/k]", Style::default().fg(theme::BLUE)),
-                    Span::styled(" nav ", Style::default().fg(theme::MUTED)),
-                    Span::styled("[h/l]", Style::default().fg(theme::BLUE)),
-                    Span::styled(" tabs ", Style::default().fg(theme::MUTED)),
-                    Span::styled("[p]", Style::default().fg(theme::BLUE)),
-                    Span::styled("in ", Style::default().fg(theme::MUTED)),
-                    Span::styled("[s]", Style::default().fg(theme::BLUE)),
-                    Span::styled("nooze ", Style::default().fg(theme::MUTED)),
-                    Span::styled("[o]", Style::default().fg(theme::BLUE)),
-                    Span::styled("pen ", Style::default().fg(theme::MUTED)),
-                    Span::styled("[y]", Style::default().fg(theme::BLUE)),
-                    Span::styled("ank ", Style::default().fg(theme::MUTED)),
-                    Span::styled("[/]", Style::default().fg(theme::BLUE)),
-                    Span::styled("search ", Style::default().fg(theme::MUTED)),
-                    Span::styled("[r]", Style::default().fg(theme::BLUE)),
-                    Span::styled("efresh ", Style::default().fg(theme::MUTED)),
-                    Span::styled("[?]", Style::default().fg(theme::BLUE)),
-                    Span::styled("help ", Style::default().fg(theme::MUTED)),
-                    Span::styled("[q]", Style::default().fg(theme::BLUE)),
-                    Span::styled("uit", Style::default().fg(theme::MUTED)),
-                ])
+                // Config warning, running timer, active filters, then keybinding hints
+                let mut spans = Vec::new();
+                if app.user_id.is_none() {
+                    spans.push(Span::styled(
+                        "⚠ user_id not numeric │ ",
+                        Style::default().fg(theme.yellow),
+                    ));
+                }
+                spans.extend(timer_spans(app, theme));
+                spans.extend(active_filter_spans(app, theme));
+                spans.extend([
+                    Span::styled("[j/k]", Style::default().fg(theme.blue)),
+                    Span::styled(" nav ", Style::default().fg(theme.muted)),
+                    Span::styled("[h/l]", Style::default().fg(theme.blue)),
+                    Span::styled(" tabs ", Style::default().fg(theme.muted)),
+                    Span::styled("[p]", Style::default().fg(theme.blue)),
+                    Span::styled("in ", Style::default().fg(theme.muted)),
+                    Span::styled("[s]", Style::default().fg(theme.blue)),
+                    Span::styled("nooze ", Style::default().fg(theme.muted)),
+                    Span::styled("[o]", Style::default().fg(theme.blue)),
+                    Span::styled("pen ", Style::default().fg(theme.muted)),
+                    Span::styled("[y]", Style::default().fg(theme.blue)),
+                    Span::styled("ank ", Style::default().fg(theme.muted)),
+                    Span::styled("[/]", Style::default().fg(theme.blue)),
+                    Span::styled("search ", Style::default().fg(theme.muted)),
+                    Span::styled("[r]", Style::default().fg(theme.blue)),
+                    Span::styled("efresh ", Style::default().fg(theme.muted)),
+                    Span::styled("[?]", Style::default().fg(theme.blue)),
+                    Span::styled("help ", Style::default().fg(theme.muted)),
+                    Span::styled("[q]", Style::default().fg(theme.blue)),
+                    Span::styled("uit", Style::default().fg(theme.muted)),
+                ]);
+                Line::from(spans)
             }
         }
         InputMode::Search => Line::from(vec![
-            Span::styled("[j/k]", Style::default().fg(theme::BLUE)),
-            Span::styled(" select ", Style::default().fg(theme::MUTED)),
-            Span::styled("[Enter]", Style::default().fg(theme::BLUE)),
-            Span::styled(" open ", Style::default().fg(theme::MUTED)),
-            Span::styled("[Esc]", Style::default().fg(theme::BLUE)),
-            Span::styled(" cancel", Style::default().fg(theme::MUTED)),
+            Span::styled("[j/k]", Style::default().fg(theme.blue)),
+            Span::styled(" select ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" open ", Style::default().fg(theme.muted)),
+            Span::styled("[Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
         ]),
         InputMode::Snooze => Line::from(vec![
-            Span::styled("Days: ", Style::default().fg(theme::MUTED)),
-            Span::styled(&app.snooze_input, Style::default().fg(theme::FG)),
+            Span::styled(
+                "Snooze (days, YYYY-MM-DD, tomorrow, next week, weekday): ",
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(&app.snooze_input, Style::default().fg(theme.fg)),
             Span::styled(" ", Style::default()),
-            Span::styled("[Esc]", Style::default().fg(theme::BLUE)),
-            Span::styled(" cancel, ", Style::default().fg(theme::MUTED)),
-            Span::styled("[Enter]", Style::default().fg(theme::BLUE)),
-            Span::styled(" confirm", Style::default().fg(theme::MUTED)),
+            Span::styled("[Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel, ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" confirm", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::WaitingReason => Line::from(vec![
+            Span::styled("Waiting on: ", Style::default().fg(theme.muted)),
+            Span::styled(&app.waiting_input, Style::default().fg(theme.fg)),
+            Span::styled("│ ", Style::default().fg(theme.blue)), // cursor
+            Span::styled("[Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel, ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" save (empty removes)", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::Note => Line::from(vec![
+            Span::styled("Note: ", Style::default().fg(theme.muted)),
+            Span::styled(
+                app.note_input.replace('\n', " ⏎ "),
+                Style::default().fg(theme.fg),
+            ),
+            Span::styled("│ ", Style::default().fg(theme.blue)), // cursor
+            Span::styled("[Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel, ", Style::default().fg(theme.muted)),
+            Span::styled("[Alt+Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" new line, ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" save (empty removes)", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::Comment => Line::from(vec![
+            Span::styled("Comment: ", Style::default().fg(theme.muted)),
+            Span::styled(
+                app.comment_input.replace('\n', " ⏎ "),
+                Style::default().fg(theme.fg),
+            ),
+            Span::styled("│ ", Style::default().fg(theme.blue)), // cursor
+            Span::styled("[Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel, ", Style::default().fg(theme.muted)),
+            Span::styled("[Alt+Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" new line, ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" post", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::TagFilter => Line::from(vec![
+            Span::styled("Filter by tag: ", Style::default().fg(theme.muted)),
+            Span::styled(&app.tag_filter_input, Style::default().fg(theme.fg)),
+            Span::styled("│ ", Style::default().fg(theme.blue)), // cursor
+            Span::styled("[Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel, ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" apply (empty clears)", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::JumpToId => Line::from(vec![
+            Span::styled("Jump to: ", Style::default().fg(theme.muted)),
+            Span::styled(&app.jump_input, Style::default().fg(theme.fg)),
+            Span::styled("│ ", Style::default().fg(theme.blue)), // cursor
+            Span::styled("[Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel, ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" go to custom ID", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::ListPicker => Line::from(vec![
+            Span::styled("[↑/↓]", Style::default().fg(theme.blue)),
+            Span::styled(" select ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" filter ", Style::default().fg(theme.muted)),
+            Span::styled("[Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::PriorityPicker => Line::from(vec![
+            Span::styled("[j/k]", Style::default().fg(theme.blue)),
+            Span::styled(" select ", Style::default().fg(theme.muted)),
+            Span::styled("[1-4/0]", Style::default().fg(theme.blue)),
+            Span::styled(" pick ", Style::default().fg(theme.muted)),
+            Span::styled("[Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" set ", Style::default().fg(theme.muted)),
+            Span::styled("[Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::Confirm => Line::from(vec![
+            Span::styled("[y/Enter]", Style::default().fg(theme.blue)),
+            Span::styled(" confirm ", Style::default().fg(theme.muted)),
+            Span::styled("[n/Esc]", Style::default().fg(theme.blue)),
+            Span::styled(" cancel", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::QrCode => Line::from(vec![
+            Span::styled("[Esc/q]", Style::default().fg(theme.blue)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
+        ]),
+        InputMode::MessageLog => Line::from(vec![
+            Span::styled("[Esc/q]", Style::default().fg(theme.blue)),
+            Span::styled(" close", Style::default().fg(theme.muted)),
         ]),
         InputMode::Help => Line::from(vec![
-            Span::styled("[Esc/q/?]", Style::default().fg(theme::BLUE)),
-            Span::styled(" close help", Style::default().fg(theme::MUTED)),
+            Span::styled("[Esc/q/?]", Style::default().fg(theme.blue)),
+            Span::styled(" close help", Style::default().fg(theme.muted)),
         ]),
     };
 
-    let paragraph = Paragraph::new(content).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::MUTED)),
-    );
+    // How stale the list is, on the right in Normal mode when there's room
+    let freshness = (app.input_mode == InputMode::Normal)
+        .then(|| {
+            Span::styled(
+                app.freshness_label(Utc::now()),
+                Style::default().fg(theme.muted),
+            )
+        })
+        .filter(|label| {
+            content.width() + label.width() + 2 <= area.width.saturating_sub(2) as usize
+        });
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.muted));
+    let inner = block.inner(area);
+    let paragraph = Paragraph::new(content).block(block);
 
     frame.render_widget(paragraph, area);
+    if let Some(label) = freshness {
+        frame.render_widget(
+            Paragraph::new(Line::from(label)).alignment(Alignment::Right),
+            inner,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keymap::KeySpec;
+    use crate::models::{Task, TaskOverlay};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_centered_rect_percentages_and_limits() {
+        let area = Rect::new(0, 0, 100, 50);
+        // 70% x 80% fits under the limits
+        assert_eq!(
+            centered_rect(70, 80, 80, 45, area),
+            Rect::new(15, 5, 70, 40)
+        );
+        // Limits win over the percentages
+        assert_eq!(
+            centered_rect(70, 80, 60, 20, area),
+            Rect::new(20, 15, 60, 20)
+        );
+    }
+
+    #[test]
+    fn test_centered_rect_fixed_size_is_clipped() {
+        let area = Rect::new(0, 0, 20, 5);
+        assert_eq!(centered_rect(100, 100, 10, 3, area), Rect::new(5, 1, 10, 3));
+        assert_eq!(centered_rect(100, 100, 30, 8, area), area);
+    }
+
+    #[test]
+    fn test_limit_lines() {
+        let theme = Theme::dark();
+        let lines =
+            || -> Vec<Line<'static>> { (0..5).map(|i| Line::from(i.to_string())).collect() };
+
+        let keymap = Keymap::default();
+
+        assert_eq!(limit_lines(lines(), None, &keymap, &theme).len(), 5);
+        assert_eq!(limit_lines(lines(), Some(5), &keymap, &theme).len(), 5);
+
+        let limited = limit_lines(lines(), Some(2), &keymap, &theme);
+        assert_eq!(limited.len(), 3);
+        assert_eq!(limited[1], Line::from("1"));
+        assert_eq!(
+            limited[2].to_string(),
+            "… (press o to open full in browser)"
+        );
+
+        // The hint names whichever key opens the task
+        let overrides = HashMap::from([("open".to_string(), KeySpec::One("O".to_string()))]);
+        let keymap = Keymap::from_config(&overrides).unwrap();
+        let limited = limit_lines(lines(), Some(2), &keymap, &theme);
+        assert_eq!(
+            limited[2].to_string(),
+            "… (press O to open full in browser)"
+        );
+    }
+
+    #[test]
+    fn test_centered_rect_offset_area() {
+        let area = Rect::new(10, 4, 40, 20);
+        assert_eq!(
+            centered_rect(50, 50, 100, 100, area),
+            Rect::new(20, 9, 20, 10)
+        );
+    }
+
+    #[test]
+    fn test_truncate_to_width_counts_columns() {
+        // CJK and emoji are two columns each and are never split
+        assert_eq!(truncate_to_width("漢字テスト", 10), "漢字テスト");
+        assert_eq!(truncate_to_width("漢字テスト", 7), "漢字...");
+        assert_eq!(truncate_to_width("漢字テスト", 6), "漢...");
+        assert_eq!(truncate_to_width("🚀 Launch 火箭", 10), "🚀 Laun...");
+        for max in 0..16 {
+            assert!(truncate_to_width("🚀 Launch 火箭", max).width() <= max);
+        }
+        assert_eq!(truncate_to_width("short", 20), "short");
+    }
+
+    #[test]
+    fn test_help_lists_every_action_with_its_bound_keys() {
+        for action in Action::all() {
+            let listed = HELP_SECTIONS.iter().any(|(_, rows)| {
+                rows.iter().any(|(keys, _)| {
+                    matches!(keys, HelpKeys::Bound(actions) if actions.contains(action))
+                })
+            });
+            assert!(listed, "{} missing from help", action.name());
+        }
+
+        let overrides = HashMap::from([(
+            "open".to_string(),
+            KeySpec::Many(vec!["ctrl-e".to_string(), "enter".to_string()]),
+        )]);
+        let keymap = Keymap::from_config(&overrides).unwrap();
+        let theme = Theme::default();
+        let text: Vec<String> = help_key_lines(&keymap, &theme)
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text
+            .iter()
+            .any(|l| l.contains("Ctrl+e, Enter") && l.ends_with("Open task in browser")));
+        assert!(text.iter().any(|l| l.contains("{N}j/k/G")));
+    }
+
+    #[test]
+    fn test_scroll_bounds_exclude_borders() {
+        let lines: Vec<Line> = (0..10).map(|i| Line::from(format!("line {}", i))).collect();
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL));
+
+        // 4 rows inside the border: scrolling 6 shows the last line at the bottom
+        assert_eq!(scroll_bounds(&paragraph, Rect::new(0, 0, 20, 6)), (10, 6));
+        // Everything fits: no scrolling
+        assert_eq!(scroll_bounds(&paragraph, Rect::new(0, 0, 20, 12)), (10, 0));
+        assert_eq!(scroll_bounds(&paragraph, Rect::new(0, 0, 20, 30)), (10, 0));
+    }
+
+    #[test]
+    fn test_shown_positions_skip_cut_text() {
+        let full = "Fix login bug";
+        let shown = truncate_to_width(full, 8);
+        assert_eq!(shown, "Fix l...");
+        // "bug" was cut, so only "l" stays highlighted (never the dots)
+        assert_eq!(shown_positions(full, &shown, vec![4, 5, 10]), vec![4]);
+        assert_eq!(shown_positions(full, full, vec![4, 10]), vec![4, 10]);
+        assert!(shown_positions(full, &truncate_to_width(full, 2), vec![0]).is_empty());
+    }
+
+    #[test]
+    fn test_preview_status_timeline() {
+        let theme = Theme::default();
+        let mut app = App::new();
+        let dt = DisplayTask::new(Task::test("a"), TaskOverlay::default());
+        let hours_ago = |h: i64| Some((Utc::now() - chrono::Duration::hours(h)).timestamp_millis());
+        let change = |status: &str, since: Option<i64>| StatusChange {
+            status: status.to_string(),
+            since,
+        };
+        let mut timeline = |history: &[StatusChange]| -> Option<String> {
+            app.set_status_history("a", Some(history.to_vec()));
+            build_preview_content(&dt, &app, None, &theme)
+                .iter()
+                .map(|line| line.to_string())
+                .find(|line| line.starts_with("History: "))
+        };
+
+        let history = [
+            change("to do", hours_ago(48)),
+            change("in progress", None),
+            change("blocked", hours_ago(4)),
+        ];
+        assert_eq!(
+            timeline(&history).as_deref(),
+            Some("History: to do 2d ago → in progress → blocked 4h ago")
+        );
+        // A task that never changed status has no timeline
+        assert_eq!(timeline(&history[..1]), None);
+    }
 }